@@ -48,6 +48,7 @@ impl Simulation for CascadeSnrMarginSim {
                 noise_figure_db: s.1,
                 output_p1db_dbm: None,
                 output_ip3_dbm: None,
+                ..Default::default()
             },
             Block {
                 name: "IF Amp".to_string(),
@@ -55,6 +56,7 @@ impl Simulation for CascadeSnrMarginSim {
                 noise_figure_db: s.3,
                 output_p1db_dbm: None,
                 output_ip3_dbm: None,
+                ..Default::default()
             },
             Block {
                 name: "Filter".to_string(),
@@ -62,6 +64,7 @@ impl Simulation for CascadeSnrMarginSim {
                 noise_figure_db: 2.0,
                 output_p1db_dbm: None,
                 output_ip3_dbm: None,
+                ..Default::default()
             },
         ];
 
@@ -20,6 +20,7 @@ fn run() {
         frequency_hz: 1.0e9,
         bandwidth_hz: 1.0e6, // Hz, leave as 0.0 or omit for CW
         noise_temperature_k: None,
+        ..Default::default()
     };
 
     let cable_from_signal_generator = Block {
@@ -28,6 +29,7 @@ fn run() {
         noise_figure_db: 6.0,
         output_p1db_dbm: None,
         output_ip3_dbm: None,
+        ..Default::default()
     };
 
     let line_amp: Block = Block {
@@ -36,6 +38,7 @@ fn run() {
         noise_figure_db: 6.0,
         output_p1db_dbm: None,
         output_ip3_dbm: None,
+        ..Default::default()
     };
 
     let cable_run_to_spectrum_analyzer: Block = Block {
@@ -44,6 +47,7 @@ fn run() {
         noise_figure_db: 6.0,
         output_p1db_dbm: None,
         output_ip3_dbm: None,
+        ..Default::default()
     };
 
     let blocks = vec![
@@ -0,0 +1,344 @@
+use std::fmt;
+
+use crate::aliasing;
+use crate::block::{Block, BlockKind};
+use crate::constants;
+
+/// Reference temperature in Kelvin for the converter's noise figure.
+const T0_K: f64 = 290.0;
+
+/// An analog-to-digital converter at the end of the analog lineup.
+///
+/// `snr_db` is the datasheet SNR of a full-scale sine over the first Nyquist zone
+/// (quantization and thermal noise). That noise is spread evenly from 0 to `fs/2`, so a
+/// signal of bandwidth `B` only sees `10·log10(fs / 2B)` dB less of it, the processing
+/// gain. Aperture jitter adds noise that scales with the signal: `SNR = −20·log10(2π·f·σ)`
+/// for a sine at `f`. The converter's noise figure is its noise density against
+/// `k·T0`, so it only depends on the full scale, sample rate and SNR.
+///
+/// The converter clips when the signal peaks, its power plus the envelope
+/// peak-to-average power ratio ([`Input::papr_db`](crate::Input::papr_db)), exceed
+/// `full_scale_dbm`, the power of a full-scale sine.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::Adc;
+///
+/// // 14-bit class converter: +4 dBm full scale, 11.5 ENOB at 500 MSPS
+/// let adc = Adc::from_enob(4.0, 500.0e6, 11.5).with_aperture_jitter_s(100.0e-15);
+/// assert!((adc.snr_db - 70.99).abs() < 0.01);
+/// assert!((adc.noise_figure_db() - 23.0).abs() < 0.1);
+/// // a 10 MHz channel gets 14 dB of processing gain
+/// assert!((adc.processing_gain_db(10.0e6) - 13.98).abs() < 0.01);
+/// ```
+#[doc(alias = "ADC")]
+#[doc(alias = "digitizer")]
+#[doc(alias = "converter")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Adc {
+    /// Input power of a full-scale sine in dBm.
+    pub full_scale_dbm: f64,
+    /// Sample rate in Hz.
+    pub sample_rate_hz: f64,
+    /// SNR of a full-scale sine over the first Nyquist zone in dB, without jitter.
+    pub snr_db: f64,
+    /// Spur-free dynamic range in dB, which caps the `sfdr_db` of the nodes after it.
+    pub sfdr_db: Option<f64>,
+    /// RMS aperture (and sample clock) jitter in seconds.
+    pub aperture_jitter_s: Option<f64>,
+    /// Bandwidth of the analog noise reaching the input, from DC, in Hz. Noise outside the
+    /// signal's Nyquist zone but below it aliases onto the signal.
+    pub noise_bandwidth_hz: Option<f64>,
+}
+
+impl fmt::Display for Adc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Adc {{ full_scale: {} dBm, sample_rate: {} Hz, snr: {} dB }}",
+            self.full_scale_dbm, self.sample_rate_hz, self.snr_db
+        )
+    }
+}
+
+impl Adc {
+    /// Create a converter from its full-scale power, sample rate and full-scale SNR.
+    #[must_use]
+    pub fn new(full_scale_dbm: f64, sample_rate_hz: f64, snr_db: f64) -> Self {
+        Self {
+            full_scale_dbm,
+            sample_rate_hz,
+            snr_db,
+            sfdr_db: None,
+            aperture_jitter_s: None,
+            noise_bandwidth_hz: None,
+        }
+    }
+
+    /// Create a converter from its effective number of bits, `SNR = 6.02·ENOB + 1.76 dB`.
+    #[doc(alias = "ENOB")]
+    #[must_use]
+    pub fn from_enob(full_scale_dbm: f64, sample_rate_hz: f64, enob: f64) -> Self {
+        Self::new(full_scale_dbm, sample_rate_hz, 6.02 * enob + 1.76)
+    }
+
+    /// Set the spur-free dynamic range in dB.
+    #[must_use]
+    pub fn with_sfdr_db(mut self, sfdr_db: f64) -> Self {
+        self.sfdr_db = Some(sfdr_db);
+        self
+    }
+
+    /// Set the RMS aperture jitter in seconds.
+    #[must_use]
+    pub fn with_aperture_jitter_s(mut self, aperture_jitter_s: f64) -> Self {
+        self.aperture_jitter_s = Some(aperture_jitter_s);
+        self
+    }
+
+    /// Set the bandwidth of the analog noise reaching the input in Hz, such as the
+    /// converter's full-power input bandwidth.
+    #[must_use]
+    pub fn with_noise_bandwidth_hz(mut self, noise_bandwidth_hz: f64) -> Self {
+        self.noise_bandwidth_hz = Some(noise_bandwidth_hz);
+        self
+    }
+
+    /// Effective number of bits from the SNR.
+    #[must_use]
+    pub fn enob(&self) -> f64 {
+        (self.snr_db - 1.76) / 6.02
+    }
+
+    /// Width of the first Nyquist zone, `fs/2`, in Hz.
+    #[must_use]
+    pub fn nyquist_bandwidth_hz(&self) -> f64 {
+        self.sample_rate_hz / 2.0
+    }
+
+    /// Processing gain in dB for a signal of `bandwidth_hz`: `10·log10(fs / 2B)`.
+    #[must_use]
+    pub fn processing_gain_db(&self, bandwidth_hz: f64) -> f64 {
+        10.0 * (self.nyquist_bandwidth_hz() / bandwidth_hz).log10()
+    }
+
+    /// Quantization and thermal noise density in dBm/Hz.
+    #[must_use]
+    pub fn noise_density_dbm_per_hz(&self) -> f64 {
+        self.full_scale_dbm - self.snr_db - 10.0 * self.nyquist_bandwidth_hz().log10()
+    }
+
+    /// SNR in dB set by aperture jitter alone for a sine at `frequency_hz`, if the jitter
+    /// is specified.
+    #[must_use]
+    pub fn jitter_snr_db(&self, frequency_hz: f64) -> Option<f64> {
+        let jitter_s = self.aperture_jitter_s?;
+        Some(-20.0 * (2.0 * std::f64::consts::PI * frequency_hz * jitter_s).log10())
+    }
+
+    /// Total noise density in dBm/Hz for a signal of `signal_power_dbm` at `frequency_hz`:
+    /// quantization and thermal noise plus the jitter noise of that signal.
+    #[must_use]
+    pub fn noise_density_at(&self, signal_power_dbm: f64, frequency_hz: f64) -> f64 {
+        let quantization_w = rfconversions::power::dbm_to_watts(self.noise_density_dbm_per_hz());
+        let jitter_w = self.jitter_snr_db(frequency_hz).map_or(0.0, |snr_db| {
+            rfconversions::power::dbm_to_watts(
+                signal_power_dbm - snr_db - 10.0 * self.nyquist_bandwidth_hz().log10(),
+            )
+        });
+        rfconversions::power::watts_to_dbm(quantization_w + jitter_w)
+    }
+
+    /// Effective noise figure in dB from the quantization and thermal noise density.
+    #[must_use]
+    pub fn noise_figure_db(&self) -> f64 {
+        noise_figure_from_density(self.noise_density_dbm_per_hz())
+    }
+
+    /// Effective noise figure in dB with the jitter noise of a signal of
+    /// `signal_power_dbm` at `frequency_hz`.
+    #[must_use]
+    pub fn noise_figure_db_at(&self, signal_power_dbm: f64, frequency_hz: f64) -> f64 {
+        noise_figure_from_density(self.noise_density_at(signal_power_dbm, frequency_hz))
+    }
+
+    /// Frequencies below the noise bandwidth that sampling folds onto `frequency_hz`, in
+    /// ascending order and without `frequency_hz` itself: `k·fs ± f` for the signal folded
+    /// into the first Nyquist zone. Empty without a noise bandwidth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::Adc;
+    ///
+    /// let adc = Adc::new(4.0, 100.0e6, 70.0).with_noise_bandwidth_hz(250.0e6);
+    /// assert_eq!(adc.alias_frequencies_hz(20.0e6), vec![80.0e6, 120.0e6, 180.0e6, 220.0e6]);
+    /// // undersampling in the second Nyquist zone: 20 MHz folds onto 80 MHz too
+    /// assert_eq!(adc.alias_frequencies_hz(80.0e6), vec![20.0e6, 120.0e6, 180.0e6, 220.0e6]);
+    /// ```
+    #[doc(alias = "aliasing")]
+    #[doc(alias = "Nyquist zone")]
+    #[must_use]
+    pub fn alias_frequencies_hz(&self, frequency_hz: f64) -> Vec<f64> {
+        match self.noise_bandwidth_hz {
+            Some(noise_bandwidth_hz) => aliasing::alias_frequencies_hz(
+                self.sample_rate_hz,
+                noise_bandwidth_hz,
+                frequency_hz,
+            ),
+            None => vec![],
+        }
+    }
+
+    /// Margin in dB from the signal peaks (`signal_power_dbm + papr_db`) to full scale;
+    /// negative when the converter clips.
+    #[doc(alias = "clipping")]
+    #[must_use]
+    pub fn headroom_db(&self, signal_power_dbm: f64, papr_db: f64) -> f64 {
+        self.full_scale_dbm - (signal_power_dbm + papr_db)
+    }
+}
+
+/// Noise figure of a two-port adding `density_dbm_per_hz` of noise at its input.
+pub(crate) fn noise_figure_from_density(density_dbm_per_hz: f64) -> f64 {
+    let density_w = rfconversions::power::dbm_to_watts(density_dbm_per_hz);
+    10.0 * (1.0 + density_w / (constants::BOLTZMANN * T0_K)).log10()
+}
+
+/// `block` with the noise figure of its converter, if it is one, for a signal of
+/// `signal_power_dbm` at `frequency_hz` (aperture jitter depends on the signal).
+pub(crate) fn at_signal(block: &Block, signal_power_dbm: f64, frequency_hz: f64) -> Option<Block> {
+    match &block.kind {
+        BlockKind::Adc(adc) if adc.aperture_jitter_s.is_some() => Some(Block {
+            noise_figure_db: adc.noise_figure_db_at(signal_power_dbm, frequency_hz),
+            ..block.clone()
+        }),
+        _ => None,
+    }
+}
+
+/// The converter's SFDR caps the cascaded `sfdr_db`.
+pub(crate) fn limit_sfdr(block: &Block, sfdr_db: Option<f64>) -> Option<f64> {
+    match (&block.kind, sfdr_db) {
+        (
+            BlockKind::Adc(Adc {
+                sfdr_db: Some(adc_sfdr_db),
+                ..
+            }),
+            Some(sfdr_db),
+        ) => Some(sfdr_db.min(*adc_sfdr_db)),
+        (BlockKind::Adc(adc), None) => adc.sfdr_db,
+        _ => sfdr_db,
+    }
+}
+
+/// Headroom to the full scale of `block` if it is a converter.
+pub(crate) fn headroom_db(block: &Block, signal_power_dbm: f64, papr_db: f64) -> Option<f64> {
+    match &block.kind {
+        BlockKind::Adc(adc) => Some(adc.headroom_db(signal_power_dbm, papr_db)),
+        _ => None,
+    }
+}
+
+impl Block {
+    /// Create an ADC stage, with no gain and the converter's effective noise figure.
+    ///
+    /// The cascade adds its quantization, thermal and jitter noise to the noise power in
+    /// the signal bandwidth, and reports the clipping headroom in
+    /// [`SignalNode::adc_headroom_db`](crate::SignalNode::adc_headroom_db).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{cascade_vector_return_output, Adc, Block, Input};
+    ///
+    /// let adc = Block::adc("ADC", Adc::from_enob(4.0, 500.0e6, 11.5));
+    /// let input = Input::new(100.0e6, 10.0e6, -20.0, Some(290.0)).with_papr_db(10.0);
+    /// let output = cascade_vector_return_output(input, vec![adc]);
+    /// assert_eq!(output.adc_headroom_db, Some(14.0));
+    /// ```
+    #[doc(alias = "ADC")]
+    #[must_use]
+    pub fn adc(name: &str, adc: Adc) -> Block {
+        Block {
+            name: name.to_string(),
+            gain_db: 0.0,
+            noise_figure_db: adc.noise_figure_db(),
+            kind: BlockKind::Adc(adc),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+
+    fn adc() -> Adc {
+        // 12-bit ideal: 74 dB SNR, +10 dBm full scale, 100 MSPS
+        Adc::new(10.0, 100.0e6, 74.0)
+    }
+
+    #[test]
+    fn noise_density_and_processing_gain() {
+        let adc = adc();
+        assert!((adc.noise_density_dbm_per_hz() - (10.0 - 74.0 - 76.9897)).abs() < 1e-3);
+        assert!((adc.processing_gain_db(50.0e6)).abs() < 1e-12);
+        assert!((adc.processing_gain_db(1.0e6) - 16.9897).abs() < 1e-3);
+        assert!((adc.enob() - 12.0).abs() < 1e-9);
+        // -141 dBm/Hz against -174 dBm/Hz
+        assert!((adc.noise_figure_db() - 32.99).abs() < 0.01);
+        assert_eq!(adc.headroom_db(-5.0, 10.0), 5.0);
+    }
+
+    #[test]
+    fn jitter_noise_scales_with_the_signal() {
+        let jittery = adc().with_aperture_jitter_s(1.0e-12);
+        // 1 ps at 100 MHz: 64 dB
+        let snr = jittery.jitter_snr_db(100.0e6).unwrap();
+        assert!((snr - 64.04).abs() < 0.01);
+        // at full scale the jitter dominates the 74 dB SNR
+        let density = jittery.noise_density_at(10.0, 100.0e6);
+        assert!((density - (-130.61)).abs() < 0.01);
+        // far below full scale it is negligible
+        let small = jittery.noise_density_at(-60.0, 100.0e6);
+        assert!((small - jittery.noise_density_dbm_per_hz()).abs() < 1e-3);
+        assert!(jittery.noise_figure_db_at(10.0, 100.0e6) > jittery.noise_figure_db());
+        assert!(adc().jitter_snr_db(100.0e6).is_none());
+    }
+
+    #[test]
+    fn cascade_adds_converter_noise_in_the_signal_bandwidth() {
+        let input = Input::new(70.0e6, 1.0e6, -30.0, Some(290.0));
+        let lna = Block {
+            name: "Gain".to_string(),
+            gain_db: 30.0,
+            noise_figure_db: 0.0,
+            ..Default::default()
+        };
+        let analog = input.cascade_block(&lna);
+        let digital = analog.cascade_block(&Block::adc("ADC", adc().with_sfdr_db(80.0)));
+
+        // -141 dBm/Hz in 1 MHz is -81 dBm, 3 dB above the -84 dBm of analog noise
+        let adc_noise_w =
+            rfconversions::power::dbm_to_watts(adc().noise_density_dbm_per_hz() + 60.0);
+        let analog_noise_w = rfconversions::power::dbm_to_watts(analog.noise_power_dbm);
+        let expected = rfconversions::power::watts_to_dbm(adc_noise_w + analog_noise_w);
+        assert!((digital.noise_power_dbm - expected).abs() < 0.01);
+        assert_eq!(digital.signal_power_dbm, 0.0);
+        assert_eq!(digital.adc_headroom_db, Some(10.0));
+        assert_eq!(digital.sfdr_db, Some(80.0));
+        // the ADC's share of the noise budget follows its effective noise figure
+        assert_eq!(digital.noise_contributions.last().unwrap().name, "ADC");
+    }
+
+    #[test]
+    fn display_adc() {
+        assert_eq!(
+            format!("{}", adc()),
+            "Adc { full_scale: 10 dBm, sample_rate: 100000000 Hz, snr: 74 dB }"
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use crate::block::{Block, BlockKind};
+use crate::constants;
+use crate::digital::DigitalStage;
+use crate::filter::Filter;
+use crate::node::{update_noise_percentages, NoiseContribution, SignalNode};
+
+/// Frequencies up to `noise_bandwidth_hz` that sampling at `sample_rate_hz` folds onto
+/// `frequency_hz`, in ascending order and without `frequency_hz` itself.
+pub(crate) fn alias_frequencies_hz(
+    sample_rate_hz: f64,
+    noise_bandwidth_hz: f64,
+    frequency_hz: f64,
+) -> Vec<f64> {
+    let fs = sample_rate_hz;
+    if !(fs > 0.0 && noise_bandwidth_hz.is_finite()) {
+        return vec![];
+    }
+    let tolerance_hz = constants::FREQUENCY_TOLERANCE * fs;
+    let remainder = frequency_hz.rem_euclid(fs);
+    let folded = if remainder <= fs / 2.0 {
+        remainder
+    } else {
+        fs - remainder
+    };
+
+    let mut aliases: Vec<f64> = vec![];
+    let mut zone = 0.0;
+    while zone * fs - folded <= noise_bandwidth_hz {
+        for alias in [zone * fs - folded, zone * fs + folded] {
+            if alias >= 0.0
+                && alias <= noise_bandwidth_hz
+                && (alias - frequency_hz).abs() > tolerance_hz
+                && aliases
+                    .last()
+                    .is_none_or(|last| (alias - last).abs() > tolerance_hz)
+            {
+                aliases.push(alias);
+            }
+        }
+        zone += 1.0;
+    }
+    aliases
+}
+
+/// The filters upstream of the output of `block`, with the share of the output noise that
+/// passed through each.
+///
+/// `input_noise_share` is the fraction of the output noise (in the signal bandwidth) that
+/// came through `block` from its input; the rest was added by `block` itself and has not
+/// been filtered. Mixers move the filters to the frequencies they translate to.
+pub(crate) fn cascade_noise_filters(
+    previous: &[(Filter, f64)],
+    block: &Block,
+    input_noise_share: f64,
+) -> Vec<(Filter, f64)> {
+    let mut noise_filters: Vec<(Filter, f64)> = previous
+        .iter()
+        .map(|(filter, share)| {
+            (
+                Filter {
+                    center_frequency_hz: block.output_frequency_hz(filter.center_frequency_hz),
+                    ..filter.clone()
+                },
+                share * input_noise_share,
+            )
+        })
+        .collect();
+    match &block.kind {
+        BlockKind::Filter(filter)
+        | BlockKind::Digital(DigitalStage {
+            filter: Some(filter),
+            ..
+        }) => noise_filters.push((filter.clone(), input_noise_share)),
+        _ => {}
+    }
+    noise_filters
+}
+
+/// Noise density at `frequency_hz` relative to the density in the signal band.
+///
+/// Noise that entered before a filter is rejected by it and by every filter after it;
+/// noise added after the last filter is white.
+fn relative_noise_density(noise_filters: &[(Filter, f64)], frequency_hz: f64) -> f64 {
+    let mut density = 0.0;
+    let mut passed = 1.0;
+    let mut share_after = 1.0;
+    for (filter, share) in noise_filters.iter().rev() {
+        density += (share_after - share) * passed;
+        passed *= rfconversions::power::db_to_linear(-filter.rejection_db(frequency_hz));
+        share_after = *share;
+    }
+    density + share_after * passed
+}
+
+/// Fold the out-of-band noise reaching `block` into the signal band of its output `node`,
+/// if it is a converter with a noise bandwidth or a decimator with a filter.
+///
+/// Each alias of the signal band brings the in-band noise density at the input,
+/// `input_noise_density_dbm_per_hz`, scaled by the rejection at the alias frequency of
+/// the filters in `noise_filters` and the decimator's own. A decimator folds the noise
+/// of its input Nyquist zone at `input_sample_rate_hz`. The folded noise is added to the
+/// node's noise power and SNR degradation and, referred to the input, to its noise
+/// figure and noise budget.
+pub(crate) fn fold_noise(
+    node: &mut SignalNode,
+    block: &Block,
+    noise_filters: &[(Filter, f64)],
+    input_noise_density_dbm_per_hz: f64,
+    input_sample_rate_hz: Option<f64>,
+) {
+    let aliases = match (&block.kind, input_sample_rate_hz) {
+        (BlockKind::Adc(adc), _) if adc.noise_bandwidth_hz.is_some() => {
+            adc.alias_frequencies_hz(node.signal_frequency_hz)
+        }
+        (BlockKind::Digital(stage), Some(sample_rate_hz))
+            if stage.decimation > 1 && stage.filter.is_some() =>
+        {
+            alias_frequencies_hz(
+                stage.output_sample_rate_hz(sample_rate_hz),
+                sample_rate_hz / 2.0,
+                node.signal_frequency_hz,
+            )
+        }
+        _ => return,
+    };
+    let noise_filters = cascade_noise_filters(noise_filters, block, 1.0);
+    let relative_density: f64 = aliases
+        .iter()
+        .map(|&alias_hz| relative_noise_density(&noise_filters, alias_hz))
+        .sum();
+    let aliased_watts = rfconversions::power::dbm_to_watts(
+        input_noise_density_dbm_per_hz + block.gain_db + 10.0 * node.signal_bandwidth_hz.log10(),
+    ) * relative_density;
+    let noise_watts = rfconversions::power::dbm_to_watts(node.noise_power_dbm);
+    let degradation_db = 10.0 * (1.0 + aliased_watts / noise_watts).log10();
+
+    tracing::debug!(
+        block = %block.name,
+        aliases = aliases.len(),
+        aliased_dbm = rfconversions::power::watts_to_dbm(aliased_watts),
+        degradation_db,
+        "Folded out-of-band noise"
+    );
+
+    node.alias_snr_degradation_db =
+        Some(node.alias_snr_degradation_db.unwrap_or(0.0) + degradation_db);
+    if aliased_watts <= 0.0 {
+        return;
+    }
+    node.noise_power_dbm = rfconversions::power::watts_to_dbm(noise_watts + aliased_watts);
+
+    // the same noise referred to the cascade input
+    let gain_linear = rfconversions::power::db_to_linear(node.cumulative_gain_db);
+    let temperature_k =
+        aliased_watts / (constants::BOLTZMANN * node.signal_bandwidth_hz * gain_linear);
+    node.cumulative_noise_temperature = node
+        .cumulative_noise_temperature
+        .map(|noise_temperature| noise_temperature + temperature_k);
+    node.cumulative_noise_figure_db = rfconversions::noise::noise_figure_from_noise_factor(
+        node.noise_factor() + temperature_k / 290.0,
+    );
+    node.noise_contributions.push(NoiseContribution::new(
+        &format!("{} (aliased noise)", block.name),
+        temperature_k,
+    ));
+    update_noise_percentages(&mut node.noise_contributions);
+
+    // the folded noise passed none of the filters
+    let share = noise_watts / (noise_watts + aliased_watts);
+    for (_, filter_share) in node.noise_filters.iter_mut() {
+        *filter_share *= share;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adc::Adc;
+    use crate::input::Input;
+    use crate::{Injection, Mixer, MixerNoiseFigure};
+
+    fn amplifier(name: &str) -> Block {
+        Block::builder(name)
+            .gain_db(40.0)
+            .noise_figure_db(3.0)
+            .build()
+            .unwrap()
+    }
+
+    fn converter() -> Block {
+        // 100 MSPS with 250 MHz of noise bandwidth: four aliases of a 20 MHz signal
+        Block::adc(
+            "ADC",
+            Adc::new(10.0, 100.0e6, 90.0).with_noise_bandwidth_hz(250.0e6),
+        )
+    }
+
+    #[test]
+    fn filters_shape_the_noise_density() {
+        let filter = Filter::new(20.0e6, 10.0e6).with_rejection(vec![(60.0e6, 30.0)]);
+        let noise_filters = vec![(filter, 0.9)];
+        // 90 % of the noise is rejected 30 dB at 80 MHz, 10 % came after the filter
+        let density = relative_noise_density(&noise_filters, 80.0e6);
+        assert!((density - (0.1 + 0.9e-3)).abs() < 1e-12);
+        assert!((relative_noise_density(&noise_filters, 20.0e6) - 1.0).abs() < 1e-12);
+        assert_eq!(relative_noise_density(&[], 80.0e6), 1.0);
+    }
+
+    #[test]
+    fn unfiltered_noise_folds_from_every_alias() {
+        let input = Input::new(20.0e6, 1.0e6, -60.0, Some(290.0));
+        let analog = input.cascade_block(&amplifier("Amp"));
+        let digital = analog.cascade_block(&converter());
+
+        // with the converter's own noise negligible, four aliases add 10·log10(5) dB
+        let degradation_db = digital.alias_snr_degradation_db.unwrap();
+        assert!(
+            (degradation_db - 6.99).abs() < 0.01,
+            "got {degradation_db:.3} dB"
+        );
+        assert!(
+            (analog.signal_to_noise_ratio_db() - digital.signal_to_noise_ratio_db() - 6.99).abs()
+                < 0.01
+        );
+        assert_eq!(
+            digital.noise_contributions.last().unwrap().name,
+            "ADC (aliased noise)"
+        );
+        let total_k: f64 = digital
+            .noise_contributions
+            .iter()
+            .map(|c| c.noise_temperature_k)
+            .sum();
+        assert!(total_k > 4.0 * analog.noise_temperature());
+
+        // without a noise bandwidth nothing folds
+        let plain = analog.cascade_block(&Block::adc("ADC", Adc::new(10.0, 100.0e6, 90.0)));
+        assert!(plain.alias_snr_degradation_db.is_none());
+    }
+
+    #[test]
+    fn anti_alias_filter_rejects_the_aliases() {
+        let input = Input::new(20.0e6, 1.0e6, -60.0, Some(290.0));
+        let anti_alias = Block::filter(
+            "Anti-Alias",
+            1.0,
+            Filter::new(20.0e6, 20.0e6).with_rejection(vec![(50.0e6, 60.0)]),
+        );
+        let filtered = input
+            .cascade_block(&amplifier("Amp"))
+            .cascade_block(&anti_alias);
+        assert_eq!(filtered.noise_filters.len(), 1);
+        let digital = filtered.cascade_block(&converter());
+        // only the filter's own thermal noise is left out of band
+        let degradation_db = digital.alias_snr_degradation_db.unwrap();
+        assert!(degradation_db < 0.05, "got {degradation_db:.3} dB");
+
+        // an amplifier after the filter adds unfiltered noise that folds again
+        let late = input
+            .cascade_block(&anti_alias)
+            .cascade_block(&amplifier("Amp"))
+            .cascade_block(&converter());
+        assert!(late.alias_snr_degradation_db.unwrap() > 1.0);
+    }
+
+    #[test]
+    fn decimation_folds_what_the_digital_filter_passes() {
+        let digitized = Input::new(20.0e6, 1.0e6, -30.0, Some(290.0))
+            .cascade_block(&amplifier("Amp"))
+            .cascade_block(&Block::adc("ADC", Adc::new(10.0, 100.0e6, 90.0)));
+        let decimator = |filter: Option<Filter>| {
+            let mut stage = DigitalStage::nco(20.0e6).with_decimation(10);
+            stage.filter = filter;
+            Block::digital("DDC", 0.0, stage)
+        };
+
+        // a filter without a stopband passes the five bands at 10 MHz steps up to 50 MHz
+        let flat = digitized.cascade_block(&decimator(Some(Filter::new(0.0, 2.0e6))));
+        let degradation_db = flat.alias_snr_degradation_db.unwrap();
+        assert!((degradation_db - 7.78).abs() < 0.01, "got {degradation_db:.3} dB");
+        assert_eq!(flat.sample_rate_hz, Some(10.0e6));
+
+        let sharp = digitized.cascade_block(&decimator(Some(
+            Filter::new(0.0, 2.0e6).with_rejection(vec![(4.0e6, 80.0)]),
+        )));
+        assert!(sharp.alias_snr_degradation_db.unwrap() < 1e-3);
+
+        // without a filter the decimation is ideal
+        let ideal = digitized.cascade_block(&decimator(None));
+        assert!(ideal.alias_snr_degradation_db.is_none());
+        assert!((ideal.noise_power_dbm - digitized.noise_power_dbm).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mixer_moves_the_filters() {
+        let filter = Block::filter("RF Filter", 1.0, Filter::new(1.0e9, 20.0e6));
+        let mixer = Block::mixer(
+            "Mixer",
+            -7.0,
+            MixerNoiseFigure::Ssb(7.0),
+            Mixer::new(1.07e9, Injection::HighSide),
+        );
+        let node = Input::new(1.0e9, 1.0e6, -60.0, None)
+            .cascade_block(&filter)
+            .cascade_block(&mixer);
+        assert_eq!(node.signal_frequency_hz, 70.0e6);
+        assert!((node.noise_filters[0].0.center_frequency_hz - 70.0e6).abs() < 1e-3);
+        assert!(node.noise_filters[0].1 < 1.0);
+    }
+}
@@ -15,6 +15,7 @@ use crate::block::Block;
 ///     noise_figure_db: 5.0,
 ///     output_p1db_dbm: Some(30.0),
 ///     output_ip3_dbm: None,
+///     ..Default::default()
 /// };
 /// let model = AmplifierModel::with_am_pm(&block, 5.0);
 /// let sweep = model.am_am_am_pm_sweep(-30.0, -30.0, 1.0);
@@ -68,6 +69,7 @@ impl fmt::Display for AmplifierPoint {
 ///     noise_figure_db: 6.0,
 ///     output_p1db_dbm: Some(33.0),
 ///     output_ip3_dbm: Some(45.0),
+///     ..Default::default()
 /// };
 /// let model = AmplifierModel::with_am_pm(&block, 8.0); // 8 °/dB AM-PM
 /// let phase = model.phase_shift_at(0.0).unwrap();
@@ -87,7 +89,7 @@ pub struct AmplifierModel<'a> {
 }
 
 impl<'a> AmplifierModel<'a> {
-    /// Create an amplifier model with no AM-PM characterization.
+    /// Create an amplifier model with the block's AM-PM coefficient, if any.
     ///
     /// # Examples
     ///
@@ -100,6 +102,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 2.0,
     ///     output_p1db_dbm: Some(10.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::new(&block);
     /// assert!(model.phase_shift_at(-30.0).is_none());
@@ -108,7 +111,7 @@ impl<'a> AmplifierModel<'a> {
     pub fn new(block: &'a Block) -> Self {
         Self {
             block,
-            am_pm_coefficient_deg_per_db: None,
+            am_pm_coefficient_deg_per_db: block.am_pm_coefficient_deg_per_db,
             saturation_power_dbm: None,
         }
     }
@@ -126,6 +129,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(30.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::with_am_pm(&block, 10.0);
     /// // At input P1dB (10 dBm), phase shift is 0
@@ -154,6 +158,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(30.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::with_saturation(&block, 35.0);
     /// assert_eq!(model.saturation_power_dbm, Some(35.0));
@@ -162,7 +167,7 @@ impl<'a> AmplifierModel<'a> {
     pub fn with_saturation(block: &'a Block, psat_dbm: f64) -> Self {
         Self {
             block,
-            am_pm_coefficient_deg_per_db: None,
+            am_pm_coefficient_deg_per_db: block.am_pm_coefficient_deg_per_db,
             saturation_power_dbm: Some(psat_dbm),
         }
     }
@@ -180,6 +185,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 6.0,
     ///     output_p1db_dbm: Some(33.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::builder(&block)
     ///     .am_pm_coefficient(8.0)
@@ -191,7 +197,7 @@ impl<'a> AmplifierModel<'a> {
     pub fn builder(block: &'a Block) -> AmplifierModelBuilder<'a> {
         AmplifierModelBuilder {
             block,
-            am_pm_coefficient_deg_per_db: None,
+            am_pm_coefficient_deg_per_db: block.am_pm_coefficient_deg_per_db,
             saturation_power_dbm: None,
         }
     }
@@ -221,6 +227,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(10.0), // input P1dB = -10 dBm
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::with_am_pm(&block, 10.0);
     /// // 5 dB above input P1dB → 50° phase shift
@@ -258,6 +265,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(30.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::with_am_pm(&block, 5.0);
     /// let sweep = model.am_am_am_pm_sweep(-40.0, -20.0, 5.0);
@@ -315,6 +323,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(10.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::with_am_pm(&block, 10.0);
     /// let backoff = model.backoff_for_target_phase(5.0).unwrap();
@@ -360,6 +369,7 @@ impl<'a> AmplifierModel<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(10.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::with_am_pm(&block, 10.0);
     /// // At deep backoff, EVM should be ~0
@@ -387,6 +397,7 @@ impl<'a> AmplifierModel<'a> {
 ///     noise_figure_db: 6.0,
 ///     output_p1db_dbm: Some(33.0),
 ///     output_ip3_dbm: None,
+///     ..Default::default()
 /// };
 /// let model = AmplifierModel::builder(&block)
 ///     .am_pm_coefficient(8.0)
@@ -415,6 +426,7 @@ impl<'a> AmplifierModelBuilder<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(30.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::builder(&block)
     ///     .am_pm_coefficient(5.0)
@@ -440,6 +452,7 @@ impl<'a> AmplifierModelBuilder<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(30.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::builder(&block)
     ///     .saturation_power(35.0)
@@ -465,6 +478,7 @@ impl<'a> AmplifierModelBuilder<'a> {
     ///     noise_figure_db: 5.0,
     ///     output_p1db_dbm: Some(30.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let model = AmplifierModel::builder(&block).build();
     /// assert!(model.am_pm_coefficient_deg_per_db.is_none());
@@ -490,6 +504,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: Some(10.0), // input P1dB = -10 dBm
             output_ip3_dbm: Some(25.0),
+            ..Default::default()
         }
     }
 
@@ -500,6 +515,22 @@ mod tests {
         assert!(model.phase_shift_at(-30.0).is_none());
     }
 
+    #[test]
+    fn new_uses_block_am_pm() {
+        let block = Block {
+            am_pm_coefficient_deg_per_db: Some(4.0),
+            ..test_block()
+        };
+        assert_eq!(
+            AmplifierModel::new(&block).am_pm_coefficient_deg_per_db,
+            Some(4.0)
+        );
+        let model = AmplifierModel::builder(&block)
+            .am_pm_coefficient(6.0)
+            .build();
+        assert_eq!(model.am_pm_coefficient_deg_per_db, Some(6.0));
+    }
+
     #[test]
     fn with_am_pm_returns_phase_shift() {
         let block = test_block();
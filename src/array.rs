@@ -0,0 +1,452 @@
+use std::fmt;
+
+use crate::block::Block;
+use crate::branching::{combiner_parts, input_node};
+use crate::combiner::Combiner;
+use crate::input::Input;
+use crate::node::{update_noise_percentages, NoiseContribution, SignalNode};
+use crate::validation::{validate_array, Diagnostic};
+
+/// Channel-to-channel gain and phase errors of an array, as RMS statistics.
+///
+/// The errors are independent between channels and zero-mean: a gain error spreads the
+/// channel voltages around their nominal value and a phase error (Gaussian, with standard
+/// deviation `phase_rms_deg`) spreads the phases. On average `N` channels then combine to
+///
+/// `|Σ aᵢ·e^(jφᵢ)|² = N·(N − 1)·e^(−σφ²) + N·(1 + σa²)`
+///
+/// instead of `N²`, where `σa = ln(10)/20 · amplitude_rms_db` is the RMS voltage error.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::ChannelErrors;
+///
+/// let errors = ChannelErrors::new(0.5, 5.0);
+/// // about 0.05 dB of SNR lost on a 64-element array
+/// assert!((errors.snr_loss_db(64) - 0.047).abs() < 0.001);
+/// assert_eq!(ChannelErrors::default().snr_loss_db(64), 0.0);
+/// ```
+#[doc(alias = "amplitude error")]
+#[doc(alias = "phase error")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChannelErrors {
+    /// RMS gain error between channels in dB.
+    pub amplitude_rms_db: f64,
+    /// RMS insertion phase error between channels in degrees.
+    pub phase_rms_deg: f64,
+}
+
+impl fmt::Display for ChannelErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ChannelErrors {{ amplitude_rms: {} dB, phase_rms: {} deg }}",
+            self.amplitude_rms_db, self.phase_rms_deg
+        )
+    }
+}
+
+impl ChannelErrors {
+    /// Create channel errors from the RMS gain error in dB and phase error in degrees.
+    #[must_use]
+    pub fn new(amplitude_rms_db: f64, phase_rms_deg: f64) -> Self {
+        Self {
+            amplitude_rms_db,
+            phase_rms_deg,
+        }
+    }
+
+    /// Mean-square voltage error of the channel gains, `σa²`.
+    fn amplitude_variance(&self) -> f64 {
+        let sigma = self.amplitude_rms_db * std::f64::consts::LN_10 / 20.0;
+        sigma * sigma
+    }
+
+    /// Expected power of `elements` channels combined in phase, relative to `elements²`
+    /// error-free channels.
+    #[must_use]
+    pub fn coherent_efficiency(&self, elements: usize) -> f64 {
+        let n = elements as f64;
+        let phase_term = (-self.phase_rms_deg.to_radians().powi(2)).exp();
+        ((n - 1.0) * phase_term + 1.0 + self.amplitude_variance()) / n
+    }
+
+    /// Expected loss of combined signal-to-noise ratio in dB, relative to error-free
+    /// channels. Gain errors scale each channel's noise too, so they cost less than
+    /// their effect on the signal alone.
+    #[must_use]
+    pub fn snr_loss_db(&self, elements: usize) -> f64 {
+        -10.0 * (self.coherent_efficiency(elements) / (1.0 + self.amplitude_variance())).log10()
+    }
+}
+
+/// The phased array combined upstream of a [`SignalNode`], kept in
+/// [`SignalNode::array`] so that later nodes can report the array G/T and its
+/// improvement in signal-to-noise ratio over one element.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArrayGain {
+    /// Number of elements combined.
+    pub elements: usize,
+    /// Gain of one element in dBi.
+    pub element_gain_dbi: f64,
+    /// Signal-to-noise ratio at the output of one element channel in dB.
+    pub element_snr_db: f64,
+    /// Loss of signal-to-noise ratio from the channel errors in dB.
+    pub error_loss_db: f64,
+}
+
+impl fmt::Display for ArrayGain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ArrayGain {{ elements: {}, element_gain: {} dBi, element_snr: {:.2} dB, error_loss: {:.2} dB }}",
+            self.elements, self.element_gain_dbi, self.element_snr_db, self.error_loss_db
+        )
+    }
+}
+
+impl ArrayGain {
+    /// Gain of the whole array in dBi, `element_gain_dbi + 10·log10(elements)`.
+    #[must_use]
+    pub fn array_gain_dbi(&self) -> f64 {
+        self.element_gain_dbi + 10.0 * (self.elements as f64).log10()
+    }
+}
+
+/// `elements` identical receive channels combined before a common back end.
+///
+/// Each element sees the same signal (the [`Input`] at one element port) with its own,
+/// uncorrelated noise (the input noise temperature is the element's antenna
+/// temperature). Combined in phase the signal grows by `10·log10(elements)` more than
+/// the noise, so the system noise temperature is that of one channel (the average over
+/// identical channels) while the SNR improves by the array gain. The
+/// [`ChannelErrors`] lower the coherent gain and appear in the noise budget as an
+/// extra noise temperature named after the combiner.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Block, ChannelErrors, Input, PhasedArray};
+///
+/// let lna = Block::builder("LNA").gain_db(25.0).noise_figure_db(1.5).build().unwrap();
+/// let array = PhasedArray::new(16, vec![lna], Block::combiner("Beamformer", 16, 1.0))
+///     .with_element_gain_dbi(5.0)
+///     .with_errors(ChannelErrors::new(0.5, 5.0));
+///
+/// let input = Input::new(10.0e9, 1.0e6, -110.0, Some(50.0));
+/// let nodes = array.cascade(&input);
+/// let combined = &nodes[0];
+/// assert!((combined.array_snr_improvement_db().unwrap() - 12.0).abs() < 0.1);
+/// assert!(combined.g_over_t_db().unwrap() > -7.0);
+/// ```
+#[doc(alias = "beamformer")]
+#[doc(alias = "array")]
+#[derive(Clone, Debug)]
+pub struct PhasedArray {
+    /// Number of elements (and channels).
+    pub elements: usize,
+    /// Gain of one element in dBi, for the array G/T.
+    pub element_gain_dbi: f64,
+    /// The blocks of one element channel, from the element port to the combiner.
+    pub channel: Vec<Block>,
+    /// The combiner, usually from [`Block::combiner`].
+    pub combiner: Block,
+    /// Blocks after the combiner.
+    pub back_end: Vec<Block>,
+    /// Channel-to-channel gain and phase errors.
+    pub errors: ChannelErrors,
+}
+
+impl fmt::Display for PhasedArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "PhasedArray {{ elements: {}, channel: {} block(s), combiner: {}, back_end: {} block(s), errors: {} }}",
+            self.elements,
+            self.channel.len(),
+            self.combiner.name,
+            self.back_end.len(),
+            self.errors
+        )
+    }
+}
+
+impl PhasedArray {
+    /// Create an array of `elements` channels made of `channel`, combined by `combiner`.
+    #[must_use]
+    pub fn new(elements: usize, channel: Vec<Block>, combiner: Block) -> Self {
+        Self {
+            elements,
+            element_gain_dbi: 0.0,
+            channel,
+            combiner,
+            back_end: vec![],
+            errors: ChannelErrors::default(),
+        }
+    }
+
+    /// Set the gain of one element in dBi.
+    #[must_use]
+    pub fn with_element_gain_dbi(mut self, element_gain_dbi: f64) -> Self {
+        self.element_gain_dbi = element_gain_dbi;
+        self
+    }
+
+    /// Set the channel-to-channel gain and phase errors.
+    #[must_use]
+    pub fn with_errors(mut self, errors: ChannelErrors) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Add the blocks after the combiner.
+    #[must_use]
+    pub fn with_back_end(mut self, back_end: Vec<Block>) -> Self {
+        self.back_end = back_end;
+        self
+    }
+
+    /// The nodes through one element channel, for `input` at its element port.
+    #[must_use]
+    pub fn element_nodes(&self, input: &Input) -> Vec<SignalNode> {
+        crate::cascade_vector_return_vector(input.clone(), self.channel.clone())
+    }
+
+    /// The combiner output node, then one node per back-end block.
+    ///
+    /// `input` is the signal at each element port. Gains are referred to the signal of
+    /// every element together, `input.power_dbm + 10·log10(elements)`.
+    #[must_use]
+    pub fn cascade(&self, input: &Input) -> Vec<SignalNode> {
+        let element = self
+            .element_nodes(input)
+            .pop()
+            .unwrap_or_else(|| input_node(input));
+        let (ports, excess) = combiner_parts(&self.combiner, self.elements);
+        let combined = combine_elements(
+            &format!("{} Inputs", self.combiner.name),
+            &element,
+            self,
+            &ports,
+        );
+        let mut nodes = vec![combined.cascade_block(&excess)];
+        for block in &self.back_end {
+            let node = nodes[nodes.len() - 1].cascade_block(block);
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    /// Check the channel, combiner and back-end blocks and the array's own parameters.
+    ///
+    /// No elements, negative error statistics or a combiner with fewer ports than
+    /// elements are errors.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate_array(self)
+    }
+}
+
+/// The node at the output of an ideal combiner fed by every channel of `array`, each
+/// ending in `element`.
+fn combine_elements(
+    name: &str,
+    element: &SignalNode,
+    array: &PhasedArray,
+    combiner: &Combiner,
+) -> SignalNode {
+    let n = array.elements as f64;
+    let ports = combiner.ways.max(array.elements) as f64;
+    let amplitude_variance = array.errors.amplitude_variance();
+
+    // combined signal, noise and in-phase levels relative to one channel's
+    let signal_ratio = if combiner.coherent {
+        n * n * array.errors.coherent_efficiency(array.elements)
+    } else {
+        n * (1.0 + amplitude_variance)
+    } / ports;
+    let noise_ratio = n * (1.0 + amplitude_variance) / ports;
+    let level_ratio_db = 10.0 * (if combiner.coherent { n * n } else { n } / ports).log10();
+    // the cascade input is the signal at every element together
+    let gain_ratio = signal_ratio / n;
+
+    // identical channels average to the same system noise temperature; whatever the
+    // combining loses in gain over the noise shows up as extra input-referred noise
+    let mut noise_contributions = element.noise_contributions.clone();
+    let element_temperature_k: f64 = noise_contributions
+        .iter()
+        .map(|c| c.noise_temperature_k)
+        .sum();
+    let degradation = noise_ratio / gain_ratio;
+    if degradation > 1.0 {
+        noise_contributions.push(NoiseContribution::new(
+            &format!("{} (combining)", array.combiner.name),
+            element_temperature_k * (degradation - 1.0),
+        ));
+    }
+    update_noise_percentages(&mut noise_contributions);
+    let system_temperature_k = element_temperature_k * degradation;
+    let source_temperature_k = noise_contributions
+        .first()
+        .map_or(270.0, |c| c.noise_temperature_k);
+    let cumulative_noise_figure_db = rfconversions::noise::noise_figure_from_noise_temperature(
+        system_temperature_k - source_temperature_k,
+    );
+
+    let mut linearity_contributions = element.linearity_contributions.clone();
+    for contribution in linearity_contributions.iter_mut() {
+        contribution.oip3_dbm = contribution.oip3_dbm.map(|oip3| oip3 + level_ratio_db);
+        contribution.p1db_dbm = contribution.p1db_dbm.map(|p1db| p1db + level_ratio_db);
+    }
+    let mut harmonics = element.harmonics.clone();
+    for harmonic in harmonics.iter_mut() {
+        harmonic.power_dbm += level_ratio_db;
+    }
+
+    let cumulative_oip3_dbm = element
+        .cumulative_oip3_dbm
+        .map(|oip3| oip3 + level_ratio_db);
+    let cumulative_oip2_dbm = element
+        .cumulative_oip2_dbm
+        .map(|oip2| oip2 + level_ratio_db);
+    let noise_floor_dbm =
+        -174.0 + 10.0 * element.signal_bandwidth_hz.log10() + cumulative_noise_figure_db;
+
+    tracing::debug!(
+        combiner = name,
+        elements = array.elements,
+        signal_ratio_db = 10.0 * signal_ratio.log10(),
+        noise_ratio_db = 10.0 * noise_ratio.log10(),
+        nf_db = cumulative_noise_figure_db,
+        "Combined array elements"
+    );
+
+    SignalNode {
+        name: name.to_string(),
+        signal_power_dbm: element.signal_power_dbm + 10.0 * signal_ratio.log10(),
+        noise_power_dbm: element.noise_power_dbm + 10.0 * noise_ratio.log10(),
+        cumulative_noise_figure_db,
+        cumulative_gain_db: element.cumulative_gain_db + 10.0 * gain_ratio.log10(),
+        cumulative_noise_temperature: Some(system_temperature_k),
+        cumulative_oip3_dbm,
+        sfdr_db: cumulative_oip3_dbm.map(|oip3| 2.0 / 3.0 * (oip3 - noise_floor_dbm)),
+        cumulative_oip2_dbm,
+        sfdr2_db: cumulative_oip2_dbm.map(|oip2| 0.5 * (oip2 - noise_floor_dbm)),
+        output_p1db_dbm: element.output_p1db_dbm.map(|p1db| p1db + level_ratio_db),
+        p1db_limiting_stage_dbm: element
+            .p1db_limiting_stage_dbm
+            .map(|p1db| p1db + level_ratio_db),
+        image_frequency_hz: None,
+        harmonics,
+        array: Some(ArrayGain {
+            elements: array.elements,
+            element_gain_dbi: array.element_gain_dbi,
+            element_snr_db: element.signal_to_noise_ratio_db(),
+            error_loss_db: array.errors.snr_loss_db(array.elements),
+        }),
+        noise_contributions,
+        linearity_contributions,
+        ..element.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lna() -> Block {
+        Block::builder("LNA")
+            .gain_db(30.0)
+            .noise_figure_db(1.0)
+            .output_ip3_dbm(20.0)
+            .build()
+            .unwrap()
+    }
+
+    fn input() -> Input {
+        Input::new(10.0e9, 1.0e6, -120.0, Some(50.0))
+    }
+
+    #[test]
+    fn ideal_array_improves_snr_by_the_element_count() {
+        let array = PhasedArray::new(64, vec![lna()], Block::combiner("Combiner", 64, 0.0));
+        let element = array.element_nodes(&input()).pop().unwrap();
+        let combined = &array.cascade(&input())[0];
+
+        assert_eq!(combined.name, "Combiner Output");
+        assert!((combined.signal_power_dbm - (element.signal_power_dbm + 18.0618)).abs() < 1e-3);
+        assert!((combined.cumulative_gain_db - 30.0).abs() < 1e-9);
+        assert!(
+            (combined.cumulative_noise_figure_db - element.cumulative_noise_figure_db).abs() < 1e-9
+        );
+        assert!((combined.array_snr_improvement_db().unwrap() - 18.0618).abs() < 1e-3);
+        // 64 amplifiers in parallel handle 18 dB more power
+        let linear_combiner = Block {
+            output_ip3_dbm: Some(100.0),
+            ..Block::combiner("Combiner", 64, 0.0)
+        };
+        let array = PhasedArray::new(64, vec![lna()], linear_combiner);
+        let combined = &array.cascade(&input())[0];
+        assert!((combined.cumulative_oip3_dbm.unwrap() - 38.0618).abs() < 1e-3);
+        // G/T: 0 dBi elements, 50 K antenna + 75 K LNA
+        let t_sys = 50.0 + rfconversions::noise::noise_temperature_from_noise_figure(1.0);
+        let expected = 18.0618 - 10.0 * t_sys.log10();
+        assert!((combined.g_over_t_db().unwrap() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn channel_errors_lower_coherent_gain() {
+        let errors = ChannelErrors::new(0.0, 10.0);
+        let sigma = 10.0_f64.to_radians();
+        let expected = (63.0 * (-sigma * sigma).exp() + 1.0) / 64.0;
+        assert!((errors.coherent_efficiency(64) - expected).abs() < 1e-12);
+        // gain errors alone add power incoherently but scale the noise as much
+        let gain_only = ChannelErrors::new(1.0, 0.0);
+        assert!(gain_only.coherent_efficiency(8) > 1.0);
+        assert!(gain_only.snr_loss_db(8) > 0.0);
+
+        let array = PhasedArray::new(64, vec![lna()], Block::combiner("Combiner", 64, 0.0))
+            .with_errors(errors);
+        let combined = &array.cascade(&input())[0];
+        let loss_db = errors.snr_loss_db(64);
+        assert!((combined.array_snr_improvement_db().unwrap() - (18.0618 - loss_db)).abs() < 1e-3);
+        let budget = &combined.noise_contributions;
+        assert!(budget.iter().any(|c| c.name == "Combiner (combining)"));
+        let total: f64 = budget.iter().map(|c| c.percent).sum();
+        assert!((total - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn back_end_noise_lowers_g_over_t() {
+        let receiver = Block::builder("Receiver")
+            .gain_db(10.0)
+            .noise_figure_db(10.0)
+            .build()
+            .unwrap();
+        let array = PhasedArray::new(4, vec![lna()], Block::combiner("Combiner", 4, 0.5))
+            .with_element_gain_dbi(6.0)
+            .with_back_end(vec![receiver]);
+        let nodes = array.cascade(&input());
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(
+            nodes[1].array.unwrap().array_gain_dbi(),
+            6.0 + 6.020599913279624
+        );
+        assert!(nodes[1].g_over_t_db().unwrap() < nodes[0].g_over_t_db().unwrap());
+        assert!(
+            nodes[1].array_snr_improvement_db().unwrap()
+                < nodes[0].array_snr_improvement_db().unwrap()
+        );
+    }
+
+    #[test]
+    fn display_array() {
+        let array = PhasedArray::new(8, vec![lna()], Block::combiner("Combiner", 8, 0.0))
+            .with_errors(ChannelErrors::new(0.5, 3.0));
+        assert_eq!(
+            format!("{}", array),
+            "PhasedArray { elements: 8, channel: 1 block(s), combiner: Combiner, back_end: 0 block(s), errors: ChannelErrors { amplitude_rms: 0.5 dB, phase_rms: 3 deg } }"
+        );
+    }
+}
@@ -0,0 +1,510 @@
+use std::fmt;
+
+use crate::block::{Block, BlockKind};
+use crate::combiner::{combine_branches, CombineReference, Combiner, Splitter};
+use crate::input::Input;
+use crate::node::{update_noise_percentages, NoiseContribution, SignalNode};
+use crate::validation::{validate_tree, Diagnostic};
+
+/// One stage of a [`Branch`]: a block, or a splitter feeding several branches.
+#[derive(Clone, Debug)]
+pub enum Stage {
+    /// A two-port block, cascaded like in a straight lineup.
+    Block(Box<Block>),
+    /// Divide the signal into `branches`, each of which ends in an output of the lineup.
+    ///
+    /// This must be the last stage of its branch; later stages are not cascaded.
+    Split {
+        /// The splitter, usually from [`Block::splitter`].
+        splitter: Box<Block>,
+        /// The branches fed by the splitter outputs.
+        branches: Vec<Branch>,
+    },
+    /// Divide the signal into `branches` and add their outputs back together, after which
+    /// the branch continues from the combiner output.
+    ///
+    /// The branches' outputs (every output, if a branch splits again) feed the combiner.
+    /// Noise from before the splitter is common to every branch and combines like the
+    /// signal; noise added in the branches, including the splitter's excess loss, combines
+    /// in power. The ideal division itself adds no noise, as that leaves through the
+    /// combiner's isolated port.
+    Combine {
+        /// The splitter, usually from [`Block::splitter`].
+        splitter: Box<Block>,
+        /// The parallel branches between splitter and combiner.
+        branches: Vec<Branch>,
+        /// The combiner, usually from [`Block::combiner`].
+        combiner: Box<Block>,
+    },
+}
+
+/// A named path through a [`LineupTree`]: blocks in order, optionally ending in a split.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Block, Branch};
+///
+/// let rx = Branch::new("Rx 1", vec![Block::builder("Cable").gain_db(-2.0).noise_figure_db(2.0).build().unwrap()]);
+/// assert_eq!(rx.stages.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Branch {
+    /// Name of the branch; the output of a branch that does not split is named after it.
+    pub name: String,
+    /// Stages in signal order.
+    pub stages: Vec<Stage>,
+}
+
+impl fmt::Display for Branch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Branch {{ name: {}, stages: [", self.name)?;
+        for (i, stage) in self.stages.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match stage {
+                Stage::Block(block) => write!(f, "{}", block.name)?,
+                Stage::Split { splitter, branches } => {
+                    write!(f, "{} -> {} branches", splitter.name, branches.len())?
+                }
+                Stage::Combine {
+                    splitter,
+                    branches,
+                    combiner,
+                } => write!(
+                    f,
+                    "{} -> {} branches -> {}",
+                    splitter.name,
+                    branches.len(),
+                    combiner.name
+                )?,
+            }
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl Branch {
+    /// Create a branch of blocks.
+    #[must_use]
+    pub fn new(name: &str, blocks: Vec<Block>) -> Self {
+        Self {
+            name: name.to_string(),
+            stages: blocks
+                .into_iter()
+                .map(|block| Stage::Block(Box::new(block)))
+                .collect(),
+        }
+    }
+
+    /// Append a block.
+    #[must_use]
+    pub fn with_block(mut self, block: Block) -> Self {
+        self.stages.push(Stage::Block(Box::new(block)));
+        self
+    }
+
+    /// End the branch in a splitter feeding `branches`.
+    #[must_use]
+    pub fn split(mut self, splitter: Block, branches: Vec<Branch>) -> Self {
+        self.stages.push(Stage::Split {
+            splitter: Box::new(splitter),
+            branches,
+        });
+        self
+    }
+
+    /// Split into parallel `branches` and recombine them; the branch continues after the combiner.
+    #[must_use]
+    pub fn combine(mut self, splitter: Block, branches: Vec<Branch>, combiner: Block) -> Self {
+        self.stages.push(Stage::Combine {
+            splitter: Box::new(splitter),
+            branches,
+            combiner: Box::new(combiner),
+        });
+        self
+    }
+}
+
+/// The nodes from the input to one output of a [`LineupTree`].
+#[derive(Clone, Debug)]
+pub struct BranchOutput {
+    /// Name of the branch that ends here.
+    pub name: String,
+    /// The node after each stage on the way, in signal order.
+    pub nodes: Vec<SignalNode>,
+}
+
+impl BranchOutput {
+    /// The node at this output, or `None` if no stage leads to it.
+    #[must_use]
+    pub fn output(&self) -> Option<&SignalNode> {
+        self.nodes.last()
+    }
+}
+
+/// A lineup that splits into several named outputs and can recombine parallel paths.
+///
+/// The `trunk` runs from the input; a [`Stage::Split`] ends it in several branches, each
+/// of which can split again, and every branch that does not split is an output. A
+/// [`Stage::Combine`] splits into parallel paths and adds them back together. All
+/// outputs are cascaded when the tree is created.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Block, Branch, Input, LineupTree};
+///
+/// let lna = Block::builder("LNA").gain_db(20.0).noise_figure_db(1.0).build().unwrap();
+/// let cable = |name: &str| Branch::new(name, vec![
+///     Block::builder("Cable").gain_db(-3.0).noise_figure_db(3.0).build().unwrap(),
+/// ]);
+/// let trunk = Branch::new("Trunk", vec![lna])
+///     .split(Block::splitter("Splitter", 2, 0.0), vec![cable("Rx A"), cable("Rx B")]);
+///
+/// let tree = LineupTree::new(Input::new(1.0e9, 1.0e6, -60.0, Some(290.0)), trunk);
+/// assert_eq!(tree.outputs().len(), 2);
+/// let rx_a = tree.output("Rx A").unwrap();
+/// assert!((rx_a.signal_power_dbm - (-46.01)).abs() < 0.01);
+/// ```
+#[doc(alias = "distribution network")]
+#[doc(alias = "splitter")]
+#[doc(alias = "combiner")]
+#[derive(Clone, Debug)]
+pub struct LineupTree {
+    input: Input,
+    trunk: Branch,
+    outputs: Vec<BranchOutput>,
+}
+
+impl fmt::Display for LineupTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LineupTree {{ input: {}, outputs: [", self.input)?;
+        for (i, output) in self.outputs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", output.name)?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl LineupTree {
+    /// Create a tree and cascade every output.
+    #[must_use]
+    pub fn new(input: Input, trunk: Branch) -> Self {
+        let mut outputs = vec![];
+        cascade_stages(&input, &trunk.name, &trunk.stages, vec![], &mut outputs);
+        tracing::debug!(outputs = outputs.len(), "Cascaded lineup tree");
+        Self {
+            input,
+            trunk,
+            outputs,
+        }
+    }
+
+    /// The input signal.
+    #[must_use]
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    /// The branch starting at the input.
+    #[must_use]
+    pub fn trunk(&self) -> &Branch {
+        &self.trunk
+    }
+
+    /// Every output, in the order the branches are given.
+    #[must_use]
+    pub fn outputs(&self) -> &[BranchOutput] {
+        &self.outputs
+    }
+
+    /// The path to the first output named `name`.
+    #[must_use]
+    pub fn branch_output(&self, name: &str) -> Option<&BranchOutput> {
+        self.outputs.iter().find(|output| output.name == name)
+    }
+
+    /// The node at the first output named `name`.
+    #[must_use]
+    pub fn output(&self, name: &str) -> Option<&SignalNode> {
+        self.branch_output(name)?.output()
+    }
+
+    /// Validate the input, every block and the structure of the tree.
+    ///
+    /// Besides [`Input::validate`] and [`Block::validate`], a split that is not the last
+    /// stage of its branch and a splitter or combiner with fewer ports than branches are
+    /// errors, and two outputs with the same name are a warning.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate_tree(&self.input, &self.trunk, &self.outputs)
+    }
+}
+
+/// The input as a node before any stage, for the noise common to split branches.
+pub(crate) fn input_node(input: &Input) -> SignalNode {
+    let mut noise_contributions = vec![NoiseContribution::new(
+        "Input",
+        input.noise_temperature_k.unwrap_or(270.0),
+    )];
+    update_noise_percentages(&mut noise_contributions);
+    SignalNode {
+        name: "Input".to_string(),
+        signal_frequency_hz: input.frequency_hz,
+        signal_bandwidth_hz: input.bandwidth_hz,
+        signal_power_dbm: input.power_dbm,
+        noise_power_dbm: input.noise_power(),
+        cumulative_noise_figure_db: 0.0,
+        cumulative_gain_db: 0.0,
+        cumulative_noise_temperature: input.noise_temperature_k,
+        phase_noise: input.phase_noise.clone(),
+        papr_db: input.papr_db,
+        noise_contributions,
+        ..Default::default()
+    }
+}
+
+/// Cascade `block` after the last node of `nodes`, or from the input.
+fn next_node(input: &Input, nodes: &[SignalNode], block: &Block) -> SignalNode {
+    match nodes.last() {
+        Some(previous) => previous.cascade_block(block),
+        None => input.cascade_block(block),
+    }
+}
+
+/// The ideal division of the splitter as a noiseless block, and its loss beyond that as a
+/// passive block.
+///
+/// Between a splitter and a combiner, the noise a lossless split adds leaves through the
+/// combiner's isolated port, so only the excess loss adds noise in each branch.
+fn splitter_parts(splitter: &Block, outputs: usize) -> (Block, Block) {
+    let loss_db = match &splitter.kind {
+        BlockKind::Splitter(ports) => ports.split_loss_db(),
+        _ => Splitter::new(outputs).split_loss_db(),
+    };
+    let ideal = Block {
+        name: splitter.name.clone(),
+        gain_db: -loss_db,
+        noise_figure_db: 0.0,
+        ..Default::default()
+    };
+    let excess = match &splitter.kind {
+        BlockKind::Splitter(_) => Block {
+            gain_db: splitter.gain_db + loss_db,
+            noise_figure_db: (splitter.noise_figure_db - loss_db).max(0.0),
+            kind: BlockKind::Generic,
+            ..splitter.clone()
+        },
+        _ => splitter.clone(),
+    };
+    (ideal, excess)
+}
+
+/// The combiner's ports and its loss beyond ideal combining, as a passive block.
+pub(crate) fn combiner_parts(combiner: &Block, inputs: usize) -> (Combiner, Block) {
+    match &combiner.kind {
+        BlockKind::Combiner(ports) => {
+            let loss_db = ports.combine_loss_db();
+            let excess = Block {
+                gain_db: combiner.gain_db + loss_db,
+                noise_figure_db: (combiner.noise_figure_db - loss_db).max(0.0),
+                kind: BlockKind::Generic,
+                ..combiner.clone()
+            };
+            (*ports, excess)
+        }
+        _ => (Combiner::new(inputs), combiner.clone()),
+    }
+}
+
+/// Cascade `stages` after `nodes`, pushing every output they end in.
+fn cascade_stages(
+    input: &Input,
+    name: &str,
+    stages: &[Stage],
+    mut nodes: Vec<SignalNode>,
+    outputs: &mut Vec<BranchOutput>,
+) {
+    for stage in stages {
+        match stage {
+            Stage::Block(block) => {
+                let node = next_node(input, &nodes, block);
+                nodes.push(node);
+            }
+            Stage::Split { splitter, branches } => {
+                let split = next_node(input, &nodes, splitter);
+                nodes.push(split);
+                for branch in branches {
+                    cascade_stages(input, &branch.name, &branch.stages, nodes.clone(), outputs);
+                }
+                return;
+            }
+            Stage::Combine {
+                splitter,
+                branches,
+                combiner,
+            } => {
+                let (ideal, excess) = splitter_parts(splitter, branches.len());
+                let mut shared = nodes
+                    .last()
+                    .cloned()
+                    .unwrap_or_else(|| input_node(input))
+                    .cascade_block(&ideal);
+                // the ideal division adds no noise, so it has no share to list
+                shared.noise_contributions.pop();
+                let split = shared.cascade_block(&excess);
+                let mut parallel = vec![];
+                for branch in branches {
+                    cascade_stages(
+                        input,
+                        &branch.name,
+                        &branch.stages,
+                        vec![split.clone()],
+                        &mut parallel,
+                    );
+                }
+                let ends: Vec<(&str, &SignalNode)> = parallel
+                    .iter()
+                    .filter_map(|path| Some((path.name.as_str(), path.output()?)))
+                    .collect();
+                if ends.is_empty() {
+                    continue;
+                }
+                let (ports, excess) = combiner_parts(combiner, ends.len());
+                let reference = CombineReference {
+                    shared: Some(&shared),
+                    input_power_dbm: input.power_dbm,
+                    source_temperature_k: input.noise_temperature_k.unwrap_or(270.0),
+                };
+                let combined = combine_branches(
+                    &format!("{} Inputs", combiner.name),
+                    &ports,
+                    &ends,
+                    &reference,
+                );
+                nodes.push(combined.cascade_block(&excess));
+            }
+        }
+    }
+    outputs.push(BranchOutput {
+        name: name.to_string(),
+        nodes,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(name: &str, gain_db: f64, noise_figure_db: f64) -> Block {
+        Block {
+            name: name.to_string(),
+            gain_db,
+            noise_figure_db,
+            ..Default::default()
+        }
+    }
+
+    fn input() -> Input {
+        Input::new(1.0e9, 1.0e6, -60.0, Some(290.0))
+    }
+
+    #[test]
+    fn trunk_without_split_matches_straight_cascade() {
+        let blocks = vec![block("LNA", 20.0, 1.0), block("Pad", -3.0, 3.0)];
+        let tree = LineupTree::new(input(), Branch::new("Rx", blocks.clone()));
+        let expected = crate::cascade_vector_return_vector(input(), blocks);
+        let output = tree.branch_output("Rx").unwrap();
+        assert_eq!(output.nodes.len(), 2);
+        assert_eq!(
+            output.output().unwrap().cumulative_noise_figure_db,
+            expected[1].cumulative_noise_figure_db
+        );
+    }
+
+    #[test]
+    fn split_outputs_follow_each_branch() {
+        let trunk = Branch::new("Trunk", vec![block("LNA", 20.0, 1.0)]).split(
+            Block::splitter("Splitter", 4, 0.5),
+            vec![
+                Branch::new("Rx 1", vec![block("Cable", -1.0, 1.0)]),
+                Branch::new("Rx 2", vec![block("Cable", -5.0, 5.0)]).split(
+                    Block::splitter("Sub-Splitter", 2, 0.0),
+                    vec![Branch::new("Rx 2a", vec![]), Branch::new("Rx 2b", vec![])],
+                ),
+            ],
+        );
+        let tree = LineupTree::new(input(), trunk);
+        let names: Vec<&str> = tree.outputs().iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["Rx 1", "Rx 2a", "Rx 2b"]);
+        let rx_1 = tree.output("Rx 1").unwrap();
+        assert!((rx_1.signal_power_dbm - (-60.0 + 20.0 - 6.5206 - 1.0)).abs() < 1e-3);
+        let rx_2a = tree.output("Rx 2a").unwrap();
+        assert!((rx_2a.signal_power_dbm - (rx_1.signal_power_dbm - 4.0 - 3.0103)).abs() < 1e-3);
+        assert_eq!(tree.branch_output("Rx 2b").unwrap().nodes.len(), 4);
+        assert!(tree.output("Missing").is_none());
+    }
+
+    #[test]
+    fn split_and_combine_is_lossless_for_the_signal() {
+        // ideal splitter and combiner around two identical amplifiers: a balanced amplifier
+        let amp = |name: &str| Branch::new(name, vec![block("Amp", 20.0, 3.0)]);
+        let trunk = Branch::new("Balanced", vec![]).combine(
+            Block::splitter("Splitter", 2, 0.0),
+            vec![amp("A"), amp("B")],
+            Block::combiner("Combiner", 2, 0.0),
+        );
+        let tree = LineupTree::new(input(), trunk);
+        let output = tree.output("Balanced").unwrap();
+        let single = input().cascade_block(&block("Amp", 20.0, 3.0));
+        assert!((output.signal_power_dbm - (-40.0)).abs() < 1e-9);
+        // a lossless split adds no noise: the noise figure is that of one amplifier
+        assert!(
+            (output.cumulative_noise_figure_db - single.cumulative_noise_figure_db).abs() < 1e-9
+        );
+        let total: f64 = output.noise_contributions.iter().map(|c| c.percent).sum();
+        assert!((total - 100.0).abs() < 1e-9);
+        assert_eq!(output.noise_contributions[0].name, "Input");
+        assert!(output
+            .noise_contributions
+            .iter()
+            .any(|c| c.name == "A: Amp"));
+        assert_eq!(output.name, "Combiner Output");
+    }
+
+    #[test]
+    fn common_noise_combines_like_the_signal() {
+        // a noisy LNA before the split: its noise is common to both paths, so splitting
+        // and recombining through ideal parts leaves the SNR unchanged
+        let trunk = Branch::new("Path", vec![block("LNA", 30.0, 6.0)]).combine(
+            Block::splitter("Splitter", 2, 0.0),
+            vec![Branch::new("A", vec![]), Branch::new("B", vec![])],
+            Block::combiner("Combiner", 2, 0.0),
+        );
+        let tree = LineupTree::new(input(), trunk);
+        let path = tree.branch_output("Path").unwrap();
+        let lna = &path.nodes[0];
+        let output = path.output().unwrap();
+        assert!((output.signal_power_dbm - lna.signal_power_dbm).abs() < 1e-9);
+        assert!((output.signal_to_noise_ratio_db() - lna.signal_to_noise_ratio_db()).abs() < 0.1);
+    }
+
+    #[test]
+    fn display_tree_and_branch() {
+        let trunk = Branch::new("Trunk", vec![block("LNA", 20.0, 1.0)]).split(
+            Block::splitter("Splitter", 2, 0.0),
+            vec![Branch::new("A", vec![]), Branch::new("B", vec![])],
+        );
+        assert_eq!(
+            format!("{}", trunk),
+            "Branch { name: Trunk, stages: [LNA, Splitter -> 2 branches] }"
+        );
+        let tree = LineupTree::new(input(), trunk);
+        assert!(format!("{}", tree).ends_with("outputs: [A, B] }"));
+    }
+}
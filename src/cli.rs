@@ -3,15 +3,33 @@ use std::fs;
 use std::path::Path;
 use std::process;
 
-// this cannot be crate::Network because of how Cargo works,
-// since cargo/rust treats lib.rs and main.rs as separate crates
 use crate::cascade_vector_return_vector;
 use crate::file_operations;
+use crate::mismatch_interfaces;
+use crate::sparameters::mismatch_adjusted_blocks;
+use crate::validate_lineup;
+use crate::Adc;
 use crate::Block;
+use crate::BlockBuilder;
+use crate::BlockKind;
+use crate::Complex;
+use crate::CompressionModel;
+use crate::Diagnostic;
+use crate::DigitalStage;
+use crate::Filter;
+use crate::GainlineupError;
+use crate::Injection;
 use crate::Input;
+use crate::Interpolation;
+use crate::MismatchInterface;
+use crate::Mixer;
+use crate::MixerNoiseFigure;
+use crate::NoiseParameters;
+use crate::PhaseNoise;
+use crate::SParameterPoint;
+use crate::SParameters;
 use crate::SignalNode;
-
-use touchstone::Network;
+use crate::TouchstoneNetwork;
 
 use serde::Deserialize;
 
@@ -25,6 +43,12 @@ pub struct Config {
     pub frequency_hz: f64,
     pub bandwidth_hz: Option<f64>,
     pub noise_temperature_k: Option<f64>,
+    /// Include the mismatch between adjacent blocks in the cascaded gain.
+    pub mismatch: bool,
+    /// Phase noise of the source.
+    pub phase_noise: Option<PhaseNoise>,
+    /// Peak-to-average power ratio of the signal envelope in dB.
+    pub papr_db: f64,
     pub blocks: Vec<Block>,
 }
 
@@ -39,13 +63,41 @@ enum BlockConfig {
     Explicit {
         name: String,
         #[serde(alias = "gain")]
-        gain_db: f64,
+        gain_db: Option<f64>,
+        gain_linear: Option<f64>,
         #[serde(alias = "noise_figure", alias = "nf")]
-        noise_figure_db: f64,
+        noise_figure_db: Option<f64>,
+        #[serde(alias = "noise_temperature", alias = "te")]
+        noise_temperature_k: Option<f64>,
         #[serde(alias = "output_p1db", alias = "op1db")]
         output_p1db_dbm: Option<f64>,
+        #[serde(alias = "input_p1db", alias = "ip1db")]
+        input_p1db_dbm: Option<f64>,
         #[serde(default, alias = "output_ip3", alias = "oip3")]
         output_ip3_dbm: Option<f64>,
+        #[serde(alias = "input_ip3", alias = "iip3")]
+        input_ip3_dbm: Option<f64>,
+        #[serde(alias = "output_ip2", alias = "oip2")]
+        output_ip2_dbm: Option<f64>,
+        #[serde(alias = "input_ip2", alias = "iip2")]
+        input_ip2_dbm: Option<f64>,
+        #[serde(alias = "oh2")]
+        output_h2_intercept_dbm: Option<f64>,
+        #[serde(alias = "oh3")]
+        output_h3_intercept_dbm: Option<f64>,
+        // [dbc, output_power_dbm]
+        second_harmonic_dbc: Option<(f64, f64)>,
+        third_harmonic_dbc: Option<(f64, f64)>,
+        #[serde(alias = "am_pm")]
+        am_pm_coefficient_deg_per_db: Option<f64>,
+        #[serde(alias = "phase")]
+        phase_deg: Option<f64>,
+        #[serde(alias = "group_delay")]
+        group_delay_s: Option<f64>,
+        #[serde(default, alias = "compression")]
+        compression_model: CompressionConfig,
+        input_vswr: Option<f64>,
+        output_vswr: Option<f64>,
     },
     Touchstone {
         file_path: String,
@@ -54,15 +106,234 @@ enum BlockConfig {
         noise_figure_db: Option<f64>,
         #[serde(alias = "output_p1db", alias = "op1db")]
         output_p1db_dbm: Option<f64>,
+        #[serde(alias = "input_p1db", alias = "ip1db")]
+        input_p1db_dbm: Option<f64>,
+        #[serde(default, alias = "output_ip3", alias = "oip3")]
+        output_ip3_dbm: Option<f64>,
+        #[serde(alias = "input_ip3", alias = "iip3")]
+        input_ip3_dbm: Option<f64>,
+        #[serde(alias = "output_ip2", alias = "oip2")]
+        output_ip2_dbm: Option<f64>,
+        #[serde(alias = "input_ip2", alias = "iip2")]
+        input_ip2_dbm: Option<f64>,
+        #[serde(alias = "oh2")]
+        output_h2_intercept_dbm: Option<f64>,
+        #[serde(alias = "oh3")]
+        output_h3_intercept_dbm: Option<f64>,
+        // [dbc, output_power_dbm]
+        second_harmonic_dbc: Option<(f64, f64)>,
+        third_harmonic_dbc: Option<(f64, f64)>,
+        #[serde(alias = "am_pm")]
+        am_pm_coefficient_deg_per_db: Option<f64>,
+        #[serde(default, alias = "compression")]
+        compression_model: CompressionConfig,
+        #[serde(default)]
+        interpolation: InterpolationType,
+        input_port: Option<usize>,
+        output_port: Option<usize>,
+        #[serde(alias = "termination")]
+        termination_ohms: Option<f64>,
+    },
+    Mixer {
+        name: String,
+        #[serde(alias = "conversion_gain", alias = "gain_db", alias = "gain")]
+        conversion_gain_db: f64,
+        #[serde(alias = "noise_figure", alias = "nf")]
+        noise_figure_db: f64,
+        #[serde(default)]
+        noise_figure_type: NoiseFigureType,
+        #[serde(alias = "lo_frequency", alias = "f_lo")]
+        lo_frequency_hz: f64,
+        #[serde(default)]
+        injection: InjectionType,
+        #[serde(alias = "lo_power")]
+        lo_power_dbm: Option<f64>,
+        // [[offset_hz, dbc_per_hz], ...]
+        lo_phase_noise: Option<Vec<(f64, f64)>>,
+        lo_multiplier: Option<f64>,
+        lo_divider: Option<f64>,
+        #[serde(alias = "output_p1db", alias = "op1db")]
+        output_p1db_dbm: Option<f64>,
+        #[serde(alias = "input_p1db", alias = "ip1db")]
+        input_p1db_dbm: Option<f64>,
+        #[serde(default, alias = "output_ip3", alias = "oip3")]
+        output_ip3_dbm: Option<f64>,
+        #[serde(alias = "input_ip3", alias = "iip3")]
+        input_ip3_dbm: Option<f64>,
+        #[serde(alias = "output_ip2", alias = "oip2")]
+        output_ip2_dbm: Option<f64>,
+        #[serde(alias = "input_ip2", alias = "iip2")]
+        input_ip2_dbm: Option<f64>,
+        #[serde(alias = "oh2")]
+        output_h2_intercept_dbm: Option<f64>,
+        #[serde(alias = "oh3")]
+        output_h3_intercept_dbm: Option<f64>,
+        // [dbc, output_power_dbm]
+        second_harmonic_dbc: Option<(f64, f64)>,
+        third_harmonic_dbc: Option<(f64, f64)>,
+        #[serde(default, alias = "compression")]
+        compression_model: CompressionConfig,
+    },
+    Filter {
+        name: String,
+        #[serde(alias = "insertion_loss", alias = "il")]
+        insertion_loss_db: f64,
+        #[serde(alias = "center_frequency", alias = "fc")]
+        center_frequency_hz: f64,
+        #[serde(alias = "bandwidth", alias = "bw")]
+        bandwidth_hz: f64,
+        #[serde(alias = "noise_bandwidth")]
+        noise_bandwidth_hz: Option<f64>,
+        // [[offset_hz, rejection_db], ...]
+        #[serde(default)]
+        rejection: Vec<(f64, f64)>,
+    },
+    // one path through an N-way splitter or combiner: 10*log10(N) plus the excess loss
+    Splitter {
+        name: String,
+        ways: usize,
+        #[serde(default, alias = "excess_loss")]
+        excess_loss_db: f64,
+    },
+    Combiner {
+        name: String,
+        ways: usize,
+        #[serde(default, alias = "excess_loss")]
+        excess_loss_db: f64,
+    },
+    Adc {
+        name: String,
+        #[serde(alias = "full_scale")]
+        full_scale_dbm: f64,
+        #[serde(alias = "sample_rate", alias = "fs")]
+        sample_rate_hz: f64,
+        enob: Option<f64>,
+        #[serde(alias = "snr")]
+        snr_db: Option<f64>,
+        #[serde(alias = "sfdr")]
+        sfdr_db: Option<f64>,
+        #[serde(alias = "aperture_jitter")]
+        aperture_jitter_s: Option<f64>,
+        #[serde(alias = "noise_bandwidth")]
+        noise_bandwidth_hz: Option<f64>,
+    },
+    // NCO, channel/decimation filter (low-pass at 0 Hz by default), decimation and rounding
+    Digital {
+        name: String,
+        #[serde(default, alias = "gain")]
+        gain_db: f64,
+        #[serde(alias = "nco_frequency", alias = "f_nco")]
+        nco_frequency_hz: Option<f64>,
+        #[serde(default = "default_decimation")]
+        decimation: u32,
+        #[serde(alias = "bits")]
+        output_bits: Option<u32>,
+        #[serde(default, alias = "center_frequency", alias = "fc")]
+        center_frequency_hz: f64,
+        #[serde(alias = "bandwidth", alias = "bw")]
+        bandwidth_hz: Option<f64>,
+        #[serde(alias = "noise_bandwidth")]
+        noise_bandwidth_hz: Option<f64>,
+        // [[offset_hz, rejection_db], ...]
+        #[serde(default)]
+        rejection: Vec<(f64, f64)>,
     },
     Include {
         path: String,
     },
 }
 
-pub fn load_config(path: &str) -> Result<Config, Box<dyn std::error::Error>> {
+// compression_model = { type = "rapp", smoothness = 2.0, saturation_power_dbm = 20.0 }
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CompressionConfig {
+    HardClip,
+    Rapp {
+        #[serde(default = "default_rapp_smoothness", alias = "p")]
+        smoothness: f64,
+        #[serde(alias = "saturation_power", alias = "psat")]
+        saturation_power_dbm: Option<f64>,
+    },
+    Saleh,
+    Tanh,
+    Cubic,
+}
+
+// the same Rapp model as `CompressionModel::default()`
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig::Rapp {
+            smoothness: default_rapp_smoothness(),
+            saturation_power_dbm: None,
+        }
+    }
+}
+
+fn default_rapp_smoothness() -> f64 {
+    CompressionModel::DEFAULT_RAPP_SMOOTHNESS
+}
+
+fn default_decimation() -> u32 {
+    1
+}
+
+impl From<CompressionConfig> for CompressionModel {
+    fn from(config: CompressionConfig) -> Self {
+        match config {
+            CompressionConfig::HardClip => CompressionModel::HardClip,
+            CompressionConfig::Rapp {
+                smoothness,
+                saturation_power_dbm,
+            } => CompressionModel::Rapp {
+                smoothness,
+                saturation_power_dbm,
+            },
+            CompressionConfig::Saleh => CompressionModel::Saleh,
+            CompressionConfig::Tanh => CompressionModel::Tanh,
+            CompressionConfig::Cubic => CompressionModel::Cubic,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum NoiseFigureType {
+    #[default]
+    Ssb,
+    Dsb,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum InjectionType {
+    #[default]
+    LowSide,
+    HighSide,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum InterpolationType {
+    #[default]
+    Linear,
+    #[serde(alias = "db")]
+    DbLinear,
+    Cubic,
+}
+
+impl From<InterpolationType> for Interpolation {
+    fn from(interpolation: InterpolationType) -> Self {
+        match interpolation {
+            InterpolationType::Linear => Interpolation::Linear,
+            InterpolationType::DbLinear => Interpolation::DbLinear,
+            InterpolationType::Cubic => Interpolation::Cubic,
+        }
+    }
+}
+
+pub fn load_config(path: &str) -> Result<Config, GainlineupError> {
     tracing::debug!("Loading config: {}", path);
-    let config_content = fs::read_to_string(path)?;
+    let config_content = read_to_string(Path::new(path))?;
     tracing::trace!("Config content: {}", config_content);
 
     // We need an intermediate struct to parse the TOML because Config now holds Vec<Block>
@@ -77,16 +348,28 @@ pub fn load_config(path: &str) -> Result<Config, Box<dyn std::error::Error>> {
         bandwidth_hz: Option<f64>,
         #[serde(alias = "noise_temperature")]
         noise_temperature_k: Option<f64>,
+        #[serde(default)]
+        mismatch: bool,
+        // [[offset_hz, dbc_per_hz], ...]
+        phase_noise: Option<Vec<(f64, f64)>>,
+        #[serde(default, alias = "papr")]
+        papr_db: f64,
         blocks: Vec<BlockConfig>,
     }
 
-    let intermediate_config: IntermediateConfig = toml::from_str(&config_content)?;
+    let intermediate_config: IntermediateConfig = toml::from_str(&config_content)
+        .map_err(|e| GainlineupError::toml_parse(path.into(), &config_content, &e))?;
     tracing::debug!("Parsed config successfully");
 
     let mut blocks = Vec::new();
     let config_path = Path::new(path);
     let base_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
 
+    let phase_noise = intermediate_config
+        .phase_noise
+        .map(|profile| phase_noise_from_config("Input", "phase_noise", &profile, None, None))
+        .transpose()?;
+
     load_blocks_recursive(
         intermediate_config.blocks,
         intermediate_config.frequency_hz,
@@ -101,6 +384,9 @@ pub fn load_config(path: &str) -> Result<Config, Box<dyn std::error::Error>> {
         frequency_hz: intermediate_config.frequency_hz,
         bandwidth_hz: intermediate_config.bandwidth_hz,
         noise_temperature_k: intermediate_config.noise_temperature_k,
+        mismatch: intermediate_config.mismatch,
+        phase_noise,
+        papr_db: intermediate_config.papr_db,
         blocks,
     })
 }
@@ -110,119 +396,779 @@ fn load_blocks_recursive(
     frequency: f64,
     blocks: &mut Vec<Block>,
     base_dir: &Path,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), GainlineupError> {
     for block_config in block_configs {
         match block_config {
             BlockConfig::Explicit {
                 name,
                 gain_db,
+                gain_linear,
                 noise_figure_db,
+                noise_temperature_k,
                 output_p1db_dbm,
+                input_p1db_dbm,
                 output_ip3_dbm,
+                input_ip3_dbm,
+                output_ip2_dbm,
+                input_ip2_dbm,
+                output_h2_intercept_dbm,
+                output_h3_intercept_dbm,
+                second_harmonic_dbc,
+                third_harmonic_dbc,
+                am_pm_coefficient_deg_per_db,
+                phase_deg,
+                group_delay_s,
+                compression_model,
+                input_vswr,
+                output_vswr,
             } => {
-                blocks.push(Block {
-                    name,
-                    gain_db,
-                    noise_figure_db,
-                    output_p1db_dbm,
-                    output_ip3_dbm,
-                });
+                let mut builder = Block::builder(&name).compression_model(compression_model.into());
+                if let Some(am_pm) = am_pm_coefficient_deg_per_db {
+                    builder = builder.am_pm_coefficient_deg_per_db(am_pm);
+                }
+                if let Some(phase_deg) = phase_deg {
+                    builder = builder.phase_deg(phase_deg);
+                }
+                if let Some(group_delay_s) = group_delay_s {
+                    builder = builder.group_delay_s(group_delay_s);
+                }
+                if let Some(input_vswr) = input_vswr {
+                    builder = builder.input_vswr(input_vswr);
+                }
+                if let Some(output_vswr) = output_vswr {
+                    builder = builder.output_vswr(output_vswr);
+                }
+                if let Some(gain_db) = gain_db {
+                    builder = builder.gain_db(gain_db);
+                }
+                if let Some(gain_linear) = gain_linear {
+                    builder = builder.gain_linear(gain_linear);
+                }
+                if gain_db.is_none() && gain_linear.is_none() {
+                    return Err(GainlineupError::invalid_parameter(
+                        &name,
+                        "gain",
+                        "missing, set gain_db or gain_linear",
+                    ));
+                }
+                if let Some(noise_figure_db) = noise_figure_db {
+                    builder = builder.noise_figure_db(noise_figure_db);
+                }
+                if let Some(noise_temperature_k) = noise_temperature_k {
+                    builder = builder.noise_temperature_k(noise_temperature_k);
+                }
+                if noise_figure_db.is_none() && noise_temperature_k.is_none() {
+                    return Err(GainlineupError::invalid_parameter(
+                        &name,
+                        "noise figure",
+                        "missing, set noise_figure_db or noise_temperature_k",
+                    ));
+                }
+                let builder = with_linearity_specs(
+                    builder,
+                    (output_p1db_dbm, input_p1db_dbm),
+                    (output_ip3_dbm, input_ip3_dbm),
+                    (output_ip2_dbm, input_ip2_dbm),
+                );
+                let builder = with_harmonic_specs(
+                    builder,
+                    (output_h2_intercept_dbm, second_harmonic_dbc),
+                    (output_h3_intercept_dbm, third_harmonic_dbc),
+                );
+                blocks.push(builder.build()?);
             }
             BlockConfig::Touchstone {
                 file_path,
                 name,
                 noise_figure_db,
                 output_p1db_dbm,
+                input_p1db_dbm,
+                output_ip3_dbm,
+                input_ip3_dbm,
+                output_ip2_dbm,
+                input_ip2_dbm,
+                output_h2_intercept_dbm,
+                output_h3_intercept_dbm,
+                second_harmonic_dbc,
+                third_harmonic_dbc,
+                am_pm_coefficient_deg_per_db,
+                compression_model,
+                interpolation,
+                input_port,
+                output_port,
+                termination_ohms,
             } => {
+                let ports = TouchstonePorts {
+                    input_port: input_port.unwrap_or(1),
+                    output_port: output_port.unwrap_or(2),
+                    termination_ohms,
+                };
+                // evaluate at the frequency reaching this block, i.e. the IF after a mixer
+                let frequency = blocks
+                    .iter()
+                    .fold(frequency, |f, block| block.output_frequency_hz(f));
+
                 // Touchstone files might also be relative to the config file
                 let full_path = base_dir.join(&file_path);
                 let TouchstoneValid {
                     contains_frequency,
                     gain,
-                } = touchstone_file_path_and_frequency_to_struct(
+                    phase_deg,
+                    group_delay_s,
+                    s_parameters,
+                    has_noise_data,
+                    noise_parameters,
+                    port_count,
+                } = touchstone_path_to_struct(
                     full_path.to_string_lossy().to_string(),
                     frequency,
-                );
+                    interpolation.into(),
+                    ports,
+                )?;
+                ports.check(&name, port_count)?;
+
+                let gain = match gain {
+                    Some(gain) if contains_frequency => gain,
+                    _ => {
+                        // report the path relative to the config file, as written
+                        return Err(GainlineupError::TouchstoneFrequencyNotFound {
+                            path: file_path.into(),
+                            frequency_hz: frequency,
+                        });
+                    }
+                };
+                let final_noise_figure = match (noise_figure_db, noise_parameters) {
+                    (Some(noise_figure_db), _) => noise_figure_db,
+                    (None, Some(noise_parameters)) => {
+                        // the previous stage's output is the source, or a matched 50 Ω source
+                        let source_reflection = blocks
+                            .last()
+                            .map_or(Complex::ZERO, |previous| previous.two_port().s22);
+                        let noise_figure_db = noise_parameters.noise_figure_db(source_reflection);
+                        tracing::debug!(
+                            block = %name,
+                            source_reflection = source_reflection.abs(),
+                            noise_figure_db,
+                            "Noise figure from touchstone noise parameters"
+                        );
+                        noise_figure_db
+                    }
+                    (None, None) if has_noise_data => {
+                        return Err(GainlineupError::invalid_parameter(
+                            &name,
+                            "noise figure",
+                            format!(
+                                "{} Hz is outside the noise data of {}, set noise_figure_db",
+                                frequency, file_path
+                            ),
+                        ));
+                    }
+                    (None, None) => -gain, // no noise data, so treat it as a passive
+                };
 
-                if !contains_frequency {
-                    let file_path_relative_to_config = file_path.clone();
-                    return Err(format!(
-                        "Frequency {} Hz not found in touchstone file {}",
-                        frequency, file_path_relative_to_config
-                    )
-                    .into());
+                let mut builder = Block::builder(&name)
+                    .gain_db(gain)
+                    .noise_figure_db(final_noise_figure)
+                    .compression_model(compression_model.into());
+                if let Some(am_pm) = am_pm_coefficient_deg_per_db {
+                    builder = builder.am_pm_coefficient_deg_per_db(am_pm);
                 }
-
-                let gain = gain.unwrap();
-                let noise_figure_default = -gain; // only handles passives right now
-                let output_p1db_default = 99.0; // 99 dBm
-
-                let final_noise_figure = noise_figure_db.unwrap_or(noise_figure_default);
-                let final_output_p1db = output_p1db_dbm.or(Some(output_p1db_default));
-
-                blocks.push(Block {
-                    name,
-                    gain_db: gain,
-                    noise_figure_db: final_noise_figure,
-                    output_p1db_dbm: final_output_p1db,
-                    output_ip3_dbm: None,
-                });
+                if let Some(phase_deg) = phase_deg {
+                    builder = builder.phase_deg(phase_deg);
+                }
+                if let Some(group_delay_s) = group_delay_s {
+                    builder = builder.group_delay_s(group_delay_s);
+                }
+                // input-referred specs use the gain read from the file
+                let builder = with_linearity_specs(
+                    builder,
+                    (output_p1db_dbm, input_p1db_dbm),
+                    (output_ip3_dbm, input_ip3_dbm),
+                    (output_ip2_dbm, input_ip2_dbm),
+                );
+                let builder = with_harmonic_specs(
+                    builder,
+                    (output_h2_intercept_dbm, second_harmonic_dbc),
+                    (output_h3_intercept_dbm, third_harmonic_dbc),
+                );
+                let mut block = builder.build()?;
+                block.s_parameters = s_parameters;
+                blocks.push(block);
+            }
+            BlockConfig::Mixer {
+                name,
+                conversion_gain_db,
+                noise_figure_db,
+                noise_figure_type,
+                lo_frequency_hz,
+                injection,
+                lo_power_dbm,
+                lo_phase_noise,
+                lo_multiplier,
+                lo_divider,
+                output_p1db_dbm,
+                input_p1db_dbm,
+                output_ip3_dbm,
+                input_ip3_dbm,
+                output_ip2_dbm,
+                input_ip2_dbm,
+                output_h2_intercept_dbm,
+                output_h3_intercept_dbm,
+                second_harmonic_dbc,
+                third_harmonic_dbc,
+                compression_model,
+            } => {
+                let noise_figure = match noise_figure_type {
+                    NoiseFigureType::Ssb => MixerNoiseFigure::Ssb(noise_figure_db),
+                    NoiseFigureType::Dsb => MixerNoiseFigure::Dsb(noise_figure_db),
+                };
+                let injection = match injection {
+                    InjectionType::LowSide => Injection::LowSide,
+                    InjectionType::HighSide => Injection::HighSide,
+                };
+                let lo_phase_noise = lo_phase_noise
+                    .map(|profile| {
+                        phase_noise_from_config(
+                            &name,
+                            "lo_phase_noise",
+                            &profile,
+                            lo_multiplier,
+                            lo_divider,
+                        )
+                    })
+                    .transpose()?;
+                let mixer = Mixer {
+                    lo_frequency_hz,
+                    injection,
+                    lo_power_dbm,
+                    lo_phase_noise,
+                };
+                let builder = Block::builder(&name)
+                    .gain_db(conversion_gain_db)
+                    .noise_figure_db(noise_figure.ssb_db())
+                    .kind(BlockKind::Mixer(mixer))
+                    .compression_model(compression_model.into());
+                let builder = with_linearity_specs(
+                    builder,
+                    (output_p1db_dbm, input_p1db_dbm),
+                    (output_ip3_dbm, input_ip3_dbm),
+                    (output_ip2_dbm, input_ip2_dbm),
+                );
+                let builder = with_harmonic_specs(
+                    builder,
+                    (output_h2_intercept_dbm, second_harmonic_dbc),
+                    (output_h3_intercept_dbm, third_harmonic_dbc),
+                );
+                blocks.push(builder.build()?);
+            }
+            BlockConfig::Filter {
+                name,
+                insertion_loss_db,
+                center_frequency_hz,
+                bandwidth_hz,
+                noise_bandwidth_hz,
+                rejection,
+            } => {
+                let filter = Filter {
+                    noise_bandwidth_hz,
+                    ..Filter::new(center_frequency_hz, bandwidth_hz).with_rejection(rejection)
+                };
+                blocks.push(Block::filter(&name, insertion_loss_db, filter));
+            }
+            BlockConfig::Splitter {
+                name,
+                ways,
+                excess_loss_db,
+            } => blocks.push(Block::splitter(&name, ways, excess_loss_db)),
+            BlockConfig::Combiner {
+                name,
+                ways,
+                excess_loss_db,
+            } => blocks.push(Block::combiner(&name, ways, excess_loss_db)),
+            BlockConfig::Adc {
+                name,
+                full_scale_dbm,
+                sample_rate_hz,
+                enob,
+                snr_db,
+                sfdr_db,
+                aperture_jitter_s,
+                noise_bandwidth_hz,
+            } => {
+                let mut adc = match (enob, snr_db) {
+                    (Some(enob), None) => Adc::from_enob(full_scale_dbm, sample_rate_hz, enob),
+                    (None, Some(snr_db)) => Adc::new(full_scale_dbm, sample_rate_hz, snr_db),
+                    (Some(_), Some(_)) => {
+                        return Err(GainlineupError::invalid_parameter(
+                            &name,
+                            "SNR",
+                            "set either enob or snr_db, not both",
+                        ));
+                    }
+                    (None, None) => {
+                        return Err(GainlineupError::invalid_parameter(
+                            &name,
+                            "SNR",
+                            "missing, set enob or snr_db",
+                        ));
+                    }
+                };
+                adc.sfdr_db = sfdr_db;
+                adc.aperture_jitter_s = aperture_jitter_s;
+                adc.noise_bandwidth_hz = noise_bandwidth_hz;
+                blocks.push(Block::adc(&name, adc));
+            }
+            BlockConfig::Digital {
+                name,
+                gain_db,
+                nco_frequency_hz,
+                decimation,
+                output_bits,
+                center_frequency_hz,
+                bandwidth_hz,
+                noise_bandwidth_hz,
+                rejection,
+            } => {
+                let filter = match bandwidth_hz {
+                    Some(bandwidth_hz) => Some(Filter {
+                        noise_bandwidth_hz,
+                        ..Filter::new(center_frequency_hz, bandwidth_hz).with_rejection(rejection)
+                    }),
+                    None if noise_bandwidth_hz.is_some() || !rejection.is_empty() => {
+                        return Err(GainlineupError::invalid_parameter(
+                            &name,
+                            "bandwidth_hz",
+                            "missing, the digital filter needs a passband bandwidth",
+                        ));
+                    }
+                    None => None,
+                };
+                let stage = DigitalStage {
+                    nco_frequency_hz,
+                    filter,
+                    decimation,
+                    output_bits,
+                };
+                blocks.push(Block::digital(&name, gain_db, stage));
             }
             BlockConfig::Include { path } => {
                 let included_path = base_dir.join(&path);
                 tracing::debug!("Loading included config: {}", included_path.display());
-                let content = fs::read_to_string(&included_path)?;
-                let included: IncludedConfig = toml::from_str(&content)?;
+                let content = read_to_string(&included_path)
+                    .map_err(|e| e.in_include(included_path.clone()))?;
+                let included: IncludedConfig = toml::from_str(&content).map_err(|e| {
+                    GainlineupError::toml_parse(included_path.clone(), &content, &e)
+                        .in_include(included_path.clone())
+                })?;
 
                 let new_base_dir = included_path.parent().unwrap_or_else(|| Path::new("."));
-                load_blocks_recursive(included.blocks, frequency, blocks, new_base_dir)?;
+                load_blocks_recursive(included.blocks, frequency, blocks, new_base_dir)
+                    .map_err(|e| e.in_include(included_path.clone()))?;
             }
         }
     }
     Ok(())
 }
 
+/// Read a file, attaching its path to any I/O error.
+fn read_to_string(path: &Path) -> Result<String, GainlineupError> {
+    fs::read_to_string(path).map_err(|source| GainlineupError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Apply each `(output-referred, input-referred)` linearity spec pair that is set.
+fn with_linearity_specs(
+    mut builder: BlockBuilder,
+    (output_p1db_dbm, input_p1db_dbm): (Option<f64>, Option<f64>),
+    (output_ip3_dbm, input_ip3_dbm): (Option<f64>, Option<f64>),
+    (output_ip2_dbm, input_ip2_dbm): (Option<f64>, Option<f64>),
+) -> BlockBuilder {
+    if let Some(output_p1db_dbm) = output_p1db_dbm {
+        builder = builder.output_p1db_dbm(output_p1db_dbm);
+    }
+    if let Some(input_p1db_dbm) = input_p1db_dbm {
+        builder = builder.input_p1db_dbm(input_p1db_dbm);
+    }
+    if let Some(output_ip3_dbm) = output_ip3_dbm {
+        builder = builder.output_ip3_dbm(output_ip3_dbm);
+    }
+    if let Some(input_ip3_dbm) = input_ip3_dbm {
+        builder = builder.input_ip3_dbm(input_ip3_dbm);
+    }
+    if let Some(output_ip2_dbm) = output_ip2_dbm {
+        builder = builder.output_ip2_dbm(output_ip2_dbm);
+    }
+    if let Some(input_ip2_dbm) = input_ip2_dbm {
+        builder = builder.input_ip2_dbm(input_ip2_dbm);
+    }
+    builder
+}
+
+/// Apply each `(intercept, [dbc, output_power_dbm])` harmonic spec pair that is set.
+fn with_harmonic_specs(
+    mut builder: BlockBuilder,
+    (output_h2_intercept_dbm, second_harmonic_dbc): (Option<f64>, Option<(f64, f64)>),
+    (output_h3_intercept_dbm, third_harmonic_dbc): (Option<f64>, Option<(f64, f64)>),
+) -> BlockBuilder {
+    if let Some(output_h2_intercept_dbm) = output_h2_intercept_dbm {
+        builder = builder.output_h2_intercept_dbm(output_h2_intercept_dbm);
+    }
+    if let Some((dbc, output_power_dbm)) = second_harmonic_dbc {
+        builder = builder.second_harmonic_dbc(dbc, output_power_dbm);
+    }
+    if let Some(output_h3_intercept_dbm) = output_h3_intercept_dbm {
+        builder = builder.output_h3_intercept_dbm(output_h3_intercept_dbm);
+    }
+    if let Some((dbc, output_power_dbm)) = third_harmonic_dbc {
+        builder = builder.third_harmonic_dbc(dbc, output_power_dbm);
+    }
+    builder
+}
+
+/// A phase noise profile from `[[offset_hz, dbc_per_hz], ...]`, scaled by an LO
+/// multiplier and divider.
+fn phase_noise_from_config(
+    block: &str,
+    parameter: &str,
+    profile: &[(f64, f64)],
+    multiplier: Option<f64>,
+    divider: Option<f64>,
+) -> Result<PhaseNoise, GainlineupError> {
+    if profile.is_empty() {
+        return Err(GainlineupError::invalid_parameter(
+            block,
+            parameter,
+            "empty, give [[offset_hz, dbc_per_hz], ...]",
+        ));
+    }
+    if let Some(&(offset_hz, _)) = profile.iter().find(|(offset_hz, _)| *offset_hz <= 0.0) {
+        return Err(GainlineupError::invalid_parameter(
+            block,
+            parameter,
+            format!("offset {} Hz (must be positive)", offset_hz),
+        ));
+    }
+    for (ratio_parameter, ratio) in [("lo_multiplier", multiplier), ("lo_divider", divider)] {
+        if let Some(ratio) = ratio {
+            if ratio <= 0.0 {
+                return Err(GainlineupError::invalid_parameter(
+                    block,
+                    ratio_parameter,
+                    format!("{} (must be positive)", ratio),
+                ));
+            }
+        }
+    }
+    Ok(PhaseNoise::new(profile)
+        .multiplied(multiplier.unwrap_or(1.0))
+        .divided(divider.unwrap_or(1.0)))
+}
+
+/// S21 of a Touchstone file at one frequency; `gain`, `phase_deg`, `group_delay_s` and
+/// `s_parameters` are `None` outside its range.
+///
+/// `noise_parameters` is `None` if the file has no noise data or it does not cover the
+/// frequency; `has_noise_data` tells the two apart.
 pub struct TouchstoneValid {
-    contains_frequency: bool,
-    gain: Option<f64>,
+    pub contains_frequency: bool,
+    pub gain: Option<f64>,
+    pub phase_deg: Option<f64>,
+    pub group_delay_s: Option<f64>,
+    pub s_parameters: Option<SParameters>,
+    pub has_noise_data: bool,
+    pub noise_parameters: Option<NoiseParameters>,
+    pub port_count: usize,
+}
+
+/// Read and parse a Touchstone (`.sNp`) file, with the noise parameters of a `.s2p` file.
+///
+/// The port count comes from the file extension.
+pub fn load_touchstone(file_path: &str) -> Result<TouchstoneNetwork, GainlineupError> {
+    let malformed = |message: String| GainlineupError::MalformedTouchstone {
+        path: file_path.into(),
+        message,
+    };
+    let port_count = Path::new(file_path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .and_then(|extension| {
+            extension
+                .strip_prefix('s')?
+                .strip_suffix('p')?
+                .parse::<usize>()
+                .ok()
+        })
+        .ok_or_else(|| malformed("only .sNp files are supported".to_string()))?;
+    let content = read_to_string(Path::new(file_path))?;
+    let network = TouchstoneNetwork::parse(&content, port_count).map_err(malformed)?;
+    tracing::debug!(
+        num_ports = network.port_count,
+        num_frequencies = network.frequencies_hz.len(),
+        num_noise_frequencies = network.noise.len(),
+        "Parsed touchstone file"
+    );
+    Ok(network)
+}
+
+/// S21 of a network as dB/degree points, sorted by frequency.
+pub fn s21_points(network: &TouchstoneNetwork) -> Vec<SParameterPoint> {
+    network.s_parameter_points(2, 1)
+}
+
+/// Which path through a Touchstone network a block uses.
+///
+/// The default is port 1 to port 2. Every other port is terminated in `termination_ohms`,
+/// or in the file's reference impedance if that is `None`. Swap the ports to use the
+/// network in the reverse direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchstonePorts {
+    pub input_port: usize,
+    pub output_port: usize,
+    pub termination_ohms: Option<f64>,
+}
+
+impl Default for TouchstonePorts {
+    fn default() -> Self {
+        Self {
+            input_port: 1,
+            output_port: 2,
+            termination_ohms: None,
+        }
+    }
+}
+
+impl TouchstonePorts {
+    /// Check the ports against a network with `port_count` ports.
+    pub fn check(&self, block: &str, port_count: usize) -> Result<(), GainlineupError> {
+        for (parameter, port) in [
+            ("input_port", self.input_port),
+            ("output_port", self.output_port),
+        ] {
+            if !(1..=port_count).contains(&port) {
+                return Err(GainlineupError::invalid_parameter(
+                    block,
+                    parameter,
+                    format!(
+                        "port {} is not one of the file's {} port(s)",
+                        port, port_count
+                    ),
+                ));
+            }
+        }
+        if self.input_port == self.output_port {
+            return Err(GainlineupError::invalid_parameter(
+                block,
+                "output_port",
+                format!("port {} is also the input port", self.output_port),
+            ));
+        }
+        if let Some(termination_ohms) = self.termination_ohms {
+            if termination_ohms.is_nan() || termination_ohms < 0.0 {
+                return Err(GainlineupError::invalid_parameter(
+                    block,
+                    "termination_ohms",
+                    format!("{} ohm (must not be negative)", termination_ohms),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reflection coefficient of the termination on the unused ports.
+    fn termination_reflection(&self, z0: f64) -> Complex {
+        match self.termination_ohms {
+            None => Complex::ZERO,
+            Some(termination_ohms) if termination_ohms.is_infinite() => Complex::ONE,
+            Some(termination_ohms) => {
+                Complex::new((termination_ohms - z0) / (termination_ohms + z0), 0.0)
+            }
+        }
+    }
 }
 
 pub fn touchstone_file_path_and_frequency_to_struct(
     file_path: String,
     frequency_in_hz: f64,
-) -> TouchstoneValid {
-    tracing::debug!("Loading touchstone file: {}", file_path);
-    let s2p = Network::new(file_path.clone());
+) -> Result<TouchstoneValid, GainlineupError> {
+    touchstone_path_to_struct(
+        file_path,
+        frequency_in_hz,
+        Interpolation::Linear,
+        TouchstonePorts::default(),
+    )
+}
 
-    // check if frequency is within the touchstone file
+/// Like [`touchstone_file_path_and_frequency_to_struct`], with `interpolation` between
+/// the file's frequencies and for the path through `ports`.
+///
+/// `gain`, `phase_deg` and `s_parameters` are also `None` if `ports` do not fit the file
+/// (see [`TouchstonePorts::check`] and `port_count`). Noise parameters describe the
+/// forward 1 → 2 path of a 2-port, so they are only used for that path.
+pub fn touchstone_path_to_struct(
+    file_path: String,
+    frequency_in_hz: f64,
+    interpolation: Interpolation,
+    ports: TouchstonePorts,
+) -> Result<TouchstoneValid, GainlineupError> {
+    tracing::debug!("Loading touchstone file: {}", file_path);
+    let network = load_touchstone(&file_path)?;
+    let port_count = network.port_count;
+    let noise = if ports == TouchstonePorts::default() {
+        network.noise.clone()
+    } else {
+        vec![]
+    };
+    let noise_parameters = NoiseParameters::interpolate(&noise, frequency_in_hz);
+
+    // frequencies between file points are interpolated, but never extrapolated
+    let contains_frequency = interpolation
+        .at(&network.s_parameter_points(1, 1), frequency_in_hz)
+        .is_some();
+    let s_parameters = path_s_parameters(&network, frequency_in_hz, interpolation, &ports);
+
+    match s_parameters {
+        Some(s_parameters) => {
+            tracing::debug!(
+                frequency_hz = frequency_in_hz,
+                %interpolation,
+                input_port = ports.input_port,
+                output_port = ports.output_port,
+                s21_db = s_parameters.s21.abs_db(),
+                s21_deg = s_parameters.s21.arg_deg(),
+                "Touchstone S21"
+            );
+            Ok(TouchstoneValid {
+                contains_frequency,
+                gain: Some(s_parameters.s21.abs_db()),
+                phase_deg: Some(s_parameters.s21.arg_deg()),
+                group_delay_s: path_group_delay_s(&network, frequency_in_hz, interpolation, &ports),
+                s_parameters: Some(s_parameters),
+                has_noise_data: !noise.is_empty(),
+                noise_parameters,
+                port_count,
+            })
+        }
+        None => {
+            tracing::debug!(
+                frequency_hz = frequency_in_hz,
+                "Frequency outside the touchstone file's range"
+            );
+            Ok(TouchstoneValid {
+                contains_frequency,
+                gain: None,
+                phase_deg: None,
+                group_delay_s: None,
+                s_parameters: None,
+                has_noise_data: !noise.is_empty(),
+                noise_parameters: None,
+                port_count,
+            })
+        }
+    }
+}
 
-    let frequency_vector = s2p.f.clone();
-    let contains_frequency = frequency_vector.contains(&frequency_in_hz);
+/// The full S-matrix of a network at one frequency, each entry interpolated on its own.
+///
+/// Returns `None` outside the file's range.
+pub fn s_matrix(
+    network: &TouchstoneNetwork,
+    frequency_in_hz: f64,
+    interpolation: Interpolation,
+) -> Option<Vec<Vec<Complex>>> {
+    (1..=network.port_count)
+        .map(|j| {
+            (1..=network.port_count)
+                .map(|k| {
+                    interpolation
+                        .at(&network.s_parameter_points(j, k), frequency_in_hz)
+                        .map(|point| Complex::from_db_deg(point.magnitude_db, point.phase_deg))
+                })
+                .collect()
+        })
+        .collect()
+}
 
-    if !contains_frequency {
-        tracing::debug!(
-            frequency_hz = frequency_in_hz,
-            "Frequency not found in touchstone file"
-        );
-        return TouchstoneValid {
-            contains_frequency: false,
-            gain: None,
-        };
-    }
+/// The four S-parameters of a 2-port network at one frequency, each interpolated on its own.
+///
+/// Returns `None` for networks with fewer than 2 ports or outside the file's range.
+pub fn two_port_s_parameters(
+    network: &TouchstoneNetwork,
+    frequency_in_hz: f64,
+    interpolation: Interpolation,
+) -> Option<SParameters> {
+    path_s_parameters(
+        network,
+        frequency_in_hz,
+        interpolation,
+        &TouchstonePorts::default(),
+    )
+}
 
-    let gain_vector = s2p.s_db(2, 1); // uses 1-based indexing
+/// The two-port through `ports` of a network at one frequency, see
+/// [`SParameters::from_multiport`].
+///
+/// Returns `None` if the ports do not fit the network or outside the file's range.
+pub fn path_s_parameters(
+    network: &TouchstoneNetwork,
+    frequency_in_hz: f64,
+    interpolation: Interpolation,
+    ports: &TouchstonePorts,
+) -> Option<SParameters> {
+    let matrix = s_matrix(network, frequency_in_hz, interpolation)?;
+    SParameters::from_multiport(
+        &matrix,
+        ports.input_port,
+        ports.output_port,
+        ports.termination_reflection(network.z0),
+    )
+}
 
-    let gain = gain_vector
+/// Group delay in seconds of the path through `ports`, `-dφ/dω` of its S21 between the file
+/// frequencies either side of `frequency_in_hz`.
+///
+/// At a file frequency the neighbours on both sides are used, or the one neighbour at the
+/// edges of the file. Phase steps between adjacent file points are unwrapped, so the points
+/// must be closer than half a turn of phase apart. Returns `None` outside the file's range
+/// or for a single-frequency file.
+#[doc(alias = "delay")]
+pub fn path_group_delay_s(
+    network: &TouchstoneNetwork,
+    frequency_in_hz: f64,
+    interpolation: Interpolation,
+    ports: &TouchstonePorts,
+) -> Option<f64> {
+    let frequencies: Vec<f64> = network.s_parameter_points(1, 1)
         .iter()
-        .find(|frequency_db| frequency_db.frequency == frequency_in_hz)
-        .unwrap()
-        .s_db
-        .decibel();
-
-    TouchstoneValid {
-        contains_frequency: true,
-        gain: Some(gain),
+        .map(|point| point.frequency_hz)
+        .collect();
+    let (first, last) = (*frequencies.first()?, *frequencies.last()?);
+    let tolerance_hz = 1e-9 * last.abs();
+    if frequency_in_hz < first - tolerance_hz || frequency_in_hz > last + tolerance_hz {
+        return None;
     }
+    let below = frequencies
+        .iter()
+        .rposition(|&f| f < frequency_in_hz - tolerance_hz)
+        .unwrap_or(0);
+    let above = frequencies
+        .iter()
+        .position(|&f| f > frequency_in_hz + tolerance_hz)
+        .unwrap_or(frequencies.len() - 1);
+    if below == above {
+        return None;
+    }
+    let phases_deg = frequencies[below..=above]
+        .iter()
+        .map(|&f| path_s_parameters(network, f, interpolation, ports).map(|s| s.s21.arg_deg()))
+        .collect::<Option<Vec<f64>>>()?;
+    let phase_change_deg: f64 = phases_deg
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0] + 180.0).rem_euclid(360.0) - 180.0)
+        .sum();
+    Some(-phase_change_deg / (360.0 * (frequencies[above] - frequencies[below])))
 }
 
 fn calculate_gainlineup(input: Input, blocks: Vec<Block>) -> Vec<SignalNode> {
@@ -283,10 +1229,33 @@ impl Command {
                     frequency_hz: config.frequency_hz,
                     bandwidth_hz: config.bandwidth_hz.unwrap_or(100.0), // CW in real life
                     noise_temperature_k: Some(config.noise_temperature_k.unwrap_or(290.0)), // 290K is standard
+                    phase_noise: config.phase_noise.clone(),
+                    papr_db: config.papr_db,
+                };
+
+                let findings = validate_lineup(&input, &config.blocks);
+                print_diagnostics(&findings);
+                let error_count = findings.iter().filter(|d| d.is_error()).count();
+                if error_count > 0 {
+                    return Err(
+                        format!("lineup failed validation with {} error(s)", error_count).into(),
+                    );
+                }
+
+                // with mismatch on, each block's gain includes the mismatch at its input
+                let blocks = if config.mismatch {
+                    print_mismatch(&mismatch_interfaces(&config.blocks));
+                    mismatch_adjusted_blocks(&config.blocks)
+                } else {
+                    config.blocks.clone()
                 };
-                let cascade = calculate_gainlineup(input.clone(), config.blocks.clone());
+
+                let cascade = calculate_gainlineup(input.clone(), blocks.clone());
                 
-                print_cascade(cascade.clone(), config.blocks.clone());
+                print_cascade(cascade.clone(), blocks.clone());
+                if let Some(output) = cascade.last() {
+                    print_phase_noise(output);
+                }
 
                 let file_path = full_path_to_config.display().to_string();
 
@@ -327,7 +1296,7 @@ impl Command {
                 match crate::plot::generate_html_table(
                     &input,
                     &cascade,
-                    &config.blocks,
+                    &blocks,
                     output_html_path_str,
                 ) {
                     Ok(_) => {
@@ -340,7 +1309,7 @@ impl Command {
             }
             Err(e) => {
                 eprintln!("Error running calculation or plotting: {}", e);
-                return Err(e);
+                return Err(e.into());
             }
         }
 
@@ -358,6 +1327,59 @@ pub fn print_error(error: &str) {
     println!("{}Problem parsing arguments: {error}{}", RED, RESET);
 }
 
+pub fn print_diagnostics(findings: &[Diagnostic]) {
+    const RED: &str = "\x1b[31m";
+    const YELLOW: &str = "\x1b[33m";
+    const RESET: &str = "\x1b[0m";
+    if findings.is_empty() {
+        return;
+    }
+    println!("Validation:");
+    for finding in findings {
+        let color = if finding.is_error() { RED } else { YELLOW };
+        println!("  {}{}{}", color, finding, RESET);
+    }
+    println!();
+}
+
+pub fn print_mismatch(interfaces: &[MismatchInterface]) {
+    if interfaces.is_empty() {
+        return;
+    }
+    println!("Mismatch:");
+    for interface in interfaces {
+        println!(
+            "  {} -> {}:\t{:>8.2} dB (uncertainty {:>6.2} to {:>6.2} dB)",
+            interface.from,
+            interface.to,
+            interface.mismatch_db,
+            interface.uncertainty_min_db,
+            interface.uncertainty_max_db
+        );
+    }
+    println!();
+}
+
+pub fn print_phase_noise(node: &SignalNode) {
+    let Some(phase_noise) = &node.phase_noise else {
+        return;
+    };
+    let Some((start_hz, stop_hz)) = phase_noise.offset_range_hz() else {
+        return;
+    };
+    println!(
+        "Phase Noise at {} ({} Hz to {} Hz offset):",
+        node.name, start_hz, stop_hz
+    );
+    if let Some(phase_error_deg) = node.rms_phase_error_deg(start_hz, stop_hz) {
+        println!("  RMS Phase Error:\t{:>8.3} deg", phase_error_deg);
+    }
+    if let Some(jitter_s) = node.rms_jitter_s(start_hz, stop_hz) {
+        println!("  RMS Jitter:\t\t{:>8.3} ps", jitter_s * 1.0e12);
+    }
+    println!();
+}
+
 pub fn print_help() {
     // ANSI color codes
     const BOLD: &str = "\x1b[1m";
@@ -416,6 +1438,17 @@ pub fn print_cascade(cascade: Vec<SignalNode>, blocks: Vec<Block>) {
             // the formatting `{:>8.2}` aligns positive and negative numbers on the decimal,
             // with two digits after the decimal (hundredths place)
             println!("Input Power\t\t{:>8.2} dBm", input_power);
+            println!(
+                "Output Frequency:\t{:>8.2} MHz",
+                node.signal_frequency_hz / 1.0e6
+            );
+            if let Some(image_frequency_hz) = node.image_frequency_hz {
+                println!("Image Frequency:\t{:>8.2} MHz", image_frequency_hz / 1.0e6);
+            }
+            println!(
+                "Output Bandwidth:\t{:>8.2} kHz",
+                node.signal_bandwidth_hz / 1.0e3
+            );
             println!("Block Gain:\t\t{:>8.2} dB", block_gain);
             println!("Block NF:\t\t{:>8.2} dB", blocks[i - 1].noise_figure_db);
             println!("Cumulative Gain:\t{:>8.2} dB", node.cumulative_gain_db);
@@ -424,6 +1457,36 @@ pub fn print_cascade(cascade: Vec<SignalNode>, blocks: Vec<Block>) {
                 node.cumulative_noise_figure_db
             );
             println!("Output Power\t\t{:>8.2} dBm", node.signal_power_dbm);
+            if let Some(output_p1db) = node.output_p1db_dbm {
+                println!("Cumulative Output P1dB:\t{:>8.2} dBm", output_p1db);
+            }
+            if node.cumulative_phase_deg != 0.0 {
+                println!("Cumulative Phase:\t{:>8.2} deg", node.cumulative_phase_deg);
+            }
+            if node.cumulative_group_delay_s != 0.0 {
+                println!(
+                    "Cumulative Group Delay:\t{:>8.3} ns",
+                    node.cumulative_group_delay_s * 1.0e9
+                );
+            }
+        }
+        for harmonic in &node.harmonics {
+            println!(
+                "H{} at {:>10.2} MHz:\t{:>8.2} dBm ({:>7.2} dBc)",
+                harmonic.order,
+                harmonic.frequency_hz / 1.0e6,
+                harmonic.power_dbm,
+                harmonic.power_dbm - node.signal_power_dbm
+            );
+        }
+        if !node.noise_contributions.is_empty() {
+            println!("Noise Contributions (input-referred):");
+            for contribution in &node.noise_contributions {
+                println!(
+                    "  {:<24}{:>10.2} K {:>6.1} %",
+                    contribution.name, contribution.noise_temperature_k, contribution.percent
+                );
+            }
         }
     }
     println!();
@@ -442,6 +1505,12 @@ pub fn print_cascade(cascade: Vec<SignalNode>, blocks: Vec<Block>) {
         "NF:\t{:>8.2} dB",
         cascade.last().unwrap().cumulative_noise_figure_db
     );
+    if let Some(output_p1db) = cascade.last().unwrap().output_p1db_dbm {
+        println!("OP1dB:\t{:>8.2} dBm", output_p1db);
+    }
+    if let Some(stage) = &cascade.last().unwrap().p1db_limiting_stage {
+        println!("Compression limited by: {}", stage);
+    }
 }
 
 #[cfg(test)]
@@ -450,6 +1519,7 @@ mod tests {
 
     use super::*;
     use std::path::PathBuf;
+    use crate::{Combiner, Splitter};
 
     fn setup_test_dir(name: &str) -> PathBuf {
         let mut path = std::env::temp_dir();
@@ -525,18 +1595,117 @@ mod tests {
         let TouchstoneValid {
             contains_frequency,
             gain,
+            phase_deg,
+            ..
         } = touchstone_file_path_and_frequency_to_struct(
             touchstone_file_path.to_string(),
             frequency_in_hz,
-        );
+        )
+        .unwrap();
 
         let gain = gain.unwrap();
         assert!(contains_frequency);
+        assert!(phase_deg.is_some());
 
         let gain_rounded_to_3_decimal_places = (gain * 1e3).round() / 1e3;
         assert_eq!(gain_rounded_to_3_decimal_places, -3.932);
     }
 
+    #[test]
+    fn test_touchstone_interpolates_between_points() {
+        let touchstone_file_path = "files/touchstone_options/ntwk3.s2p";
+        let gain_at = |frequency_in_hz: f64, interpolation: Interpolation| {
+            touchstone_path_to_struct(
+                touchstone_file_path.to_string(),
+                frequency_in_hz,
+                interpolation,
+                TouchstonePorts::default(),
+            )
+            .unwrap()
+            .gain
+            .unwrap()
+        };
+        let (low, high) = (
+            gain_at(6.0e9, Interpolation::Linear),
+            gain_at(6.1e9, Interpolation::Linear),
+        );
+        for interpolation in [
+            Interpolation::Linear,
+            Interpolation::DbLinear,
+            Interpolation::Cubic,
+        ] {
+            let mid = gain_at(6.05e9, interpolation);
+            assert!(mid < low && mid > high, "{}: {}", interpolation, mid);
+        }
+        let db_linear = gain_at(6.05e9, Interpolation::DbLinear);
+        assert!((db_linear - (low + high) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_touchstone_group_delay() {
+        // a 1 ns delay line, with the phase wrapped into (-180, 180] as files store it
+        let test_dir = setup_test_dir("test_touchstone_group_delay");
+        let path = test_dir.join("delay_line.s2p");
+        let mut content = String::from("# GHz S MA R 50\n");
+        for i in 0..=10 {
+            let frequency_ghz = 1.0 + 0.1 * f64::from(i);
+            let phase_deg = (-360.0 * frequency_ghz + 180.0).rem_euclid(360.0) - 180.0;
+            content.push_str(&format!(
+                "{} 0 0 1 {} 1 {} 0 0\n",
+                frequency_ghz, phase_deg, phase_deg
+            ));
+        }
+        fs::write(&path, content).unwrap();
+        let network = load_touchstone(path.to_str().unwrap()).unwrap();
+        let delay_at = |frequency_in_hz: f64| {
+            path_group_delay_s(
+                &network,
+                frequency_in_hz,
+                Interpolation::Linear,
+                &TouchstonePorts::default(),
+            )
+        };
+
+        for frequency_in_hz in [1.0e9, 1.5e9, 1.55e9, 2.0e9] {
+            let delay = delay_at(frequency_in_hz).unwrap();
+            assert!(
+                (delay - 1.0e-9).abs() < 1e-15,
+                "{}: {}",
+                frequency_in_hz,
+                delay
+            );
+        }
+        assert_eq!(delay_at(2.5e9), None);
+
+        let touchstone = touchstone_file_path_and_frequency_to_struct(
+            path.to_string_lossy().to_string(),
+            1.5e9,
+        )
+        .unwrap();
+        assert!((touchstone.group_delay_s.unwrap() - 1.0e-9).abs() < 1e-15);
+        assert!((touchstone.phase_deg.unwrap() - (-180.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_phase_and_group_delay_config() {
+        let test_dir = setup_test_dir("test_phase_and_group_delay_config");
+        let toml_path = test_dir.join("phase.toml");
+        fs::write(
+            &toml_path,
+            "pin = -30.0\nf = 2.0e9\n[[blocks]]\ntype = \"explicit\"\nname = \"Filter\"\ngain = -1.0\nnf = 1.0\nphase = 40.0\ngroup_delay = 5.0e-9\n[[blocks]]\ntype = \"explicit\"\nname = \"Cable\"\ngain = -0.5\nnf = 0.5\nphase_deg = -100.0\ngroup_delay_s = 2.0e-9\n",
+        )
+        .unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.blocks[0].phase_deg, Some(40.0));
+        assert_eq!(config.blocks[1].group_delay_s, Some(2.0e-9));
+
+        let input = Input::new(config.frequency_hz, 1.0e6, config.input_power_dbm, None);
+        let cascade = calculate_gainlineup(input, config.blocks);
+        let output = cascade.last().unwrap();
+        assert!((output.cumulative_phase_deg - (-60.0)).abs() < 1e-9);
+        assert!((output.cumulative_group_delay_s - 7.0e-9).abs() < 1e-18);
+    }
+
     #[test]
     fn test_touchstone_file_path_and_frequency_to_gain_not_found() {
         let touchstone_file_path = "files/touchstone_options/ntwk3.s2p";
@@ -544,12 +1713,16 @@ mod tests {
         let TouchstoneValid {
             contains_frequency,
             gain,
+            phase_deg,
+            ..
         } = touchstone_file_path_and_frequency_to_struct(
             touchstone_file_path.to_string(),
             frequency_in_hz,
-        );
+        )
+        .unwrap();
         assert!(!contains_frequency);
         assert_eq!(gain, None);
+        assert_eq!(phase_deg, None);
     }
 
     #[test]
@@ -610,11 +1783,655 @@ mod tests {
         } = &config.blocks[0]
         {
             assert_eq!(name, "LNA");
-            assert_eq!(*gain_db, 20.0);
-            assert_eq!(*noise_figure_db, 2.0);
+            assert_eq!(*gain_db, Some(20.0));
+            assert_eq!(*noise_figure_db, Some(2.0));
             assert_eq!(*output_p1db_dbm, Some(10.0));
         } else {
             panic!("Expected Explicit block");
         }
     }
+
+    #[test]
+    fn test_harmonics_config() {
+        let config = load_config("files/harmonics/config.toml").unwrap();
+        assert_eq!(config.blocks[0].output_h2_intercept_dbm, Some(45.0));
+        // -25 dBc and -35 dBc at +28 dBm out
+        assert_eq!(config.blocks[1].output_h2_intercept_dbm, Some(53.0));
+        assert_eq!(config.blocks[1].output_h3_intercept_dbm, Some(45.5));
+
+        let input = Input::new(
+            config.frequency_hz,
+            config.bandwidth_hz.unwrap(),
+            config.input_power_dbm,
+            None,
+        );
+        let cascade = calculate_gainlineup(input, config.blocks);
+        let pa = &cascade[1];
+        let filtered = &cascade[2];
+        assert_eq!(filtered.harmonics.len(), 2);
+        assert_eq!(filtered.harmonics[0].frequency_hz, 4.8e9);
+        // the filter is 50.1 dB down at 4.8 GHz, plus its insertion loss
+        let h2_rejection = pa.harmonics[0].power_dbm - filtered.harmonics[0].power_dbm;
+        assert!((h2_rejection - 50.625).abs() < 1e-9);
+        assert!(filtered.harmonic_dbc(3).unwrap() < -90.0);
+    }
+
+    #[test]
+    fn test_splitter_and_combiner_config() {
+        let test_dir = setup_test_dir("test_splitter_and_combiner_config");
+        let toml_path = test_dir.join("config.toml");
+        fs::write(
+            &toml_path,
+            "pin = -30.0\nf = 2.0e9\n[[blocks]]\ntype = \"splitter\"\nname = \"Splitter\"\nways = 4\nexcess_loss_db = 0.5\n[[blocks]]\ntype = \"combiner\"\nname = \"Combiner\"\nways = 2\n",
+        )
+        .unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.blocks[0].kind, BlockKind::Splitter(Splitter::new(4)));
+        assert!((config.blocks[0].gain_db + 6.5206).abs() < 1e-4);
+        assert_eq!(config.blocks[1].kind, BlockKind::Combiner(Combiner::new(2)));
+        assert!((config.blocks[1].noise_figure_db - 3.0103).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_adc_config() {
+        let test_dir = setup_test_dir("test_adc_config");
+        let toml_path = test_dir.join("config.toml");
+        let config_with = |adc: &str| {
+            fs::write(
+                &toml_path,
+                format!(
+                    "pin = -20.0\nf = 100.0e6\npapr = 10.0\n[[blocks]]\ntype = \"adc\"\nname = \"ADC\"\nfull_scale = 4.0\nfs = 500.0e6\n{}\n",
+                    adc
+                ),
+            )
+            .unwrap();
+            load_config(toml_path.to_str().unwrap())
+        };
+        let config = config_with(
+            "enob = 11.5\nsfdr = 80.0\naperture_jitter = 100.0e-15\nnoise_bandwidth = 1.0e9",
+        )
+        .unwrap();
+        assert_eq!(config.papr_db, 10.0);
+        let expected = Adc::from_enob(4.0, 500.0e6, 11.5)
+            .with_sfdr_db(80.0)
+            .with_aperture_jitter_s(100.0e-15)
+            .with_noise_bandwidth_hz(1.0e9);
+        assert_eq!(config.blocks[0].kind, BlockKind::Adc(expected));
+
+        let config = config_with("snr_db = 70.0").unwrap();
+        assert_eq!(config.blocks[0].kind, BlockKind::Adc(Adc::new(4.0, 500.0e6, 70.0)));
+
+        match config_with("enob = 11.5\nsnr_db = 70.0") {
+            Err(GainlineupError::InvalidParameter { parameter, .. }) => assert_eq!(parameter, "SNR"),
+            other => panic!("expected an invalid parameter error, got {:?}", other),
+        }
+        assert!(config_with("").is_err());
+    }
+
+    #[test]
+    fn test_digital_config() {
+        let test_dir = setup_test_dir("test_digital_config");
+        let toml_path = test_dir.join("config.toml");
+        let config_with = |digital: &str| {
+            fs::write(
+                &toml_path,
+                format!(
+                    "pin = -20.0\nf = 30.0e6\n[[blocks]]\ntype = \"digital\"\nname = \"DDC\"\n{}\n",
+                    digital
+                ),
+            )
+            .unwrap();
+            load_config(toml_path.to_str().unwrap())
+        };
+        let config = config_with(
+            "gain = 6.0\nf_nco = 30.0e6\ndecimation = 8\nbits = 16\nbw = 2.0e6\nrejection = [[4.0e6, 80.0]]",
+        )
+        .unwrap();
+        let expected = DigitalStage::nco(30.0e6)
+            .with_filter(Filter::new(0.0, 2.0e6).with_rejection(vec![(4.0e6, 80.0)]))
+            .with_decimation(8)
+            .with_output_bits(16);
+        assert_eq!(config.blocks[0].kind, BlockKind::Digital(expected));
+        assert_eq!(config.blocks[0].gain_db, 6.0);
+
+        let config = config_with("bits = 12").unwrap();
+        assert_eq!(
+            config.blocks[0].kind,
+            BlockKind::Digital(DigitalStage::truncation(12))
+        );
+
+        match config_with("rejection = [[4.0e6, 80.0]]") {
+            Err(GainlineupError::InvalidParameter { parameter, .. }) => {
+                assert_eq!(parameter, "bandwidth_hz")
+            }
+            other => panic!("expected an invalid parameter error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_phase_noise_config() {
+        let config = load_config("files/phase_noise/config.toml").unwrap();
+        let source = config.phase_noise.as_ref().unwrap();
+        assert_eq!(source.level_at(1.0e3), Some(-110.0));
+        match &config.blocks[1].kind {
+            BlockKind::Mixer(mixer) => {
+                // -100 dBc/Hz synthesizer times 4
+                let lo = mixer.lo_phase_noise.as_ref().unwrap();
+                assert!((lo.level_at(1.0e3).unwrap() - (-87.96)).abs() < 0.01);
+            }
+            other => panic!("expected a mixer, got {:?}", other),
+        }
+
+        let test_dir = setup_test_dir("test_phase_noise_config");
+        let toml_path = test_dir.join("config.toml");
+        let config_with = |mixer: &str| {
+            fs::write(
+                &toml_path,
+                format!(
+                    "pin = -70.0\nf = 2.4e9\n[[blocks]]\ntype = \"mixer\"\nname = \"Mixer\"\ngain = -7.0\nnf = 7.0\nf_lo = 2.3e9\n{}\n",
+                    mixer
+                ),
+            )
+            .unwrap();
+            load_config(toml_path.to_str().unwrap())
+        };
+        let config =
+            config_with("lo_phase_noise = [[1.0e3, -90.0], [1.0e6, -130.0]]\nlo_divider = 2.0")
+                .unwrap();
+        assert!(config.phase_noise.is_none());
+        let lo = config.blocks[0].lo_phase_noise().unwrap();
+        assert!((lo.level_at(1.0e6).unwrap() - (-136.02)).abs() < 0.01);
+
+        let err = config_with("lo_phase_noise = [[0.0, -90.0]]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid lo_phase_noise for block 'Mixer': offset 0 Hz (must be positive)"
+        );
+        let err =
+            config_with("lo_phase_noise = [[1.0e3, -90.0]]\nlo_multiplier = 0.0").unwrap_err();
+        assert!(err.to_string().contains("lo_multiplier"), "{}", err);
+        let err = config_with("lo_phase_noise = []").unwrap_err();
+        assert!(err.to_string().contains("empty"), "{}", err);
+    }
+
+    #[test]
+    fn test_load_superhet_config_tracks_if() {
+        let config = load_config("files/superhet/superhet.toml").unwrap();
+        let input = Input::new(
+            config.frequency_hz,
+            config.bandwidth_hz.unwrap(),
+            config.input_power_dbm,
+            None,
+        );
+        let cascade = calculate_gainlineup(input, config.blocks.clone());
+
+        let frequencies: Vec<f64> = cascade
+            .iter()
+            .map(|node| node.signal_frequency_hz)
+            .collect();
+        assert_eq!(
+            frequencies,
+            vec![2.45e9, 2.45e9, 300.0e6, 300.0e6, 10.7e6, 10.7e6, 10.7e6]
+        );
+        assert_eq!(cascade[2].image_frequency_hz, Some(1.85e9));
+        assert_eq!(cascade[4].image_frequency_hz, Some(321.4e6));
+        assert_eq!(cascade[6].image_frequency_hz, None);
+
+        // the IF filter sets the final noise bandwidth
+        assert_eq!(cascade[4].signal_bandwidth_hz, 2.0e6);
+        assert_eq!(cascade[6].signal_bandwidth_hz, 200.0e3);
+
+        // second mixer noise figure is given as DSB in the config
+        assert!((config.blocks[4].noise_figure_db - 6.51).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_compression_model_parsing() {
+        let toml_content = r#"
+            pin = -30.0
+            f = 1.0e9
+            [[blocks]]
+            type = "explicit"
+            name = "PA"
+            gain = 30.0
+            nf = 5.0
+            op1db = 20.0
+            compression_model = { type = "rapp", smoothness = 3.0, psat = 23.0 }
+
+            [[blocks]]
+            type = "explicit"
+            name = "Driver"
+            gain = 20.0
+            nf = 4.0
+            op1db = 10.0
+            compression = { type = "saleh" }
+
+            [[blocks]]
+            type = "explicit"
+            name = "LNA"
+            gain = 20.0
+            nf = 1.0
+
+            [[blocks]]
+            type = "explicit"
+            name = "Limiter"
+            gain = 10.0
+            nf = 1.0
+            op1db = 0.0
+            compression = { type = "hard_clip" }
+        "#;
+
+        let test_dir = setup_test_dir("test_compression_model_parsing");
+        let toml_path = test_dir.join("compression.toml");
+        fs::write(&toml_path, toml_content).unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            config.blocks[0].compression_model,
+            CompressionModel::Rapp {
+                smoothness: 3.0,
+                saturation_power_dbm: Some(23.0)
+            }
+        );
+        assert_eq!(config.blocks[1].compression_model, CompressionModel::Saleh);
+        assert_eq!(
+            config.blocks[2].compression_model,
+            CompressionModel::default()
+        );
+        assert_eq!(
+            config.blocks[3].compression_model,
+            CompressionModel::HardClip
+        );
+    }
+
+    #[test]
+    fn test_invalid_rapp_parameters_are_errors() {
+        let test_dir = setup_test_dir("test_invalid_rapp_parameters_are_errors");
+        let toml_path = test_dir.join("rapp.toml");
+        for compression in [
+            "{ type = \"rapp\", smoothness = 0.0 }",
+            "{ type = \"rapp\", psat = 5.0 }",
+        ] {
+            let toml_content = format!(
+                "pin = -30.0\nf = 1.0e9\n[[blocks]]\ntype = \"explicit\"\nname = \"PA\"\ngain = 30.0\nnf = 5.0\nop1db = 10.0\ncompression = {}\n",
+                compression
+            );
+            fs::write(&toml_path, toml_content).unwrap();
+            match load_config(toml_path.to_str().unwrap()) {
+                Err(GainlineupError::InvalidParameter { parameter, .. }) => {
+                    assert_eq!(parameter, "compression_model")
+                }
+                other => panic!("expected an invalid parameter error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_alternate_specs_parsing() {
+        let toml_content = r#"
+            pin = -60.0
+            f = 1.0e9
+            [[blocks]]
+            type = "explicit"
+            name = "LNA"
+            gain_linear = 100.0
+            te = 75.0
+            iip3 = -5.0
+            ip1db = -15.0
+
+            [[blocks]]
+            type = "mixer"
+            name = "Mixer"
+            conversion_gain_db = -7.0
+            nf = 7.0
+            lo_frequency_hz = 0.9e9
+            iip3 = 15.0
+            iip2 = 50.0
+        "#;
+
+        let test_dir = setup_test_dir("test_alternate_specs_parsing");
+        let toml_path = test_dir.join("alternate.toml");
+        fs::write(&toml_path, toml_content).unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+
+        let lna = &config.blocks[0];
+        assert!((lna.gain_db - 20.0).abs() < 1e-9);
+        assert!((lna.noise_figure_db - 1.0).abs() < 0.01);
+        assert!((lna.output_ip3_dbm.unwrap() - 15.0).abs() < 1e-9);
+        assert!((lna.output_p1db_dbm.unwrap() - 4.0).abs() < 1e-9);
+        assert_eq!(config.blocks[1].output_ip3_dbm, Some(8.0));
+        assert_eq!(config.blocks[1].output_ip2_dbm, Some(43.0));
+    }
+
+    #[test]
+    fn test_conflicting_specs_error() {
+        let toml_content = r#"
+            pin = -60.0
+            f = 1.0e9
+            [[blocks]]
+            type = "explicit"
+            name = "LNA"
+            gain_db = 20.0
+            gain_linear = 10.0
+            nf = 1.0
+        "#;
+
+        let test_dir = setup_test_dir("test_conflicting_specs_error");
+        let toml_path = test_dir.join("conflict.toml");
+        fs::write(&toml_path, toml_content).unwrap();
+        let err = load_config(toml_path.to_str().unwrap()).unwrap_err();
+        assert!(
+            err.to_string().contains("invalid gain for block 'LNA'"),
+            "{}",
+            err
+        );
+
+        let missing = toml_content.replace("gain_db = 20.0\n            gain_linear = 10.0\n", "");
+        fs::write(&toml_path, missing).unwrap();
+        let err = load_config(toml_path.to_str().unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid gain for block 'LNA': missing, set gain_db or gain_linear"
+        );
+    }
+
+    #[test]
+    fn test_toml_parse_error_has_location() {
+        let test_dir = setup_test_dir("test_toml_parse_error_has_location");
+        let toml_path = test_dir.join("broken.toml");
+        fs::write(&toml_path, "pin = -30.0\nf = = 1.0e9\n").unwrap();
+        let err = load_config(toml_path.to_str().unwrap()).unwrap_err();
+        match err {
+            GainlineupError::TomlParse { line, .. } => assert_eq!(line, Some(2)),
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_config_is_io_error() {
+        let err = load_config("files/does_not_exist.toml").unwrap_err();
+        assert!(matches!(err, GainlineupError::Io { .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_include_error_reports_chain() {
+        let test_dir = setup_test_dir("test_include_error_reports_chain");
+        fs::write(
+            test_dir.join("top.toml"),
+            "pin = -30.0\nf = 1.0e9\n[[blocks]]\ntype = \"include\"\npath = \"middle.toml\"\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.join("middle.toml"),
+            "[[blocks]]\ntype = \"include\"\npath = \"bottom.toml\"\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.join("bottom.toml"),
+            "[[blocks]]\ntype = \"explicit\"\nname = \"Amp\"\nnf = 3.0\n",
+        )
+        .unwrap();
+
+        let err = load_config(test_dir.join("top.toml").to_str().unwrap()).unwrap_err();
+        match err {
+            GainlineupError::Include { chain, source } => {
+                assert_eq!(
+                    chain,
+                    vec![test_dir.join("middle.toml"), test_dir.join("bottom.toml")]
+                );
+                assert!(matches!(
+                    *source,
+                    GainlineupError::InvalidParameter { ref block, .. } if block == "Amp"
+                ));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_touchstone_is_error() {
+        let test_dir = setup_test_dir("test_malformed_touchstone_is_error");
+        let s2p_path = test_dir.join("bad.s2p");
+        fs::write(
+            &s2p_path,
+            "# GHz S MA R 50\n# Hz S RI R 50\n1.0 0 0 0 0 0 0 0 0\n",
+        )
+        .unwrap();
+        let err = load_touchstone(s2p_path.to_str().unwrap()).unwrap_err();
+        assert!(
+            matches!(err, GainlineupError::MalformedTouchstone { .. }),
+            "{}",
+            err
+        );
+        assert!(err.to_string().contains("more than one option line"), "{}", err);
+
+        // a truncated entry is reported through the config that uses the file
+        fs::write(&s2p_path, "# GHz S MA R 50
+1.0 0 0 1 0 0 0
+").unwrap();
+        let toml_path = test_dir.join("config.toml");
+        fs::write(
+            &toml_path,
+            "pin = -30.0\nf = 1.0e9\n[[blocks]]\ntype = \"touchstone\"\nfile_path = \"bad.s2p\"\nname = \"Amp\"\n",
+        )
+        .unwrap();
+        let err = load_config(toml_path.to_str().unwrap()).unwrap_err();
+        match &err {
+            GainlineupError::MalformedTouchstone { message, .. } => {
+                assert_eq!(message, "last data entry has 7 values, expected 9");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+
+        let txt_path = test_dir.join("bad.txt");
+        fs::write(&txt_path, "# GHz S MA R 50
+").unwrap();
+        let err = load_touchstone(txt_path.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains(".sNp"), "{}", err);
+
+        let err = load_touchstone("files/does_not_exist.s2p").unwrap_err();
+        assert!(matches!(err, GainlineupError::Io { .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_run_rejects_invalid_lineup() {
+        let test_dir = setup_test_dir("test_run_rejects_invalid_lineup");
+        let toml_path = test_dir.join("zero_bandwidth.toml");
+        fs::write(
+            &toml_path,
+            "pin = -30.0\nf = 1.0e9\nbw = 0.0\n[[blocks]]\ntype = \"explicit\"\nname = \"Amp\"\ngain = 20.0\nnf = 3.0\n",
+        )
+        .unwrap();
+        let args = vec![
+            String::from("program_name"),
+            toml_path.to_str().unwrap().to_string(),
+        ];
+        let err = Command::run(&args).unwrap_err();
+        assert_eq!(err.to_string(), "lineup failed validation with 1 error(s)");
+    }
+
+    #[test]
+    fn test_touchstone_interpolation_parsing() {
+        let test_dir = setup_test_dir("test_touchstone_interpolation_parsing");
+        fs::copy(
+            "files/touchstone_options/ntwk3.s2p",
+            test_dir.join("ntwk3.s2p"),
+        )
+        .unwrap();
+        let toml_path = test_dir.join("interpolated.toml");
+        fs::write(
+            &toml_path,
+            "pin = -30.0\nf = 6.05e9\n[[blocks]]\ntype = \"touchstone\"\nfile_path = \"ntwk3.s2p\"\nname = \"Cable\"\ninterpolation = \"db_linear\"\n",
+        )
+        .unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+        let gain_db = config.blocks[0].gain_db;
+        assert!(gain_db < -3.93 && gain_db > -4.4, "{}", gain_db);
+    }
+
+    #[test]
+    fn test_touchstone_linearity_specs() {
+        let config = load_config("files/touchstone_options/config.toml").unwrap();
+        let block = &config.blocks[0];
+        assert_eq!(block.output_p1db_dbm, Some(20.0));
+        assert_eq!(block.output_ip3_dbm, Some(30.0));
+        // no P1dB means linear, as for explicit blocks
+        assert_eq!(config.blocks[2].output_p1db_dbm, None);
+        assert_eq!(config.blocks[2].output_ip3_dbm, None);
+
+        let test_dir = setup_test_dir("test_touchstone_linearity_specs");
+        fs::copy(
+            "files/touchstone_options/ntwk3.s2p",
+            test_dir.join("ntwk3.s2p"),
+        )
+        .unwrap();
+        let toml_path = test_dir.join("input_referred.toml");
+        fs::write(
+            &toml_path,
+            "pin = -30.0\nf = 6.0e9\n[[blocks]]\ntype = \"touchstone\"\nfile_path = \"ntwk3.s2p\"\nname = \"PA\"\nip1db = 0.0\niip3 = 10.0\noip2 = 50.0\nam_pm = 3.0\n",
+        )
+        .unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+        let pa = &config.blocks[0];
+        // input-referred specs are referred to the output with the file's gain
+        assert!((pa.output_ip3_dbm.unwrap() - (10.0 + pa.gain_db)).abs() < 1e-9);
+        assert!(pa.output_p1db_dbm.is_some());
+        assert_eq!(pa.output_ip2_dbm, Some(50.0));
+        assert_eq!(pa.am_pm_coefficient_deg_per_db, Some(3.0));
+        assert!(pa.s_parameters.is_some());
+    }
+
+    #[test]
+    fn test_touchstone_noise_parameters() {
+        let config = load_config("files/touchstone_noise/config.toml").unwrap();
+        let lna = &config.blocks[0];
+        // S21 (5.62, 15 dB) and S12 (0.05) are not swapped
+        assert!((lna.gain_db - 14.99).abs() < 0.01, "{}", lna.gain_db);
+        assert!((lna.s_parameters.unwrap().s12.abs() - 0.05).abs() < 1e-9);
+
+        // 50 Ω source: Fmin 0.5 dB, |Γopt| 0.47 at 125°, rn 0.17
+        assert!(
+            (lna.noise_figure_db - 1.279).abs() < 1e-3,
+            "{}",
+            lna.noise_figure_db
+        );
+        // the filter's 1.8:1 output (Γ = 0.29 at 0°) is further from Γopt than 50 Ω
+        let lna_after_filter = &config.blocks[2];
+        assert!(lna_after_filter.noise_figure_db > lna.noise_figure_db + 0.5);
+    }
+
+    #[test]
+    fn test_touchstone_2_noise_parameters() {
+        let test_dir = setup_test_dir("test_touchstone_2_noise_parameters");
+        fs::write(
+            test_dir.join("lna.s2p"),
+            "[Version] 2.0\n# MHz S DB R 50\n[Number of Ports] 2\n[Two-Port Data Order] 12_21\n[Number of Frequencies] 3\n[Number of Noise Frequencies] 2\n[Network Data]\n1000 -10 0 -30 0 20 90 -12 0\n3000 -10 0 -30 0 18 45 -12 0\n5000 -10 0 -30 0 16 0 -12 0\n[Noise Data]\n2000 0.6 0.0 0 0.2\n4000 0.8 0.0 0 0.2\n[End]\n",
+        )
+        .unwrap();
+        let toml_path = test_dir.join("config.toml");
+        let block = "[[blocks]]\ntype = \"touchstone\"\nfile_path = \"lna.s2p\"\nname = \"LNA\"\n";
+        fs::write(&toml_path, format!("pin = -90.0\nf = 3.0e9\n{}", block)).unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+        // 12_21 order: S21 is the third pair
+        assert!((config.blocks[0].gain_db - 18.0).abs() < 1e-9);
+        // Γopt = 0 and a matched source give Fmin
+        assert!((config.blocks[0].noise_figure_db - 0.7).abs() < 1e-9);
+
+        // inside the network data but outside the noise data
+        fs::write(&toml_path, format!("pin = -90.0\nf = 4.5e9\n{}", block)).unwrap();
+        let err = load_config(toml_path.to_str().unwrap()).unwrap_err();
+        assert!(
+            err.to_string().contains("outside the noise data"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_touchstone_multiport_paths() {
+        let config = load_config("files/touchstone_multiport/config.toml").unwrap();
+        // S31 = 0.69 at 2 GHz, with port 2 matched
+        let splitter = &config.blocks[0];
+        assert!((splitter.gain_db - 20.0 * 0.69_f64.log10()).abs() < 1e-9);
+        assert_eq!(splitter.noise_figure_db, -splitter.gain_db);
+        let s = splitter.s_parameters.unwrap();
+        assert!((s.s11.abs() - 0.08).abs() < 1e-9);
+        assert!((s.s22.abs() - 0.09).abs() < 1e-9);
+
+        // reverse direction (S12) with port 3 open changes the path a little
+        let combiner = &config.blocks[2];
+        assert!((combiner.gain_db - 20.0 * 0.68_f64.log10()).abs() < 0.1);
+        assert!((combiner.gain_db - 20.0 * 0.68_f64.log10()).abs() > 1e-6);
+    }
+
+    #[test]
+    fn test_touchstone_invalid_ports() {
+        let test_dir = setup_test_dir("test_touchstone_invalid_ports");
+        fs::copy(
+            "files/touchstone_multiport/splitter.s3p",
+            test_dir.join("splitter.s3p"),
+        )
+        .unwrap();
+        let toml_path = test_dir.join("config.toml");
+        let config_with = |ports: &str| {
+            fs::write(
+                &toml_path,
+                format!(
+                    "pin = -30.0\nf = 2.0e9\n[[blocks]]\ntype = \"touchstone\"\nfile_path = \"splitter.s3p\"\nname = \"Splitter\"\n{}\n",
+                    ports
+                ),
+            )
+            .unwrap();
+            load_config(toml_path.to_str().unwrap())
+        };
+
+        let err = config_with("output_port = 4").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid output_port for block 'Splitter': port 4 is not one of the file's 3 port(s)"
+        );
+        let err = config_with("input_port = 2\noutput_port = 2").unwrap_err();
+        assert!(err.to_string().contains("also the input port"), "{}", err);
+        let err = config_with("termination_ohms = -50.0").unwrap_err();
+        assert!(err.to_string().contains("termination_ohms"), "{}", err);
+
+        // default ports on a 3-port file: 1 -> 2 with port 3 matched
+        let config = config_with("").unwrap();
+        assert!((config.blocks[0].gain_db - 20.0 * 0.68_f64.log10()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mismatch_config_parsing() {
+        let test_dir = setup_test_dir("test_mismatch_config_parsing");
+        fs::copy(
+            "files/touchstone_options/ntwk3.s2p",
+            test_dir.join("ntwk3.s2p"),
+        )
+        .unwrap();
+        let toml_path = test_dir.join("mismatch.toml");
+        fs::write(
+            &toml_path,
+            "pin = -30.0\nf = 6.0e9\nmismatch = true\n[[blocks]]\ntype = \"explicit\"\nname = \"Amp\"\ngain_db = 20.0\nnf = 3.0\noutput_vswr = 2.0\n[[blocks]]\ntype = \"touchstone\"\nfile_path = \"ntwk3.s2p\"\nname = \"Cable\"\n",
+        )
+        .unwrap();
+        let config = load_config(toml_path.to_str().unwrap()).unwrap();
+        assert!(config.mismatch);
+        assert_eq!(config.blocks[0].output_vswr, Some(2.0));
+
+        // the Touchstone block carries all four S-parameters, with S21 matching its gain
+        let s = config.blocks[1].s_parameters.unwrap();
+        assert!((s.gain_db() - config.blocks[1].gain_db).abs() < 1e-9);
+        assert!(s.s11.abs() > 0.0 && s.s11.abs() < 1.0);
+
+        let interfaces = mismatch_interfaces(&config.blocks);
+        assert_eq!(interfaces.len(), 1);
+        assert_eq!(interfaces[0].source_reflection, 1.0 / 3.0);
+        assert!(interfaces[0].mismatch_db >= interfaces[0].uncertainty_min_db - 1e-9);
+        assert!(interfaces[0].mismatch_db <= interfaces[0].uncertainty_max_db + 1e-9);
+
+        // mismatch defaults to off
+        let toml_path = test_dir.join("matched.toml");
+        fs::write(&toml_path, "pin = -30.0\nf = 6.0e9\nblocks = []\n").unwrap();
+        assert!(!load_config(toml_path.to_str().unwrap()).unwrap().mismatch);
+    }
 }
@@ -0,0 +1,495 @@
+use std::fmt;
+
+use crate::block::Block;
+use crate::constants;
+use crate::harmonics::Harmonic;
+use crate::node::{update_noise_percentages, NoiseContribution, SignalNode};
+use crate::sparameters::Complex;
+
+/// An N-way power splitter (Wilkinson or resistive divider).
+///
+/// On a single path the splitter is a passive loss of `10·log10(ways)` plus its excess
+/// loss, with a noise figure equal to that loss. Build one with
+/// [`Block::splitter`](crate::Block::splitter) and feed its outputs to branches with
+/// [`Branch::split`](crate::Branch::split).
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::Block;
+///
+/// let splitter = Block::splitter("4-Way", 4, 0.5);
+/// assert!((splitter.gain_db - (-6.52)).abs() < 0.01);
+/// assert_eq!(splitter.noise_figure_db, -splitter.gain_db);
+/// ```
+#[doc(alias = "divider")]
+#[doc(alias = "Wilkinson")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Splitter {
+    /// Number of output ports.
+    pub ways: usize,
+}
+
+impl fmt::Display for Splitter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Splitter {{ ways: {} }}", self.ways)
+    }
+}
+
+impl Splitter {
+    /// Create an N-way splitter.
+    #[must_use]
+    pub fn new(ways: usize) -> Self {
+        Self { ways }
+    }
+
+    /// The ideal split loss `10·log10(ways)` in dB.
+    #[must_use]
+    pub fn split_loss_db(&self) -> f64 {
+        10.0 * (self.ways as f64).log10()
+    }
+}
+
+/// An N-way power combiner.
+///
+/// Signals that are coherent across the inputs add in voltage, so N equal in-phase
+/// inputs come out N times stronger than one of them through the combiner; noise that
+/// is uncorrelated across the inputs adds in power. `coherent = false` adds the signals
+/// in power too, e.g. for channels at different frequencies.
+///
+/// On a single path (only one input driven) the combiner is a passive loss of
+/// `10·log10(ways)` plus its excess loss, like a [`Splitter`]. Build one with
+/// [`Block::combiner`](crate::Block::combiner) and use it in
+/// [`Branch::combine`](crate::Branch::combine).
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::Combiner;
+///
+/// let combiner = Combiner::new(2);
+/// assert!(combiner.coherent);
+/// assert!(!Combiner::new(2).incoherent().coherent);
+/// ```
+#[doc(alias = "summer")]
+#[doc(alias = "Wilkinson")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Combiner {
+    /// Number of input ports.
+    pub ways: usize,
+    /// Whether the signals at the inputs add in voltage (`true`) or in power.
+    pub coherent: bool,
+}
+
+impl fmt::Display for Combiner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Combiner {{ ways: {}, coherent: {} }}",
+            self.ways, self.coherent
+        )
+    }
+}
+
+impl Combiner {
+    /// Create a coherent N-way combiner.
+    #[must_use]
+    pub fn new(ways: usize) -> Self {
+        Self {
+            ways,
+            coherent: true,
+        }
+    }
+
+    /// Add the input signals in power instead of voltage.
+    #[must_use]
+    pub fn incoherent(mut self) -> Self {
+        self.coherent = false;
+        self
+    }
+
+    /// The ideal combining loss of one input `10·log10(ways)` in dB.
+    #[must_use]
+    pub fn combine_loss_db(&self) -> f64 {
+        10.0 * (self.ways as f64).log10()
+    }
+}
+
+/// Where the branches feeding a combiner came from, which sets what adds coherently.
+pub(crate) struct CombineReference<'a> {
+    /// The node before the splitter feeding every branch, or `None` for independent
+    /// sources. Its noise is common to every branch and adds like the signal.
+    pub shared: Option<&'a SignalNode>,
+    /// Signal power at the cascade input in dBm, summed over independent sources.
+    pub input_power_dbm: f64,
+    /// Source noise temperature in Kelvin, averaged over independent sources.
+    pub source_temperature_k: f64,
+}
+
+/// Add `(power in W, phase in degrees)` terms in voltage (coherent) or in power, then
+/// divide by the number of combiner ports.
+fn sum_over_ports(terms: &[(f64, f64)], coherent: bool, ways: usize) -> f64 {
+    let total = if coherent {
+        let reference_deg = terms.first().map_or(0.0, |&(_, phase_deg)| phase_deg);
+        let sum = terms
+            .iter()
+            .fold(Complex::ZERO, |sum, &(watts, phase_deg)| {
+                sum + Complex::from_polar_deg(watts.sqrt(), phase_deg - reference_deg)
+            });
+        sum.abs() * sum.abs()
+    } else {
+        terms.iter().map(|&(watts, _)| watts).sum()
+    };
+    total / ways as f64
+}
+
+/// Combine the levels of each branch that has one, in phase when `coherent`.
+fn combine_levels(levels: &[Option<f64>], coherent: bool, ways: usize) -> Option<f64> {
+    let terms: Option<Vec<(f64, f64)>> = levels
+        .iter()
+        .map(|level| level.map(|dbm| (rfconversions::power::dbm_to_watts(dbm), 0.0)))
+        .collect();
+    Some(rfconversions::power::watts_to_dbm(sum_over_ports(
+        &terms?, coherent, ways,
+    )))
+}
+
+/// The node at the output of an ideal (lossless) N-way combiner fed by `branches`.
+///
+/// The combiner's excess loss is left to the caller, who cascades it as a passive block.
+/// The signal adds in voltage with each branch's phase when `combiner` is coherent.
+/// Noise from the shared node is common to every branch and adds like the signal; the
+/// rest adds in power. Intercepts, P1dB and harmonics add in phase (the worst case).
+pub(crate) fn combine_branches(
+    name: &str,
+    combiner: &Combiner,
+    branches: &[(&str, &SignalNode)],
+    reference: &CombineReference,
+) -> SignalNode {
+    let ways = combiner.ways.max(branches.len());
+    let coherent = combiner.coherent;
+    let first = branches[0].1;
+
+    let signal_terms: Vec<(f64, f64)> = branches
+        .iter()
+        .map(|(_, node)| {
+            (
+                rfconversions::power::dbm_to_watts(node.signal_power_dbm),
+                node.cumulative_phase_deg,
+            )
+        })
+        .collect();
+    let signal_watts = sum_over_ports(&signal_terms, coherent, ways);
+    let signal_power_dbm = rfconversions::power::watts_to_dbm(signal_watts);
+    let cumulative_gain_db = signal_power_dbm - reference.input_power_dbm;
+    let gain_linear = rfconversions::power::db_to_linear(cumulative_gain_db);
+
+    // split each branch's noise into the part common to every branch and its own
+    let mut common_terms = vec![];
+    let mut own_noise_watts = 0.0;
+    for (_, node) in branches {
+        let noise_watts = rfconversions::power::dbm_to_watts(node.noise_power_dbm);
+        let common_watts = reference.shared.map_or(0.0, |shared| {
+            rfconversions::power::dbm_to_watts(
+                shared.noise_power_dbm + node.cumulative_gain_db - shared.cumulative_gain_db,
+            ) * node.signal_bandwidth_hz
+                / shared.signal_bandwidth_hz
+        });
+        let common_watts = common_watts.min(noise_watts);
+        common_terms.push((common_watts, node.cumulative_phase_deg));
+        own_noise_watts += noise_watts - common_watts;
+    }
+    let noise_watts = sum_over_ports(&common_terms, coherent, ways) + own_noise_watts / ways as f64;
+
+    // contributions before the split keep their input-referred share; each branch's own
+    // stages are scaled by that branch's gain relative to the combined gain
+    let shared_count = reference
+        .shared
+        .map_or(0, |shared| shared.noise_contributions.len());
+    let mut noise_contributions: Vec<NoiseContribution> = first
+        .noise_contributions
+        .iter()
+        .take(shared_count)
+        .cloned()
+        .collect();
+    for (branch, node) in branches {
+        let scale = rfconversions::power::db_to_linear(node.cumulative_gain_db)
+            / (ways as f64 * gain_linear);
+        for contribution in node.noise_contributions.iter().skip(shared_count) {
+            noise_contributions.push(NoiseContribution::new(
+                &format!("{}: {}", branch, contribution.name),
+                contribution.noise_temperature_k * scale,
+            ));
+        }
+    }
+    update_noise_percentages(&mut noise_contributions);
+    let system_temperature_k: f64 = noise_contributions
+        .iter()
+        .map(|c| c.noise_temperature_k)
+        .sum();
+    let cumulative_noise_figure_db = rfconversions::noise::noise_figure_from_noise_temperature(
+        system_temperature_k - reference.source_temperature_k,
+    );
+
+    let levels = |field: fn(&SignalNode) -> Option<f64>| -> Vec<Option<f64>> {
+        branches.iter().map(|(_, node)| field(node)).collect()
+    };
+    let cumulative_oip3_dbm = combine_levels(&levels(|n| n.cumulative_oip3_dbm), coherent, ways);
+    let cumulative_oip2_dbm = combine_levels(&levels(|n| n.cumulative_oip2_dbm), coherent, ways);
+    let output_p1db_dbm = combine_levels(&levels(|n| n.output_p1db_dbm), coherent, ways);
+
+    // the stage closest to compression in any branch, with every branch at that level
+    let limiting = branches
+        .iter()
+        .filter_map(|(_, node)| {
+            Some((
+                node.p1db_limiting_stage.clone()?,
+                node.p1db_limiting_stage_dbm?,
+            ))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+    let (p1db_limiting_stage, p1db_limiting_stage_dbm) = match limiting {
+        Some((stage, dbm)) => (
+            Some(stage),
+            combine_levels(&vec![Some(dbm); branches.len()], coherent, ways),
+        ),
+        None => (None, None),
+    };
+
+    let bandwidth_hz = first.signal_bandwidth_hz;
+    let noise_floor_dbm = -174.0 + 10.0 * bandwidth_hz.log10() + cumulative_noise_figure_db;
+
+    let count = branches.len() as f64;
+    let phase_sum = branches.iter().fold(Complex::ZERO, |sum, (_, node)| {
+        sum + Complex::from_polar_deg(
+            rfconversions::power::dbm_to_watts(node.signal_power_dbm).sqrt(),
+            node.cumulative_phase_deg - first.cumulative_phase_deg,
+        )
+    });
+
+    tracing::debug!(
+        combiner = name,
+        branches = branches.len(),
+        signal_dbm = signal_power_dbm,
+        noise_dbm = rfconversions::power::watts_to_dbm(noise_watts),
+        nf_db = cumulative_noise_figure_db,
+        "Combined branches"
+    );
+
+    SignalNode {
+        name: name.to_string(),
+        signal_frequency_hz: first.signal_frequency_hz,
+        signal_bandwidth_hz: bandwidth_hz,
+        signal_power_dbm,
+        noise_power_dbm: rfconversions::power::watts_to_dbm(noise_watts),
+        cumulative_noise_figure_db,
+        cumulative_gain_db,
+        cumulative_noise_temperature: Some(system_temperature_k),
+        cumulative_oip3_dbm,
+        sfdr_db: cumulative_oip3_dbm.map(|oip3| 2.0 / 3.0 * (oip3 - noise_floor_dbm)),
+        cumulative_oip2_dbm,
+        sfdr2_db: cumulative_oip2_dbm.map(|oip2| 0.5 * (oip2 - noise_floor_dbm)),
+        output_p1db_dbm,
+        p1db_limiting_stage,
+        p1db_limiting_stage_dbm,
+        image_frequency_hz: None,
+        cumulative_phase_deg: if coherent {
+            first.cumulative_phase_deg + phase_sum.arg_deg()
+        } else {
+            first.cumulative_phase_deg
+        },
+        cumulative_am_pm_deg: branches
+            .iter()
+            .map(|(_, node)| node.cumulative_am_pm_deg)
+            .sum::<f64>()
+            / count,
+        cumulative_group_delay_s: branches
+            .iter()
+            .map(|(_, node)| node.cumulative_group_delay_s)
+            .sum::<f64>()
+            / count,
+        phase_noise: first.phase_noise.clone(),
+        lo_phase_noise: first.lo_phase_noise.clone(),
+        harmonics: combine_harmonics(branches, coherent, ways),
+        array: None,
+        papr_db: first.papr_db,
+        adc_headroom_db: None,
+        noise_filters: first.noise_filters.clone(),
+        alias_snr_degradation_db: first.alias_snr_degradation_db,
+        sample_rate_hz: first.sample_rate_hz,
+        full_scale_dbm: first.full_scale_dbm,
+        noise_contributions,
+        linearity_contributions: vec![],
+    }
+}
+
+/// Harmonics of the same order and frequency from every branch, added like the signal.
+fn combine_harmonics(
+    branches: &[(&str, &SignalNode)],
+    coherent: bool,
+    ways: usize,
+) -> Vec<Harmonic> {
+    let mut groups: Vec<(Harmonic, Vec<(f64, f64)>)> = vec![];
+    for (_, node) in branches {
+        for harmonic in &node.harmonics {
+            let watts = rfconversions::power::dbm_to_watts(harmonic.power_dbm);
+            match groups.iter_mut().find(|(existing, _)| {
+                existing.order == harmonic.order
+                    && (existing.frequency_hz - harmonic.frequency_hz).abs()
+                        <= constants::FREQUENCY_TOLERANCE * harmonic.frequency_hz
+            }) {
+                Some((_, terms)) => terms.push((watts, 0.0)),
+                None => groups.push((harmonic.clone(), vec![(watts, 0.0)])),
+            }
+        }
+    }
+    let mut harmonics: Vec<Harmonic> = groups
+        .into_iter()
+        .map(|(harmonic, terms)| Harmonic {
+            power_dbm: rfconversions::power::watts_to_dbm(sum_over_ports(&terms, coherent, ways)),
+            ..harmonic
+        })
+        .collect();
+    harmonics.sort_by(|a, b| {
+        a.order
+            .cmp(&b.order)
+            .then(a.frequency_hz.total_cmp(&b.frequency_hz))
+    });
+    harmonics
+}
+
+impl Block {
+    /// Create an N-way splitter stage from its excess loss beyond the ideal `10·log10(N)`.
+    ///
+    /// The noise figure equals the total loss (passive at 290 K).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Block, BlockKind, Splitter};
+    ///
+    /// let splitter = Block::splitter("2-Way", 2, 0.3);
+    /// assert!((splitter.gain_db - (-3.31)).abs() < 0.01);
+    /// assert_eq!(splitter.kind, BlockKind::Splitter(Splitter::new(2)));
+    /// ```
+    #[doc(alias = "divider")]
+    #[must_use]
+    pub fn splitter(name: &str, ways: usize, excess_loss_db: f64) -> Block {
+        let splitter = Splitter::new(ways);
+        let loss_db = splitter.split_loss_db() + excess_loss_db;
+        Block {
+            name: name.to_string(),
+            gain_db: -loss_db,
+            noise_figure_db: loss_db,
+            kind: crate::block::BlockKind::Splitter(splitter),
+            ..Default::default()
+        }
+    }
+
+    /// Create a coherent N-way combiner stage from its excess loss beyond the ideal
+    /// `10·log10(N)`.
+    ///
+    /// `gain_db` and `noise_figure_db` are for one driven input, like a splitter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Block, BlockKind, Combiner};
+    ///
+    /// let combiner = Block::combiner("Combiner", 2, 0.3);
+    /// assert!((combiner.gain_db - (-3.31)).abs() < 0.01);
+    /// assert_eq!(combiner.kind, BlockKind::Combiner(Combiner::new(2)));
+    /// ```
+    #[doc(alias = "summer")]
+    #[must_use]
+    pub fn combiner(name: &str, ways: usize, excess_loss_db: f64) -> Block {
+        let combiner = Combiner::new(ways);
+        let loss_db = combiner.combine_loss_db() + excess_loss_db;
+        Block {
+            name: name.to_string(),
+            gain_db: -loss_db,
+            noise_figure_db: loss_db,
+            kind: crate::block::BlockKind::Combiner(combiner),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+
+    fn amp(name: &str) -> Block {
+        Block::builder(name)
+            .gain_db(20.0)
+            .noise_figure_db(3.0)
+            .output_ip3_dbm(30.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn independent_channels_average_noise_temperature() {
+        let input = Input::new(1.0e9, 1.0e6, -80.0, Some(290.0));
+        let a = input.cascade_block(&amp("A"));
+        let b = input.cascade_block(&amp("B"));
+        let reference = CombineReference {
+            shared: None,
+            input_power_dbm: -80.0 + 10.0 * 2.0_f64.log10(),
+            source_temperature_k: 290.0,
+        };
+        let node = combine_branches(
+            "Sum",
+            &Combiner::new(2),
+            &[("1", &a), ("2", &b)],
+            &reference,
+        );
+        // coherent signal: +3 dB over one channel, uncorrelated noise: unchanged
+        assert!((node.signal_power_dbm - (a.signal_power_dbm + 3.0103)).abs() < 1e-3);
+        assert!((node.noise_power_dbm - a.noise_power_dbm).abs() < 1e-9);
+        assert!((node.cumulative_gain_db - 20.0).abs() < 1e-9);
+        assert!((node.cumulative_noise_figure_db - a.cumulative_noise_figure_db).abs() < 1e-9);
+        assert_eq!(node.noise_contributions[0].name, "1: Input");
+        assert!((node.noise_contributions[0].percent - 25.0).abs() < 0.1);
+        // identical intercepts combine to +3 dB
+        assert!((node.cumulative_oip3_dbm.unwrap() - 33.0103).abs() < 1e-3);
+    }
+
+    #[test]
+    fn incoherent_and_out_of_phase_signals() {
+        let input = Input::new(1.0e9, 1.0e6, -80.0, Some(290.0));
+        let a = input.cascade_block(&amp("A"));
+        let mut b = input.cascade_block(&amp("B"));
+        let reference = CombineReference {
+            shared: None,
+            input_power_dbm: -80.0 + 10.0 * 2.0_f64.log10(),
+            source_temperature_k: 290.0,
+        };
+        let node = combine_branches(
+            "Sum",
+            &Combiner::new(2).incoherent(),
+            &[("1", &a), ("2", &b)],
+            &reference,
+        );
+        assert!((node.signal_power_dbm - a.signal_power_dbm).abs() < 1e-9);
+
+        b.cumulative_phase_deg = 180.0;
+        let node = combine_branches(
+            "Sum",
+            &Combiner::new(2),
+            &[("1", &a), ("2", &b)],
+            &reference,
+        );
+        assert!(node.signal_power_dbm < a.signal_power_dbm - 100.0);
+    }
+
+    #[test]
+    fn display_splitter_and_combiner() {
+        assert_eq!(format!("{}", Splitter::new(4)), "Splitter { ways: 4 }");
+        assert_eq!(
+            format!("{}", Combiner::new(2)),
+            "Combiner { ways: 2, coherent: true }"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+
+/// 1 dB as a power ratio.
+const ONE_DB: f64 = 1.258_925_411_794_167_2;
+
+/// AM-AM (output power versus input power) model used to compress a block's output.
+///
+/// Every smooth model is calibrated from the block's output P1dB so that the gain is
+/// exactly 1 dB below small-signal gain when the output is at P1dB (unless a Rapp
+/// `saturation_power_dbm` is given). The default is Rapp
+/// with a smoothness of [`DEFAULT_RAPP_SMOOTHNESS`](Self::DEFAULT_RAPP_SMOOTHNESS), a
+/// typical solid-state amplifier knee, with `P_sat` derived from P1dB. `HardClip` is the
+/// crate's behaviour before compression models: perfectly linear, then limited at
+/// P1dB + 1 dB, so its gain is never compressed at P1dB.
+///
+/// With `P_lin` the uncompressed (small-signal) output power:
+///
+/// | Model    | Output power                                   | Saturation       |
+/// |----------|------------------------------------------------|------------------|
+/// | HardClip | `min(P_lin, P1dB + 1 dB)`                      | P1dB + 1 dB      |
+/// | Rapp     | `P_lin / (1 + (P_lin / P_sat)^p)^(1/p)`        | `P_sat`          |
+/// | Saleh    | `P_lin / (1 + P_lin / P_0)²`                   | `P_0 / 4`, then folds back |
+/// | Tanh     | `P_s · tanh²(√(P_lin / P_s))`                  | `P_s`            |
+/// | Cubic    | `P_lin · (1 − P_lin / P_c)²`, held past its peak | `4 P_c / 27`   |
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::CompressionModel;
+///
+/// let rapp = CompressionModel::Rapp { smoothness: 2.0, saturation_power_dbm: None };
+/// // 1 dB compressed at P1dB = 10 dBm
+/// let pout = rapp.output_power_dbm(11.0, 10.0);
+/// assert!((pout - 10.0).abs() < 1e-9);
+/// // nearly linear well below P1dB
+/// assert!((rapp.output_power_dbm(-20.0, 10.0) - (-20.0)).abs() < 0.01);
+/// ```
+#[doc(alias = "AM-AM")]
+#[doc(alias = "Rapp")]
+#[doc(alias = "Saleh")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompressionModel {
+    /// Linear up to P1dB + 1 dB, then clamped there.
+    HardClip,
+    /// Rapp solid-state amplifier model.
+    ///
+    /// An explicit `saturation_power_dbm` overrides the P1dB calibration: the curve runs
+    /// through that `P_sat`, so the gain at the block's P1dB is no longer exactly 1 dB
+    /// compressed. `smoothness` must be positive and `P_sat` above P1dB + 1 dB;
+    /// [`BlockBuilder::build`](crate::BlockBuilder::build) rejects anything else and
+    /// [`Block::validate`](crate::Block::validate) reports it as an error.
+    Rapp {
+        /// Smoothness factor `p` (higher is a sharper knee; 2–3 is typical for SSPAs).
+        smoothness: f64,
+        /// Saturated output power in dBm, derived from P1dB and `smoothness` if `None`.
+        saturation_power_dbm: Option<f64>,
+    },
+    /// Saleh traveling-wave tube model; gain folds back beyond saturation.
+    Saleh,
+    /// Hyperbolic tangent soft limiter.
+    Tanh,
+    /// Third-order polynomial, held at its peak once the cubic term turns over.
+    Cubic,
+}
+
+impl Default for CompressionModel {
+    fn default() -> Self {
+        CompressionModel::Rapp {
+            smoothness: CompressionModel::DEFAULT_RAPP_SMOOTHNESS,
+            saturation_power_dbm: None,
+        }
+    }
+}
+
+impl fmt::Display for CompressionModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompressionModel::HardClip => write!(f, "hard clip"),
+            CompressionModel::Rapp {
+                smoothness,
+                saturation_power_dbm,
+            } => {
+                write!(f, "Rapp (p = {}", smoothness)?;
+                if let Some(psat) = saturation_power_dbm {
+                    write!(f, ", psat: {} dBm", psat)?;
+                }
+                write!(f, ")")
+            }
+            CompressionModel::Saleh => write!(f, "Saleh"),
+            CompressionModel::Tanh => write!(f, "tanh"),
+            CompressionModel::Cubic => write!(f, "cubic"),
+        }
+    }
+}
+
+impl CompressionModel {
+    /// Rapp smoothness `p` of the default model.
+    pub const DEFAULT_RAPP_SMOOTHNESS: f64 = 2.0;
+
+    /// Compressed output power in dBm for an uncompressed output of `linear_output_dbm`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::CompressionModel;
+    ///
+    /// let clip = CompressionModel::HardClip;
+    /// assert_eq!(clip.output_power_dbm(5.0, 10.0), 5.0);
+    /// assert_eq!(clip.output_power_dbm(20.0, 10.0), 11.0);
+    ///
+    /// for model in [CompressionModel::Saleh, CompressionModel::Tanh, CompressionModel::Cubic] {
+    ///     assert!((model.output_power_dbm(11.0, 10.0) - 10.0).abs() < 1e-6);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn output_power_dbm(&self, linear_output_dbm: f64, output_p1db_dbm: f64) -> f64 {
+        // uncompressed output power at the 1 dB compression point
+        let linear_at_p1db_dbm = output_p1db_dbm + 1.0;
+        let p_lin = rfconversions::power::dbm_to_watts(linear_output_dbm);
+        let p_lin_1 = rfconversions::power::dbm_to_watts(linear_at_p1db_dbm);
+
+        let p_out = match *self {
+            CompressionModel::HardClip => {
+                return linear_output_dbm.min(linear_at_p1db_dbm);
+            }
+            CompressionModel::Rapp { smoothness, .. } => {
+                let p_sat = rfconversions::power::dbm_to_watts(
+                    self.saturation_power_dbm(output_p1db_dbm),
+                );
+                p_lin / (1.0 + (p_lin / p_sat).powf(smoothness)).powf(1.0 / smoothness)
+            }
+            CompressionModel::Saleh => {
+                let p_0 = p_lin_1 / (ONE_DB.sqrt() - 1.0);
+                p_lin / (1.0 + p_lin / p_0).powi(2)
+            }
+            CompressionModel::Tanh => {
+                let u_1 = tanh_one_db_argument();
+                let p_s = p_lin_1 / (u_1 * u_1);
+                p_s * (p_lin / p_s).sqrt().tanh().powi(2)
+            }
+            CompressionModel::Cubic => {
+                let p_c = p_lin_1 / (1.0 - 1.0 / ONE_DB.sqrt());
+                // the cubic peaks at P_c / 3; beyond that the output is held
+                let p_lin = p_lin.min(p_c / 3.0);
+                p_lin * (1.0 - p_lin / p_c).powi(2)
+            }
+        };
+        rfconversions::power::watts_to_dbm(p_out)
+    }
+
+    /// Maximum (saturated) output power in dBm of this model for the given output P1dB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::CompressionModel;
+    ///
+    /// assert_eq!(CompressionModel::HardClip.saturation_power_dbm(10.0), 11.0);
+    /// let rapp = CompressionModel::Rapp { smoothness: 2.0, saturation_power_dbm: Some(13.0) };
+    /// assert_eq!(rapp.saturation_power_dbm(10.0), 13.0);
+    /// ```
+    #[must_use]
+    pub fn saturation_power_dbm(&self, output_p1db_dbm: f64) -> f64 {
+        let linear_at_p1db_dbm = output_p1db_dbm + 1.0;
+        let p_lin_1 = rfconversions::power::dbm_to_watts(linear_at_p1db_dbm);
+        let p_sat = match *self {
+            CompressionModel::HardClip => return linear_at_p1db_dbm,
+            CompressionModel::Rapp {
+                smoothness,
+                saturation_power_dbm,
+            } => {
+                if let Some(psat) = saturation_power_dbm {
+                    return psat;
+                }
+                p_lin_1 / (ONE_DB.powf(smoothness) - 1.0).powf(1.0 / smoothness)
+            }
+            CompressionModel::Saleh => p_lin_1 / (ONE_DB.sqrt() - 1.0) / 4.0,
+            CompressionModel::Tanh => {
+                let u_1 = tanh_one_db_argument();
+                p_lin_1 / (u_1 * u_1)
+            }
+            CompressionModel::Cubic => {
+                let p_c = p_lin_1 / (1.0 - 1.0 / ONE_DB.sqrt());
+                4.0 * p_c / 27.0
+            }
+        };
+        rfconversions::power::watts_to_dbm(p_sat)
+    }
+
+    /// What is wrong with the model's parameters for a block with `output_p1db_dbm`, if
+    /// anything: a Rapp smoothness that is not positive, or a saturation power that is not
+    /// finite or not above P1dB + 1 dB.
+    pub(crate) fn parameter_problem(&self, output_p1db_dbm: Option<f64>) -> Option<String> {
+        let CompressionModel::Rapp {
+            smoothness,
+            saturation_power_dbm,
+        } = *self
+        else {
+            return None;
+        };
+        if !(smoothness.is_finite() && smoothness > 0.0) {
+            return Some(format!(
+                "Rapp smoothness {} must be a positive number",
+                smoothness
+            ));
+        }
+        let psat = saturation_power_dbm?;
+        if !psat.is_finite() {
+            return Some(format!(
+                "Rapp saturation power {} is not a finite number",
+                psat
+            ));
+        }
+        match output_p1db_dbm {
+            Some(op1db) if psat <= op1db + 1.0 => Some(format!(
+                "Rapp saturation power {} dBm must be above the output P1dB + 1 dB ({} dBm)",
+                psat,
+                op1db + 1.0
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Argument `u` where `tanh(u) / u` is 1 dB (in power) below unity.
+fn tanh_one_db_argument() -> f64 {
+    let target = 1.0 / ONE_DB.sqrt();
+    // tanh(u) / u falls monotonically from 1 at u = 0
+    let (mut lo, mut hi) = (1e-6_f64, 5.0_f64);
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if mid.tanh() / mid > target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODELS: [CompressionModel; 5] = [
+        CompressionModel::HardClip,
+        CompressionModel::Rapp {
+            smoothness: 2.0,
+            saturation_power_dbm: None,
+        },
+        CompressionModel::Saleh,
+        CompressionModel::Tanh,
+        CompressionModel::Cubic,
+    ];
+
+    #[test]
+    fn smooth_models_are_one_db_compressed_at_p1db() {
+        for model in &MODELS[1..] {
+            let compression = 11.0 - model.output_power_dbm(11.0, 10.0);
+            assert!(
+                (compression - 1.0).abs() < 1e-6,
+                "{} compression at P1dB was {}",
+                model,
+                compression
+            );
+        }
+    }
+
+    #[test]
+    fn models_are_linear_at_low_power() {
+        for model in &MODELS {
+            let pout = model.output_power_dbm(-40.0, 10.0);
+            assert!((pout - (-40.0)).abs() < 0.01, "{} gave {}", model, pout);
+        }
+    }
+
+    #[test]
+    fn smooth_models_compress_gradually() {
+        // half a dB below the hard clip's knee the smooth models already compress a little
+        for model in &MODELS[1..] {
+            let compression = 5.0 - model.output_power_dbm(5.0, 10.0);
+            assert!(
+                compression > 0.05 && compression < 1.0,
+                "{} gave {}",
+                model,
+                compression
+            );
+        }
+        assert_eq!(CompressionModel::HardClip.output_power_dbm(5.0, 10.0), 5.0);
+    }
+
+    #[test]
+    fn models_approach_saturation() {
+        for model in [
+            CompressionModel::Rapp {
+                smoothness: 2.0,
+                saturation_power_dbm: None,
+            },
+            CompressionModel::Tanh,
+            CompressionModel::Cubic,
+        ] {
+            let psat = model.saturation_power_dbm(10.0);
+            let pout = model.output_power_dbm(40.0, 10.0);
+            assert!(pout <= psat + 1e-9, "{} exceeded saturation", model);
+            assert!(psat - pout < 0.01, "{} gave {} vs psat {}", model, pout, psat);
+        }
+    }
+
+    #[test]
+    fn saleh_peaks_then_folds_back() {
+        let model = CompressionModel::Saleh;
+        let psat = model.saturation_power_dbm(10.0);
+        let p_0_dbm = psat + 10.0 * 4.0_f64.log10();
+        assert!((model.output_power_dbm(p_0_dbm, 10.0) - psat).abs() < 1e-9);
+        assert!(model.output_power_dbm(p_0_dbm + 6.0, 10.0) < psat - 1.0);
+    }
+
+    #[test]
+    fn rapp_explicit_saturation() {
+        let model = CompressionModel::Rapp {
+            smoothness: 3.0,
+            saturation_power_dbm: Some(20.0),
+        };
+        assert!((model.output_power_dbm(60.0, 10.0) - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn display_models() {
+        assert_eq!(format!("{}", CompressionModel::Saleh), "Saleh");
+        let rapp = CompressionModel::Rapp {
+            smoothness: 2.0,
+            saturation_power_dbm: Some(20.0),
+        };
+        assert_eq!(format!("{}", rapp), "Rapp (p = 2, psat: 20 dBm)");
+    }
+}
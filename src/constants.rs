@@ -1,2 +1,6 @@
 /// Boltzmann constant in J/K (SI units).
 pub const BOLTZMANN: f64 = 1.380649e-23;
+
+/// Relative tolerance when matching frequencies, so float noise in file or computed
+/// frequencies (table edges, harmonic and alias frequencies) still matches.
+pub(crate) const FREQUENCY_TOLERANCE: f64 = 1e-9;
@@ -0,0 +1,300 @@
+use std::fmt;
+
+use crate::adc::noise_figure_from_density;
+use crate::block::{Block, BlockKind};
+use crate::filter::Filter;
+
+/// A digital signal processing stage after the ADC.
+///
+/// In order, the stage mixes the samples down by its NCO frequency, filters them (the FIR
+/// or CIC response of a channel or decimation filter), keeps every `decimation`-th sample
+/// and rounds the result to `output_bits`. The filter is given after the NCO, so a
+/// low-pass is a [`Filter`] centered at 0 Hz; it narrows the bandwidth like an analog
+/// filter, and the out-of-band noise it passes folds onto the signal at the decimated
+/// rate. Without a filter, decimation is ideal.
+///
+/// Rounding to `b` bits adds the quantization noise of a `b`-bit converter,
+/// `SNR = 6.02·b + 1.76 dB` for a full-scale sine, spread over the Nyquist zone of the
+/// output sample rate. Levels stay in dBm referred to the ADC input, so
+/// [`SignalNode::signal_power_dbfs`](crate::SignalNode::signal_power_dbfs) is the same
+/// bookkeeping in dBFS; a digital gain (the block's `gain_db`) moves the signal against
+/// the fixed full scale.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{DigitalStage, Filter};
+///
+/// let ddc = DigitalStage::nco(30.0e6)
+///     .with_filter(Filter::new(0.0, 2.0e6).with_rejection(vec![(2.0e6, 80.0)]))
+///     .with_decimation(16)
+///     .with_output_bits(16);
+/// assert_eq!(ddc.output_frequency_hz(31.0e6), 1.0e6);
+/// assert_eq!(ddc.output_sample_rate_hz(100.0e6), 6.25e6);
+/// assert!((ddc.rounding_snr_db().unwrap() - 98.08).abs() < 0.01);
+/// ```
+#[doc(alias = "DSP")]
+#[doc(alias = "DDC")]
+#[doc(alias = "NCO")]
+#[doc(alias = "decimation")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigitalStage {
+    /// NCO frequency in Hz the signal is mixed down by, if any.
+    pub nco_frequency_hz: Option<f64>,
+    /// Channel or decimation filter at the NCO output, if any.
+    pub filter: Option<Filter>,
+    /// Decimation factor, 1 for none.
+    pub decimation: u32,
+    /// Word width in bits the output is rounded or truncated to, if any.
+    pub output_bits: Option<u32>,
+}
+
+impl Default for DigitalStage {
+    fn default() -> Self {
+        Self {
+            nco_frequency_hz: None,
+            filter: None,
+            decimation: 1,
+            output_bits: None,
+        }
+    }
+}
+
+impl fmt::Display for DigitalStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DigitalStage {{ decimation: {}", self.decimation)?;
+        if let Some(nco_frequency_hz) = self.nco_frequency_hz {
+            write!(f, ", nco: {} Hz", nco_frequency_hz)?;
+        }
+        if let Some(output_bits) = self.output_bits {
+            write!(f, ", output_bits: {}", output_bits)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl DigitalStage {
+    /// A numerically controlled oscillator and mixer, shifting the signal down by
+    /// `frequency_hz`.
+    #[must_use]
+    pub fn nco(frequency_hz: f64) -> Self {
+        Self {
+            nco_frequency_hz: Some(frequency_hz),
+            ..Default::default()
+        }
+    }
+
+    /// A decimator keeping every `factor`-th sample.
+    #[doc(alias = "CIC")]
+    #[doc(alias = "FIR")]
+    #[must_use]
+    pub fn decimator(factor: u32) -> Self {
+        Self {
+            decimation: factor,
+            ..Default::default()
+        }
+    }
+
+    /// Rounding or truncation to `bits`.
+    #[doc(alias = "fixed point")]
+    #[doc(alias = "requantization")]
+    #[must_use]
+    pub fn truncation(bits: u32) -> Self {
+        Self {
+            output_bits: Some(bits),
+            ..Default::default()
+        }
+    }
+
+    /// Set the channel or decimation filter.
+    #[must_use]
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Set the decimation factor.
+    #[must_use]
+    pub fn with_decimation(mut self, factor: u32) -> Self {
+        self.decimation = factor;
+        self
+    }
+
+    /// Set the output word width in bits.
+    #[must_use]
+    pub fn with_output_bits(mut self, bits: u32) -> Self {
+        self.output_bits = Some(bits);
+        self
+    }
+
+    /// Signal frequency in Hz after the NCO for an input at `input_frequency_hz`.
+    #[must_use]
+    pub fn output_frequency_hz(&self, input_frequency_hz: f64) -> f64 {
+        match self.nco_frequency_hz {
+            Some(nco_frequency_hz) => (input_frequency_hz - nco_frequency_hz).abs(),
+            None => input_frequency_hz,
+        }
+    }
+
+    /// Sample rate in Hz after decimating `input_sample_rate_hz`.
+    #[must_use]
+    pub fn output_sample_rate_hz(&self, input_sample_rate_hz: f64) -> f64 {
+        input_sample_rate_hz / f64::from(self.decimation.max(1))
+    }
+
+    /// SNR in dB of a full-scale sine rounded to `output_bits`, if set.
+    #[must_use]
+    pub fn rounding_snr_db(&self) -> Option<f64> {
+        self.output_bits.map(|bits| 6.02 * f64::from(bits) + 1.76)
+    }
+
+    /// Rounding noise density in dBm/Hz at the output for a full scale of
+    /// `full_scale_dbm` and an input sample rate of `input_sample_rate_hz`, if the output
+    /// is rounded.
+    #[must_use]
+    pub fn rounding_noise_density_dbm_per_hz(
+        &self,
+        full_scale_dbm: f64,
+        input_sample_rate_hz: f64,
+    ) -> Option<f64> {
+        let nyquist_bandwidth_hz = self.output_sample_rate_hz(input_sample_rate_hz) / 2.0;
+        self.rounding_snr_db()
+            .map(|snr_db| full_scale_dbm - snr_db - 10.0 * nyquist_bandwidth_hz.log10())
+    }
+}
+
+/// `block` with the noise figure of its rounding noise, if it is a digital stage that
+/// rounds and an ADC upstream set the full scale and sample rate.
+pub(crate) fn at_node(
+    block: &Block,
+    full_scale_dbm: Option<f64>,
+    sample_rate_hz: Option<f64>,
+) -> Option<Block> {
+    let BlockKind::Digital(stage) = &block.kind else {
+        return None;
+    };
+    let density_dbm_per_hz =
+        stage.rounding_noise_density_dbm_per_hz(full_scale_dbm?, sample_rate_hz?)?;
+    // referred to the stage input, like any other noise figure
+    Some(Block {
+        noise_figure_db: noise_figure_from_density(density_dbm_per_hz - block.gain_db),
+        ..block.clone()
+    })
+}
+
+/// Sample rate at the output of `block`: set by an ADC, divided by decimation.
+pub(crate) fn sample_rate_hz(block: &Block, previous: Option<f64>) -> Option<f64> {
+    match &block.kind {
+        BlockKind::Adc(adc) => Some(adc.sample_rate_hz),
+        BlockKind::Digital(stage) => previous.map(|rate| stage.output_sample_rate_hz(rate)),
+        _ => previous,
+    }
+}
+
+/// The level in dBm referred to the lineup that is 0 dBFS at the output of `block`.
+pub(crate) fn full_scale_dbm(block: &Block, previous: Option<f64>) -> Option<f64> {
+    match &block.kind {
+        BlockKind::Adc(adc) => Some(adc.full_scale_dbm + block.gain_db),
+        _ => previous,
+    }
+}
+
+impl Block {
+    /// Create a digital stage with a digital gain of `gain_db`.
+    ///
+    /// The noise figure is set in the cascade from the rounding noise, relative to the full
+    /// scale and sample rate of the ADC upstream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{cascade_vector_return_vector, Adc, Block, DigitalStage, Input};
+    ///
+    /// let blocks = vec![
+    ///     Block::adc("ADC", Adc::new(4.0, 100.0e6, 70.0)),
+    ///     Block::digital("Gain", 6.0, DigitalStage::truncation(16)),
+    /// ];
+    /// let nodes = cascade_vector_return_vector(Input::new(10.0e6, 1.0e6, -20.0, None), blocks);
+    /// assert_eq!(nodes[0].signal_power_dbfs(), Some(-24.0));
+    /// assert_eq!(nodes[1].signal_power_dbfs(), Some(-18.0));
+    /// ```
+    #[doc(alias = "DSP")]
+    #[must_use]
+    pub fn digital(name: &str, gain_db: f64, stage: DigitalStage) -> Block {
+        Block {
+            name: name.to_string(),
+            gain_db,
+            noise_figure_db: 0.0,
+            kind: BlockKind::Digital(stage),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adc::Adc;
+    use crate::input::Input;
+
+    fn digitized() -> crate::SignalNode {
+        // 12 dB below a +10 dBm full scale at 100 MSPS
+        let input = Input::new(20.0e6, 1.0e6, -2.0, Some(290.0));
+        input.cascade_block(&Block::adc("ADC", Adc::new(10.0, 100.0e6, 74.0)))
+    }
+
+    #[test]
+    fn nco_and_decimation_move_the_signal() {
+        let stage = DigitalStage::nco(25.0e6).with_decimation(4);
+        assert_eq!(stage.output_frequency_hz(20.0e6), 5.0e6);
+        assert_eq!(stage.output_sample_rate_hz(100.0e6), 25.0e6);
+        assert_eq!(DigitalStage::decimator(0).output_sample_rate_hz(1.0), 1.0);
+
+        let node = digitized().cascade_block(&Block::digital("DDC", 0.0, stage));
+        assert_eq!(node.signal_frequency_hz, 5.0e6);
+        assert_eq!(node.sample_rate_hz, Some(25.0e6));
+        assert_eq!(node.full_scale_dbm, Some(10.0));
+        assert!((node.processing_gain_db().unwrap() - 10.97).abs() < 0.01);
+    }
+
+    #[test]
+    fn rounding_noise_follows_the_word_width() {
+        let adc = digitized();
+        // 16 bits are 24 dB quieter than the 74 dB converter: a small loss
+        let wide = adc.cascade_block(&Block::digital("Round", 0.0, DigitalStage::truncation(16)));
+        let loss_db = adc.signal_to_noise_ratio_db() - wide.signal_to_noise_ratio_db();
+        assert!(loss_db > 0.0 && loss_db < 0.05, "got {loss_db:.3} dB");
+
+        // 12 bits round off as much noise as the converter adds
+        let narrow = adc.cascade_block(&Block::digital("Round", 0.0, DigitalStage::truncation(12)));
+        let density = DigitalStage::truncation(12)
+            .rounding_noise_density_dbm_per_hz(10.0, 100.0e6)
+            .unwrap();
+        let expected_w = rfconversions::power::dbm_to_watts(adc.noise_power_dbm)
+            + rfconversions::power::dbm_to_watts(density + 60.0);
+        assert!(
+            (narrow.noise_power_dbm - rfconversions::power::watts_to_dbm(expected_w)).abs() < 1e-6
+        );
+
+        // digital gain raises signal and rounding noise together against full scale
+        let gained = adc.cascade_block(&Block::digital("Gain", 6.0, DigitalStage::default()));
+        assert_eq!(gained.signal_power_dbfs(), Some(-6.0));
+        assert!((gained.signal_to_noise_ratio_db() - adc.signal_to_noise_ratio_db()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn without_an_adc_there_is_no_rounding_noise() {
+        let block = Block::digital("Round", 0.0, DigitalStage::truncation(8));
+        assert!(at_node(&block, None, Some(1.0e6)).is_none());
+        assert!(at_node(&block, Some(0.0), None).is_none());
+        assert!(at_node(&block, Some(0.0), Some(1.0e6)).is_some());
+    }
+
+    #[test]
+    fn display_digital_stage() {
+        assert_eq!(
+            format!("{}", DigitalStage::nco(1.0e6).with_output_bits(16)),
+            "DigitalStage { decimation: 1, nco: 1000000 Hz, output_bits: 16 }"
+        );
+    }
+}
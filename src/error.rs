@@ -0,0 +1,237 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Errors from loading a lineup configuration, reading Touchstone data or building blocks.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Block, GainlineupError};
+///
+/// let err = Block::builder("Amp").gain_db(20.0).gain_linear(50.0).build().unwrap_err();
+/// match err {
+///     GainlineupError::InvalidParameter { block, parameter, .. } => {
+///         assert_eq!(block, "Amp");
+///         assert_eq!(parameter, "gain");
+///     }
+///     other => panic!("unexpected error: {}", other),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GainlineupError {
+    /// A file could not be read.
+    Io {
+        /// The file being read.
+        path: PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+    /// A configuration file is not valid TOML or does not match the expected schema.
+    TomlParse {
+        /// The file being parsed.
+        path: PathBuf,
+        /// 1-based line of the error, if known.
+        line: Option<usize>,
+        /// 1-based column of the error, if known.
+        column: Option<usize>,
+        /// The parser's description of the problem.
+        message: String,
+    },
+    /// The requested frequency is outside the range of a Touchstone file (no extrapolation).
+    TouchstoneFrequencyNotFound {
+        /// The Touchstone file, as written in the configuration.
+        path: PathBuf,
+        /// The frequency that was looked up in Hz.
+        frequency_hz: f64,
+    },
+    /// A Touchstone file is not an `.sNp` file or its contents are malformed.
+    MalformedTouchstone {
+        /// The Touchstone file.
+        path: PathBuf,
+        /// The first problem found, e.g. a short data entry or an unknown option.
+        message: String,
+    },
+    /// An error inside an included configuration file.
+    Include {
+        /// Included files from the outermost to the one that failed.
+        chain: Vec<PathBuf>,
+        /// The error in the innermost included file.
+        source: Box<GainlineupError>,
+    },
+    /// A block parameter is missing, out of range or contradicts another one.
+    InvalidParameter {
+        /// Name of the block.
+        block: String,
+        /// The parameter (or quantity) at fault, e.g. `gain` or `noise_temperature_k`.
+        parameter: String,
+        /// What is wrong with it.
+        reason: String,
+    },
+}
+
+impl fmt::Display for GainlineupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GainlineupError::Io { path, source } => {
+                write!(f, "failed to read {}: {}", path.display(), source)
+            }
+            GainlineupError::TomlParse {
+                path,
+                line,
+                column,
+                message,
+            } => {
+                write!(f, "failed to parse {}", path.display())?;
+                if let (Some(line), Some(column)) = (line, column) {
+                    write!(f, " at line {}, column {}", line, column)?;
+                }
+                write!(f, ": {}", message)
+            }
+            GainlineupError::TouchstoneFrequencyNotFound { path, frequency_hz } => write!(
+                f,
+                "Frequency {} Hz not found in touchstone file {}",
+                frequency_hz,
+                path.display()
+            ),
+            GainlineupError::MalformedTouchstone { path, message } => write!(
+                f,
+                "malformed touchstone file {}: {}",
+                path.display(),
+                message
+            ),
+            GainlineupError::Include { chain, source } => {
+                write!(f, "in included file ")?;
+                for (i, path) in chain.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " -> ")?;
+                    }
+                    write!(f, "{}", path.display())?;
+                }
+                write!(f, ": {}", source)
+            }
+            GainlineupError::InvalidParameter {
+                block,
+                parameter,
+                reason,
+            } => write!(f, "invalid {} for block '{}': {}", parameter, block, reason),
+        }
+    }
+}
+
+impl std::error::Error for GainlineupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GainlineupError::Io { source, .. } => Some(source),
+            GainlineupError::Include { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl GainlineupError {
+    /// A [`GainlineupError::TomlParse`] with the line and column resolved from `content`.
+    #[cfg(feature = "cli")]
+    pub(crate) fn toml_parse(path: PathBuf, content: &str, error: &toml::de::Error) -> Self {
+        let (line, column) = match error.span() {
+            Some(span) => {
+                let before = &content[..span.start.min(content.len())];
+                let line = before.matches('\n').count() + 1;
+                let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+                (Some(line), Some(column))
+            }
+            None => (None, None),
+        };
+        GainlineupError::TomlParse {
+            path,
+            line,
+            column,
+            message: error.message().trim_end().to_string(),
+        }
+    }
+
+    /// A [`GainlineupError::InvalidParameter`] for `block`.
+    pub(crate) fn invalid_parameter(
+        block: &str,
+        parameter: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        GainlineupError::InvalidParameter {
+            block: block.to_string(),
+            parameter: parameter.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Wrap an error from the included file `path`, extending the include chain.
+    #[cfg(feature = "cli")]
+    pub(crate) fn in_include(self, path: PathBuf) -> Self {
+        match self {
+            GainlineupError::Include { mut chain, source } => {
+                chain.insert(0, path);
+                GainlineupError::Include { chain, source }
+            }
+            other => GainlineupError::Include {
+                chain: vec![path],
+                source: Box::new(other),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn toml_parse_reports_line_and_column() {
+        let content = "a = 1\nb = = 2\n";
+        let error = toml::from_str::<toml::Table>(content).unwrap_err();
+        let err = GainlineupError::toml_parse(PathBuf::from("x.toml"), content, &error);
+        match &err {
+            GainlineupError::TomlParse { line, column, .. } => {
+                assert_eq!(*line, Some(2));
+                assert!(column.unwrap() > 1);
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(err
+            .to_string()
+            .starts_with("failed to parse x.toml at line 2"));
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn include_chain_nests_outermost_first() {
+        let err = GainlineupError::invalid_parameter("LNA", "gain", "missing")
+            .in_include(PathBuf::from("inner.toml"))
+            .in_include(PathBuf::from("outer.toml"));
+        match &err {
+            GainlineupError::Include { chain, source } => {
+                assert_eq!(
+                    chain,
+                    &vec![PathBuf::from("outer.toml"), PathBuf::from("inner.toml")]
+                );
+                assert!(matches!(**source, GainlineupError::InvalidParameter { .. }));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert_eq!(
+            err.to_string(),
+            "in included file outer.toml -> inner.toml: invalid gain for block 'LNA': missing"
+        );
+    }
+
+    #[test]
+    fn touchstone_frequency_not_found_message() {
+        let err = GainlineupError::TouchstoneFrequencyNotFound {
+            path: PathBuf::from("ntwk3.s2p"),
+            frequency_hz: 11.0e9,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Frequency 11000000000 Hz not found in touchstone file ntwk3.s2p"
+        );
+    }
+}
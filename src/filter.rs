@@ -0,0 +1,175 @@
+use std::fmt;
+
+/// Passband and stopband of a band-pass filter stage.
+///
+/// Inside the passband (`center ± bandwidth / 2`) the filter only has its insertion loss
+/// (the block's `gain_db`). Outside it, extra rejection is linearly interpolated from the
+/// `rejection` table of `(offset from center in Hz, rejection in dB)` pairs, ramping from
+/// 0 dB at the passband edge and holding the last entry beyond the table. The table is
+/// symmetric about the center frequency.
+///
+/// The noise bandwidth sets the bandwidth of the signal and noise downstream; it defaults
+/// to the passband bandwidth.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::Filter;
+///
+/// // 10.7 MHz IF filter, 200 kHz wide
+/// let filter = Filter::new(10.7e6, 200.0e3).with_rejection(vec![(200.0e3, 30.0), (400.0e3, 60.0)]);
+/// assert_eq!(filter.rejection_db(10.75e6), 0.0);
+/// assert!((filter.rejection_db(10.9e6) - 30.0).abs() < 1e-9);
+/// assert!((filter.rejection_db(10.4e6) - 45.0).abs() < 1e-9);
+/// assert_eq!(filter.rejection_db(20.0e6), 60.0);
+/// ```
+#[doc(alias = "BPF")]
+#[doc(alias = "bandpass")]
+#[doc(alias = "noise bandwidth")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filter {
+    /// Center frequency of the passband in Hz.
+    pub center_frequency_hz: f64,
+    /// Passband bandwidth in Hz.
+    pub bandwidth_hz: f64,
+    /// Equivalent noise bandwidth in Hz, defaults to `bandwidth_hz` if `None`.
+    pub noise_bandwidth_hz: Option<f64>,
+    /// Stopband rejection as `(offset from center in Hz, rejection in dB)` pairs.
+    pub rejection: Vec<(f64, f64)>,
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Filter {{ center_frequency: {} Hz, bandwidth: {} Hz, noise_bandwidth: {} Hz }}",
+            self.center_frequency_hz,
+            self.bandwidth_hz,
+            self.noise_bandwidth_hz()
+        )
+    }
+}
+
+impl Filter {
+    /// Create a filter with the given passband and no stopband table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::Filter;
+    ///
+    /// let filter = Filter::new(70.0e6, 36.0e6);
+    /// assert_eq!(filter.noise_bandwidth_hz(), 36.0e6);
+    /// ```
+    #[must_use]
+    pub fn new(center_frequency_hz: f64, bandwidth_hz: f64) -> Self {
+        Self {
+            center_frequency_hz,
+            bandwidth_hz,
+            noise_bandwidth_hz: None,
+            rejection: vec![],
+        }
+    }
+
+    /// Set the equivalent noise bandwidth in Hz.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::Filter;
+    ///
+    /// let filter = Filter::new(70.0e6, 36.0e6).with_noise_bandwidth(40.0e6);
+    /// assert_eq!(filter.noise_bandwidth_hz(), 40.0e6);
+    /// ```
+    #[must_use]
+    pub fn with_noise_bandwidth(mut self, noise_bandwidth_hz: f64) -> Self {
+        self.noise_bandwidth_hz = Some(noise_bandwidth_hz);
+        self
+    }
+
+    /// Set the stopband rejection table (sorted by offset internally).
+    #[must_use]
+    pub fn with_rejection(mut self, mut rejection: Vec<(f64, f64)>) -> Self {
+        rejection.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self.rejection = rejection;
+        self
+    }
+
+    /// Equivalent noise bandwidth in Hz.
+    #[must_use]
+    pub fn noise_bandwidth_hz(&self) -> f64 {
+        self.noise_bandwidth_hz.unwrap_or(self.bandwidth_hz)
+    }
+
+    /// `true` if `frequency_hz` is inside the passband.
+    #[must_use]
+    pub fn in_passband(&self, frequency_hz: f64) -> bool {
+        (frequency_hz - self.center_frequency_hz).abs() <= self.bandwidth_hz / 2.0
+    }
+
+    /// Rejection in dB (positive, relative to the passband) at `frequency_hz`.
+    #[must_use]
+    pub fn rejection_db(&self, frequency_hz: f64) -> f64 {
+        if self.in_passband(frequency_hz) {
+            return 0.0;
+        }
+        let offset_hz = (frequency_hz - self.center_frequency_hz).abs();
+
+        // ramp from 0 dB at the passband edge through the table, hold the last entry
+        let mut previous = (self.bandwidth_hz / 2.0, 0.0);
+        for &(table_offset_hz, rejection_db) in &self.rejection {
+            if offset_hz <= table_offset_hz {
+                if table_offset_hz <= previous.0 {
+                    return rejection_db;
+                }
+                let t = (offset_hz - previous.0) / (table_offset_hz - previous.0);
+                return previous.1 + (rejection_db - previous.1) * t;
+            }
+            previous = (table_offset_hz, rejection_db);
+        }
+        previous.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passband_has_no_rejection() {
+        let filter = Filter::new(1.0e9, 100.0e6).with_rejection(vec![(100.0e6, 40.0)]);
+        assert_eq!(filter.rejection_db(1.0e9), 0.0);
+        assert_eq!(filter.rejection_db(1.05e9), 0.0);
+        assert_eq!(filter.rejection_db(0.95e9), 0.0);
+    }
+
+    #[test]
+    fn rejection_ramps_from_passband_edge() {
+        let filter = Filter::new(1.0e9, 100.0e6).with_rejection(vec![(150.0e6, 40.0)]);
+        // halfway between the 50 MHz edge and the 150 MHz table point
+        assert!((filter.rejection_db(1.1e9) - 20.0).abs() < 1e-9);
+        assert!((filter.rejection_db(0.9e9) - 20.0).abs() < 1e-9);
+        assert_eq!(filter.rejection_db(2.0e9), 40.0);
+    }
+
+    #[test]
+    fn rejection_table_is_sorted() {
+        let filter =
+            Filter::new(0.0, 2.0).with_rejection(vec![(10.0, 60.0), (3.0, 20.0), (5.0, 40.0)]);
+        assert_eq!(filter.rejection[0], (3.0, 20.0));
+        assert!((filter.rejection_db(4.0) - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_table_has_no_stopband() {
+        let filter = Filter::new(1.0e9, 10.0e6);
+        assert_eq!(filter.rejection_db(5.0e9), 0.0);
+        assert!(!filter.in_passband(5.0e9));
+    }
+
+    #[test]
+    fn display_filter() {
+        let s = format!("{}", Filter::new(70.0e6, 36.0e6));
+        assert!(s.contains("noise_bandwidth: 36000000 Hz"));
+    }
+}
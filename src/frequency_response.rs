@@ -0,0 +1,419 @@
+use std::fmt;
+
+use crate::node::SignalNode;
+
+/// Block parameters at a single frequency, one row of a [`FrequencyResponse`] table.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::FrequencyPoint;
+///
+/// let point = FrequencyPoint {
+///     frequency_hz: 11.7e9,
+///     gain_db: 55.0,
+///     noise_figure_db: 0.8,
+///     output_p1db_dbm: Some(10.0),
+///     output_ip3_dbm: Some(20.0),
+/// };
+/// assert_eq!(point.gain_db, 55.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrequencyPoint {
+    /// Frequency of this point in Hz.
+    pub frequency_hz: f64,
+    /// Small-signal gain in dB at this frequency.
+    pub gain_db: f64,
+    /// Noise figure in dB at this frequency.
+    pub noise_figure_db: f64,
+    /// Output-referred 1 dB compression point in dBm at this frequency, if applicable.
+    pub output_p1db_dbm: Option<f64>,
+    /// Output-referred third-order intercept point in dBm at this frequency, if applicable.
+    pub output_ip3_dbm: Option<f64>,
+}
+
+impl fmt::Display for FrequencyPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FrequencyPoint {{ frequency: {} Hz, gain: {} dB, noise_figure: {} dB",
+            self.frequency_hz, self.gain_db, self.noise_figure_db
+        )?;
+        if let Some(output_p1db) = self.output_p1db_dbm {
+            write!(f, ", output_p1db: {} dBm", output_p1db)?;
+        }
+        if let Some(output_ip3) = self.output_ip3_dbm {
+            write!(f, ", output_ip3: {} dBm", output_ip3)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+/// Tabulated gain, noise figure, P1dB and OIP3 of a block over frequency.
+///
+/// Values between table points are linearly interpolated (in dB/dBm versus Hz).
+/// Outside the table the nearest edge point is held.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{FrequencyPoint, FrequencyResponse};
+///
+/// let response = FrequencyResponse::new(vec![
+///     FrequencyPoint {
+///         frequency_hz: 10.0e9,
+///         gain_db: 20.0,
+///         noise_figure_db: 1.0,
+///         output_p1db_dbm: None,
+///         output_ip3_dbm: None,
+///     },
+///     FrequencyPoint {
+///         frequency_hz: 12.0e9,
+///         gain_db: 16.0,
+///         noise_figure_db: 2.0,
+///         output_p1db_dbm: None,
+///         output_ip3_dbm: None,
+///     },
+/// ]);
+/// let mid = response.at(11.0e9).unwrap();
+/// assert!((mid.gain_db - 18.0).abs() < 1e-9);
+/// assert!((mid.noise_figure_db - 1.5).abs() < 1e-9);
+/// ```
+#[doc(alias = "wideband")]
+#[doc(alias = "frequency dependent")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrequencyResponse {
+    points: Vec<FrequencyPoint>,
+}
+
+impl FrequencyResponse {
+    /// Create a frequency response from a table of points (sorted by frequency internally).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{FrequencyPoint, FrequencyResponse};
+    ///
+    /// let response = FrequencyResponse::new(vec![FrequencyPoint {
+    ///     frequency_hz: 1.0e9,
+    ///     gain_db: 10.0,
+    ///     noise_figure_db: 3.0,
+    ///     output_p1db_dbm: None,
+    ///     output_ip3_dbm: None,
+    /// }]);
+    /// assert_eq!(response.points().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn new(mut points: Vec<FrequencyPoint>) -> Self {
+        points.sort_by(|a, b| a.frequency_hz.total_cmp(&b.frequency_hz));
+        Self { points }
+    }
+
+    /// The table points, sorted by ascending frequency.
+    #[must_use]
+    pub fn points(&self) -> &[FrequencyPoint] {
+        &self.points
+    }
+
+    /// Lowest and highest frequency in the table in Hz, or `None` if the table is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{FrequencyPoint, FrequencyResponse};
+    ///
+    /// let point = |f: f64| FrequencyPoint {
+    ///     frequency_hz: f,
+    ///     gain_db: 10.0,
+    ///     noise_figure_db: 3.0,
+    ///     output_p1db_dbm: None,
+    ///     output_ip3_dbm: None,
+    /// };
+    /// let response = FrequencyResponse::new(vec![point(2.0e9), point(1.0e9)]);
+    /// assert_eq!(response.frequency_range_hz(), Some((1.0e9, 2.0e9)));
+    /// ```
+    #[must_use]
+    pub fn frequency_range_hz(&self) -> Option<(f64, f64)> {
+        let first = self.points.first()?;
+        let last = self.points.last()?;
+        Some((first.frequency_hz, last.frequency_hz))
+    }
+
+    /// Block parameters at `frequency_hz`, linearly interpolated between table points.
+    ///
+    /// Outside the table the nearest edge point is held. P1dB and OIP3 are only
+    /// interpolated when both neighbouring points define them, otherwise `None`.
+    /// Returns `None` if the table is empty.
+    #[must_use]
+    pub fn at(&self, frequency_hz: f64) -> Option<FrequencyPoint> {
+        let first = self.points.first()?;
+        let last = self.points.last()?;
+
+        if frequency_hz <= first.frequency_hz {
+            if frequency_hz < first.frequency_hz {
+                tracing::warn!(
+                    frequency_hz,
+                    table_start_hz = first.frequency_hz,
+                    "Frequency below frequency response table, holding first point"
+                );
+            }
+            return Some(FrequencyPoint {
+                frequency_hz,
+                ..first.clone()
+            });
+        }
+        if frequency_hz >= last.frequency_hz {
+            if frequency_hz > last.frequency_hz {
+                tracing::warn!(
+                    frequency_hz,
+                    table_stop_hz = last.frequency_hz,
+                    "Frequency above frequency response table, holding last point"
+                );
+            }
+            return Some(FrequencyPoint {
+                frequency_hz,
+                ..last.clone()
+            });
+        }
+
+        // first index with a frequency above the requested one; guaranteed to be in 1..len
+        let upper = self
+            .points
+            .partition_point(|point| point.frequency_hz <= frequency_hz);
+        let lo = &self.points[upper - 1];
+        let hi = &self.points[upper];
+        let t = (frequency_hz - lo.frequency_hz) / (hi.frequency_hz - lo.frequency_hz);
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        let lerp_option = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => Some(lerp(a, b)),
+            _ => None,
+        };
+
+        Some(FrequencyPoint {
+            frequency_hz,
+            gain_db: lerp(lo.gain_db, hi.gain_db),
+            noise_figure_db: lerp(lo.noise_figure_db, hi.noise_figure_db),
+            output_p1db_dbm: lerp_option(lo.output_p1db_dbm, hi.output_p1db_dbm),
+            output_ip3_dbm: lerp_option(lo.output_ip3_dbm, hi.output_ip3_dbm),
+        })
+    }
+}
+
+/// Result of cascading a lineup at every point of a frequency grid.
+///
+/// `nodes[i]` holds the [`SignalNode`] after each block when the input is at
+/// `frequencies_hz[i]`.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Input, Block, cascade_frequency_sweep, frequency_grid};
+///
+/// let input = Input::new(11.0e9, 1.0e6, -80.0, Some(290.0));
+/// let blocks = vec![Block {
+///     name: "LNA".to_string(),
+///     gain_db: 30.0,
+///     noise_figure_db: 1.0,
+///     output_p1db_dbm: None,
+///     output_ip3_dbm: None,
+///     ..Default::default()
+/// }];
+/// let sweep = cascade_frequency_sweep(&input, &blocks, &frequency_grid(10.7e9, 12.75e9, 5));
+/// assert_eq!(sweep.frequencies_hz.len(), 5);
+/// assert_eq!(sweep.nodes[0].len(), 1);
+/// ```
+#[doc(alias = "wideband")]
+#[doc(alias = "frequency sweep")]
+#[derive(Clone, Debug, Default)]
+pub struct FrequencySweep {
+    /// Input frequencies that were evaluated, in Hz.
+    pub frequencies_hz: Vec<f64>,
+    /// Cascade nodes at each input frequency (same order as `frequencies_hz`).
+    pub nodes: Vec<Vec<SignalNode>>,
+}
+
+impl FrequencySweep {
+    /// Final output node at each frequency, paired with the input frequency in Hz.
+    ///
+    /// Frequencies where the lineup is empty are skipped.
+    #[must_use]
+    pub fn outputs(&self) -> Vec<(f64, &SignalNode)> {
+        self.frequencies_hz
+            .iter()
+            .zip(self.nodes.iter())
+            .filter_map(|(&frequency_hz, nodes)| nodes.last().map(|node| (frequency_hz, node)))
+            .collect()
+    }
+
+    /// Highest cascaded noise figure at the output across the grid, as `(frequency_hz, nf_db)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Input, Block, FrequencyPoint, FrequencyResponse, cascade_frequency_sweep};
+    ///
+    /// let point = |f: f64, nf: f64| FrequencyPoint {
+    ///     frequency_hz: f,
+    ///     gain_db: 30.0,
+    ///     noise_figure_db: nf,
+    ///     output_p1db_dbm: None,
+    ///     output_ip3_dbm: None,
+    /// };
+    /// let lna = Block {
+    ///     name: "LNA".to_string(),
+    ///     frequency_response: Some(FrequencyResponse::new(vec![
+    ///         point(10.7e9, 0.7),
+    ///         point(12.75e9, 1.1),
+    ///     ])),
+    ///     ..Default::default()
+    /// };
+    /// let input = Input::new(11.0e9, 1.0e6, -80.0, Some(290.0));
+    /// let sweep = cascade_frequency_sweep(&input, &[lna], &[10.7e9, 11.7e9, 12.75e9]);
+    /// let (frequency_hz, nf_db) = sweep.worst_case_noise_figure().unwrap();
+    /// assert_eq!(frequency_hz, 12.75e9);
+    /// assert!((nf_db - 1.1).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn worst_case_noise_figure(&self) -> Option<(f64, f64)> {
+        self.outputs()
+            .into_iter()
+            .map(|(frequency_hz, node)| (frequency_hz, node.cumulative_noise_figure_db))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Lowest cascaded gain at the output across the grid, as `(frequency_hz, gain_db)`.
+    #[must_use]
+    pub fn minimum_gain(&self) -> Option<(f64, f64)> {
+        self.outputs()
+            .into_iter()
+            .map(|(frequency_hz, node)| (frequency_hz, node.cumulative_gain_db))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Peak-to-peak variation of the output cumulative gain across the grid in dB.
+    #[must_use]
+    pub fn gain_flatness_db(&self) -> Option<f64> {
+        let gains: Vec<f64> = self
+            .outputs()
+            .into_iter()
+            .map(|(_, node)| node.cumulative_gain_db)
+            .collect();
+        let max = gains.iter().copied().reduce(f64::max)?;
+        let min = gains.iter().copied().reduce(f64::min)?;
+        Some(max - min)
+    }
+}
+
+/// Evenly spaced frequency grid from `start_hz` to `stop_hz` inclusive.
+///
+/// Returns `[start_hz]` when `points` is 1 and an empty Vec when `points` is 0.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::frequency_grid;
+///
+/// let grid = frequency_grid(1.0e9, 2.0e9, 3);
+/// assert_eq!(grid, vec![1.0e9, 1.5e9, 2.0e9]);
+/// ```
+#[must_use]
+pub fn frequency_grid(start_hz: f64, stop_hz: f64, points: usize) -> Vec<f64> {
+    match points {
+        0 => vec![],
+        1 => vec![start_hz],
+        _ => {
+            let step = (stop_hz - start_hz) / (points - 1) as f64;
+            (0..points)
+                .map(|i| {
+                    if i == points - 1 {
+                        stop_hz
+                    } else {
+                        start_hz + step * i as f64
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(frequency_hz: f64, gain_db: f64, noise_figure_db: f64) -> FrequencyPoint {
+        FrequencyPoint {
+            frequency_hz,
+            gain_db,
+            noise_figure_db,
+            output_p1db_dbm: Some(10.0),
+            output_ip3_dbm: None,
+        }
+    }
+
+    #[test]
+    fn new_sorts_points() {
+        let response = FrequencyResponse::new(vec![point(3.0, 0.0, 0.0), point(1.0, 0.0, 0.0)]);
+        assert_eq!(response.points()[0].frequency_hz, 1.0);
+        assert_eq!(response.frequency_range_hz(), Some((1.0, 3.0)));
+    }
+
+    #[test]
+    fn at_interpolates_linearly() {
+        let response = FrequencyResponse::new(vec![
+            point(1.0e9, 10.0, 2.0),
+            point(2.0e9, 20.0, 4.0),
+            point(4.0e9, 0.0, 8.0),
+        ]);
+        let p = response.at(1.25e9).unwrap();
+        assert!((p.gain_db - 12.5).abs() < 1e-9);
+        assert!((p.noise_figure_db - 2.5).abs() < 1e-9);
+        assert_eq!(p.output_p1db_dbm, Some(10.0));
+        assert_eq!(p.output_ip3_dbm, None);
+
+        let p = response.at(3.0e9).unwrap();
+        assert!((p.gain_db - 10.0).abs() < 1e-9);
+        assert!((p.noise_figure_db - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn at_exact_point() {
+        let response =
+            FrequencyResponse::new(vec![point(1.0e9, 10.0, 2.0), point(2.0e9, 20.0, 4.0)]);
+        assert_eq!(response.at(2.0e9).unwrap().gain_db, 20.0);
+        assert_eq!(response.at(1.0e9).unwrap().gain_db, 10.0);
+    }
+
+    #[test]
+    fn at_holds_edges() {
+        let response =
+            FrequencyResponse::new(vec![point(1.0e9, 10.0, 2.0), point(2.0e9, 20.0, 4.0)]);
+        let below = response.at(0.5e9).unwrap();
+        assert_eq!(below.gain_db, 10.0);
+        assert_eq!(below.frequency_hz, 0.5e9);
+        assert_eq!(response.at(3.0e9).unwrap().gain_db, 20.0);
+    }
+
+    #[test]
+    fn at_empty_is_none() {
+        assert!(FrequencyResponse::default().at(1.0e9).is_none());
+        assert!(FrequencyResponse::default().frequency_range_hz().is_none());
+    }
+
+    #[test]
+    fn frequency_grid_endpoints() {
+        let grid = frequency_grid(10.7e9, 12.75e9, 42);
+        assert_eq!(grid.len(), 42);
+        assert_eq!(grid[0], 10.7e9);
+        assert_eq!(*grid.last().unwrap(), 12.75e9);
+        assert!(frequency_grid(1.0, 2.0, 0).is_empty());
+        assert_eq!(frequency_grid(1.0, 2.0, 1), vec![1.0]);
+    }
+
+    #[test]
+    fn display_frequency_point() {
+        let s = format!("{}", point(1.0e9, 10.0, 2.0));
+        assert!(s.contains("gain: 10 dB"));
+        assert!(s.contains("output_p1db: 10 dBm"));
+        assert!(!s.contains("output_ip3"));
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt;
+
+use crate::block::Block;
+use crate::constants;
+
+/// A harmonic of the signal at a node of the cascade.
+///
+/// `order` is the harmonic number of the stage that generated it. After a mixer the
+/// harmonics of earlier stages are translated by the LO like the signal, so they are no
+/// longer at a multiple of the signal frequency.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{cascade_vector_return_output, Block, Input};
+///
+/// // +10 dBm out with OH2 = +40 dBm: H2 = 2·10 − 40 = −20 dBm, 30 dBc
+/// let amp = Block::builder("Amp").gain_db(20.0).output_h2_intercept_dbm(40.0).build().unwrap();
+/// let node = cascade_vector_return_output(Input::new(1.0e9, 1.0e6, -10.0, None), vec![amp]);
+/// let h2 = &node.harmonics[0];
+/// assert_eq!((h2.order, h2.frequency_hz), (2, 2.0e9));
+/// assert!((h2.power_dbm - (-20.0)).abs() < 1e-9);
+/// assert_eq!(node.harmonic_dbc(2), Some(-30.0));
+/// ```
+#[doc(alias = "H2")]
+#[doc(alias = "H3")]
+#[doc(alias = "harmonic distortion")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Harmonic {
+    /// Harmonic number (2 for the second harmonic).
+    pub order: u32,
+    /// Frequency in Hz.
+    pub frequency_hz: f64,
+    /// Power in dBm.
+    pub power_dbm: f64,
+}
+
+impl fmt::Display for Harmonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Harmonic {{ order: {}, frequency: {} Hz, power: {} dBm }}",
+            self.order, self.frequency_hz, self.power_dbm
+        )
+    }
+}
+
+/// The harmonics at the output of `block`: the `previous` ones passed through it, plus
+/// the ones it generates for a carrier at `input_frequency_hz` and `input_power_dbm`.
+///
+/// Harmonics pass a block at its gain at their own frequency (its frequency response, if
+/// tabulated, less any filter rejection) and a mixer translates them like the signal.
+/// Harmonics of the same order and frequency from different stages add in voltage, in
+/// phase (the worst case), like the IM products behind the cascaded OIP2 and OIP3.
+pub(crate) fn cascade_harmonics(
+    previous: &[Harmonic],
+    block: &Block,
+    input_frequency_hz: f64,
+    input_power_dbm: f64,
+) -> Vec<Harmonic> {
+    let mut harmonics: Vec<Harmonic> = previous
+        .iter()
+        .map(|harmonic| Harmonic {
+            order: harmonic.order,
+            frequency_hz: block.output_frequency_hz(harmonic.frequency_hz),
+            power_dbm: harmonic.power_dbm + block.at_frequency(harmonic.frequency_hz).gain_db
+                - block.rejection_db(harmonic.frequency_hz),
+        })
+        .collect();
+
+    let output_frequency_hz = block.output_frequency_hz(input_frequency_hz);
+    for order in [2, 3] {
+        let Some(power_dbm) = block.harmonic_output_power_dbm(order, input_power_dbm) else {
+            continue;
+        };
+        let generated = Harmonic {
+            order,
+            frequency_hz: f64::from(order) * output_frequency_hz,
+            power_dbm,
+        };
+        tracing::trace!(
+            block = %block.name,
+            order,
+            frequency_hz = generated.frequency_hz,
+            power_dbm,
+            "Generated harmonic"
+        );
+        match harmonics.iter_mut().find(|harmonic| {
+            harmonic.order == order
+                && (harmonic.frequency_hz - generated.frequency_hz).abs()
+                    <= constants::FREQUENCY_TOLERANCE * generated.frequency_hz
+        }) {
+            Some(existing) => {
+                let amplitude = rfconversions::power::dbm_to_watts(existing.power_dbm).sqrt()
+                    + rfconversions::power::dbm_to_watts(power_dbm).sqrt();
+                existing.power_dbm = rfconversions::power::watts_to_dbm(amplitude * amplitude);
+            }
+            None => harmonics.push(generated),
+        }
+    }
+    harmonics.sort_by(|a, b| {
+        a.order
+            .cmp(&b.order)
+            .then(a.frequency_hz.total_cmp(&b.frequency_hz))
+    });
+    harmonics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Filter, FrequencyPoint, FrequencyResponse, Injection, Mixer, MixerNoiseFigure};
+
+    fn amp(name: &str, oh2_dbm: f64, oh3_dbm: f64) -> Block {
+        Block::builder(name)
+            .gain_db(20.0)
+            .output_h2_intercept_dbm(oh2_dbm)
+            .output_h3_intercept_dbm(oh3_dbm)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn stage_generates_second_and_third_harmonics() {
+        let harmonics = cascade_harmonics(&[], &amp("Amp", 40.0, 30.0), 1.0e9, -10.0);
+        assert_eq!(harmonics.len(), 2);
+        assert_eq!(harmonics[0].frequency_hz, 2.0e9);
+        assert!((harmonics[0].power_dbm - (-20.0)).abs() < 1e-9);
+        // 3·10 − 2·30
+        assert_eq!(harmonics[1].order, 3);
+        assert!((harmonics[1].power_dbm - (-30.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn same_frequency_adds_in_voltage() {
+        let first = cascade_harmonics(&[], &amp("First", 40.0, 30.0), 1.0e9, -30.0);
+        // a lossless block with the same OH2 adds an equal H2: +6 dB
+        let block = Block::builder("Second")
+            .output_h2_intercept_dbm(40.0)
+            .build()
+            .unwrap();
+        let second = cascade_harmonics(&first, &block, 1.0e9, -10.0);
+        assert_eq!(second.len(), 2);
+        assert!((second[0].power_dbm - (first[0].power_dbm + 6.0206)).abs() < 1e-3);
+        assert_eq!(second[1], first[1]);
+    }
+
+    #[test]
+    fn filter_rejects_and_response_shapes_harmonics() {
+        let previous = cascade_harmonics(&[], &amp("Amp", 40.0, 30.0), 1.0e9, -10.0);
+        let low_pass = Block::filter(
+            "Filter",
+            1.0,
+            Filter::new(1.0e9, 200.0e6).with_rejection(vec![(1.0e9, 40.0), (2.0e9, 60.0)]),
+        );
+        let filtered = cascade_harmonics(&previous, &low_pass, 1.0e9, 10.0);
+        assert!((filtered[0].power_dbm - (-20.0 - 1.0 - 40.0)).abs() < 1e-9);
+        assert!((filtered[1].power_dbm - (-30.0 - 1.0 - 60.0)).abs() < 1e-9);
+
+        let point = |frequency_hz: f64, gain_db: f64| FrequencyPoint {
+            frequency_hz,
+            gain_db,
+            noise_figure_db: 3.0,
+            output_p1db_dbm: None,
+            output_ip3_dbm: None,
+        };
+        let rolling_off = Block {
+            name: "Driver".to_string(),
+            frequency_response: Some(FrequencyResponse::new(vec![
+                point(1.0e9, 15.0),
+                point(3.0e9, 5.0),
+            ])),
+            ..Default::default()
+        };
+        let amplified = cascade_harmonics(&previous, &rolling_off, 1.0e9, 10.0);
+        assert!((amplified[0].power_dbm - (-20.0 + 10.0)).abs() < 1e-9);
+        assert!((amplified[1].power_dbm - (-30.0 + 5.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mixer_translates_harmonics() {
+        let previous = cascade_harmonics(&[], &amp("Amp", 40.0, 30.0), 1.0e9, -10.0);
+        let mixer = Block::mixer(
+            "Mixer",
+            -7.0,
+            MixerNoiseFigure::Ssb(7.0),
+            Mixer::new(0.9e9, Injection::LowSide),
+        );
+        let translated = cascade_harmonics(&previous, &mixer, 1.0e9, 10.0);
+        assert_eq!(translated[0].frequency_hz, 1.1e9);
+        assert!((translated[0].power_dbm - (-27.0)).abs() < 1e-9);
+        assert_eq!(translated[1].frequency_hz, 2.1e9);
+    }
+
+    #[test]
+    fn display_harmonic() {
+        let harmonic = Harmonic {
+            order: 2,
+            frequency_hz: 2.0e9,
+            power_dbm: -20.0,
+        };
+        assert_eq!(
+            format!("{}", harmonic),
+            "Harmonic { order: 2, frequency: 2000000000 Hz, power: -20 dBm }"
+        );
+    }
+}
@@ -1,9 +1,17 @@
 use std::default::Default;
 use std::fmt;
 
+use crate::adc;
+use crate::aliasing::{cascade_noise_filters, fold_noise};
 use crate::block::Block;
 use crate::constants;
-use crate::node::SignalNode;
+use crate::digital;
+use crate::harmonics::cascade_harmonics;
+use crate::node::{
+    update_linearity_percentages, update_noise_percentages, LinearityContribution,
+    NoiseContribution, SignalNode,
+};
+use crate::phase_noise::{combine_phase_noise, PhaseNoise};
 
 /// The input signal that enters the RF cascade.
 ///
@@ -22,6 +30,7 @@ use crate::node::SignalNode;
 ///     bandwidth_hz: 1.0e6,
 ///     power_dbm: -50.0,
 ///     noise_temperature_k: Some(270.0),
+///     ..Default::default()
 /// };
 /// ```
 #[doc(alias = "signal")]
@@ -36,6 +45,12 @@ pub struct Input {
     pub power_dbm: f64,
     /// Noise temperature of the input in Kelvin (defaults to 270 K if `None`).
     pub noise_temperature_k: Option<f64>,
+    /// Phase noise of the source, if characterized.
+    pub phase_noise: Option<PhaseNoise>,
+    /// Peak-to-average power ratio of the signal envelope in dB (0 for a CW tone).
+    #[doc(alias = "PAPR")]
+    #[doc(alias = "crest factor")]
+    pub papr_db: f64,
 }
 
 impl fmt::Display for Input {
@@ -56,6 +71,8 @@ impl Default for Input {
             // https://www.w8ji.com/cw_bandwidth_described.htm describes how CW signals are generally made, which require non-zero bandwidth
             power_dbm: 0.0, // placeholder value, you should change this (0 dBm)
             noise_temperature_k: None,
+            phase_noise: None,
+            papr_db: 0.0,
         }
     }
 }
@@ -84,10 +101,47 @@ impl Input {
             bandwidth_hz,
             power_dbm,
             noise_temperature_k,
+            phase_noise: None,
+            papr_db: 0.0,
         }
     }
 
-    /// Noise spectral density in dBm/Hz.
+    /// Set the phase noise of the source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Input, PhaseNoise};
+    ///
+    /// let input = Input::new(1.0e9, 1.0e6, -30.0, Some(290.0))
+    ///     .with_phase_noise(PhaseNoise::new(&[(1.0e3, -110.0), (1.0e6, -150.0)]));
+    /// assert!(input.phase_noise.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_phase_noise(mut self, phase_noise: PhaseNoise) -> Self {
+        self.phase_noise = Some(phase_noise);
+        self
+    }
+
+    /// Set the peak-to-average power ratio of the signal envelope in dB, which sets the
+    /// headroom an ADC needs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::Input;
+    ///
+    /// // OFDM signal
+    /// let input = Input::new(2.4e9, 20.0e6, -60.0, None).with_papr_db(10.0);
+    /// assert_eq!(input.papr_db, 10.0);
+    /// ```
+    #[must_use]
+    pub fn with_papr_db(mut self, papr_db: f64) -> Self {
+        self.papr_db = papr_db;
+        self
+    }
+
+        /// Noise spectral density in dBm/Hz.
     ///
     /// # Examples
     ///
@@ -162,6 +216,7 @@ impl Input {
     ///     noise_figure_db: 1.5,
     ///     output_p1db_dbm: None,
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let output = input.cascade_block(&lna);
     /// assert_eq!(output.signal_power_dbm, 0.0); // -30 + 30 = 0 dBm
@@ -169,6 +224,24 @@ impl Input {
     /// ```
     #[must_use]
     pub fn cascade_block(&self, block: &Block) -> SignalNode {
+        // evaluate frequency-dependent blocks at the frequency reaching them
+        let evaluated_block;
+        let block = if block.frequency_response.is_some() {
+            evaluated_block = block.at_frequency(self.frequency_hz);
+            &evaluated_block
+        } else {
+            block
+        };
+        // converters add jitter noise that depends on the signal reaching them
+        let converter_block;
+        let block = match adc::at_signal(block, self.power_dbm, self.frequency_hz) {
+            Some(evaluated) => {
+                converter_block = evaluated;
+                &converter_block
+            }
+            None => block,
+        };
+
         
         tracing::debug!("Start INPUT");
 
@@ -180,17 +253,14 @@ impl Input {
         let block_noise_temperature =
             rfconversions::noise::noise_temperature_from_noise_factor(block_noise_factor);
 
+        // filters narrow the bandwidth and reject signals outside their passband
+        let output_bandwidth_hz = block.output_bandwidth_hz(self.bandwidth_hz);
+        let bandwidth_ratio_db = 10.0 * (output_bandwidth_hz / self.bandwidth_hz).log10();
+        let rejection_db = block.rejection_db(self.frequency_hz);
+
         // handle compression point
-        let output_power_dbm_without_compression = self.power_dbm + block.gain_db;
-        let output_power_dbm = if let Some(output_p1db_dbm) = block.output_p1db_dbm {
-            if output_power_dbm_without_compression > output_p1db_dbm + 1.0 {
-                output_p1db_dbm + 1.0
-            } else {
-                output_power_dbm_without_compression
-            }
-        } else {
-            output_power_dbm_without_compression
-        };
+        let output_power_dbm_without_compression = self.power_dbm + block.gain_db - rejection_db;
+        let output_power_dbm = block.compress(output_power_dbm_without_compression);
 
         let stage_power_gain_db = output_power_dbm - self.power_dbm;
 
@@ -208,14 +278,23 @@ impl Input {
                 Some(270.0 + block_noise_temperature / stage_power_gain_linear)
             };
 
+        // the first stage's noise temperature is already input-referred
+        let mut noise_contributions = vec![
+            NoiseContribution::new("Input", self.noise_temperature_k.unwrap_or(270.0)),
+            NoiseContribution::new(&block.name, block_noise_temperature),
+        ];
+        update_noise_percentages(&mut noise_contributions);
+
         let input_noise_power = self.noise_power();
 
         
         tracing::debug!("Input Noise Power: (dBm) {}", input_noise_power);
 
-        let output_noise_power_from_input_dbm = input_noise_power + stage_power_gain_db;
+        // rejection only applies to the signal, and only the noise inside the output bandwidth gets through
+        let output_noise_power_from_input_dbm =
+            input_noise_power + stage_power_gain_db + rejection_db + bandwidth_ratio_db;
 
-        let output_noise_power_from_block_dbm = block.output_noise_power(self.bandwidth_hz);
+        let output_noise_power_from_block_dbm = block.output_noise_power(output_bandwidth_hz);
 
         
         tracing::debug!(
@@ -259,26 +338,64 @@ impl Input {
         // OIP3: first block in chain, just use block's OIP3
         let cumulative_oip3_dbm = block.output_ip3_dbm;
 
+        // OIP2: likewise the block's own
+        let cumulative_oip2_dbm = block.output_ip2_dbm;
+
+        let mut linearity_contributions = vec![LinearityContribution::new(
+            &block.name,
+            block.output_ip3_dbm,
+            block.output_p1db_dbm,
+        )];
+        update_linearity_percentages(&mut linearity_contributions);
+
         // SFDR calculation
-        let sfdr_db = cumulative_oip3_dbm.map(|oip3| {
-            let noise_floor_dbm =
-                -174.0 + 10.0 * self.bandwidth_hz.log10() + cumulative_noise_figure;
-            2.0 / 3.0 * (oip3 - noise_floor_dbm)
-        });
+        let noise_floor_dbm = -174.0 + 10.0 * output_bandwidth_hz.log10() + cumulative_noise_figure;
+        let sfdr_db = adc::limit_sfdr(
+            block,
+            cumulative_oip3_dbm.map(|oip3| 2.0 / 3.0 * (oip3 - noise_floor_dbm)),
+        );
+        let sfdr2_db = cumulative_oip2_dbm.map(|oip2| 0.5 * (oip2 - noise_floor_dbm));
 
-        SignalNode {
+        let mut node = SignalNode {
             name: output_node_name,
             signal_power_dbm: output_power_dbm,
-            signal_frequency_hz: self.frequency_hz,
-            signal_bandwidth_hz: self.bandwidth_hz,
+            signal_frequency_hz: block.output_frequency_hz(self.frequency_hz),
+            signal_bandwidth_hz: output_bandwidth_hz,
             cumulative_noise_figure_db: cumulative_noise_figure,
             cumulative_gain_db: stage_power_gain_db,
             cumulative_noise_temperature,
             noise_power_dbm: output_noise_power_at_output_dbm,
             cumulative_oip3_dbm,
             sfdr_db,
+            cumulative_oip2_dbm,
+            sfdr2_db,
             output_p1db_dbm: block.output_p1db_dbm,
-        }
+            p1db_limiting_stage: block.output_p1db_dbm.map(|_| block.name.clone()),
+            p1db_limiting_stage_dbm: block.output_p1db_dbm,
+            image_frequency_hz: block.image_frequency_hz(self.frequency_hz),
+            cumulative_phase_deg: block.phase_at(self.power_dbm),
+            cumulative_am_pm_deg: block.am_pm_phase_at(self.power_dbm),
+            cumulative_group_delay_s: block.group_delay_s.unwrap_or(0.0),
+            phase_noise: combine_phase_noise(self.phase_noise.as_ref(), block.lo_phase_noise()),
+            lo_phase_noise: block.lo_phase_noise().cloned(),
+            harmonics: cascade_harmonics(&[], block, self.frequency_hz, self.power_dbm),
+            array: None,
+            papr_db: self.papr_db,
+            adc_headroom_db: adc::headroom_db(block, self.power_dbm, self.papr_db),
+            noise_filters: cascade_noise_filters(
+                &[],
+                block,
+                output_noise_power_from_input_watts / total_noise_power_at_output_watts,
+            ),
+            alias_snr_degradation_db: None,
+            sample_rate_hz: digital::sample_rate_hz(block, None),
+            full_scale_dbm: digital::full_scale_dbm(block, None),
+            noise_contributions,
+            linearity_contributions,
+        };
+        // converters fold the out-of-band noise reaching them into the signal band
+        fold_noise(&mut node, block, &[], self.noise_spectral_density(), None);
+        node
     }
 }
 
@@ -295,6 +412,7 @@ mod tests {
             noise_figure_db: 10.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let signal_node = input.cascade_block(&block);
         assert_eq!(signal_node.name, "Test Block Output");
@@ -330,6 +448,8 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: None,
+            compression_model: crate::CompressionModel::HardClip,
+            ..Default::default()
         };
 
         let signal_node = input.cascade_block(&block);
@@ -436,6 +556,7 @@ mod tests {
             noise_figure_db: 2.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
 
         let node_290 = input_290.cascade_block(&block);
@@ -458,11 +579,15 @@ mod tests {
             noise_figure_db: 2.0,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: Some(25.0),
+            ..Default::default()
         };
         let node = input.cascade_block(&block);
 
         // OIP3 should pass through from block
         assert_eq!(node.cumulative_oip3_dbm, Some(25.0));
+        // so does P1dB, with this block as the limit
+        assert_eq!(node.output_p1db_dbm, Some(10.0));
+        assert_eq!(node.p1db_limiting_stage.as_deref(), Some("LNA"));
         // SFDR should be calculated
         assert!(node.sfdr_db.is_some());
         assert!(node.sfdr_db.unwrap() > 0.0);
@@ -477,11 +602,51 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let node = input.cascade_block(&block);
 
         assert_eq!(node.cumulative_oip3_dbm, None);
         assert_eq!(node.sfdr_db, None);
+        assert_eq!(node.cumulative_oip2_dbm, None);
+        assert_eq!(node.sfdr2_db, None);
+    }
+
+    #[test]
+    fn test_cascade_block_noise_contributions() {
+        let input = Input::new(1.0e9, 1.0e6, -30.0, None);
+        let block = Block {
+            name: "LNA".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 3.0103,
+            ..Default::default()
+        };
+        let node = input.cascade_block(&block);
+
+        // default 270 K source and a 290 K first stage, not divided by its own gain
+        let contributions = &node.noise_contributions;
+        assert_eq!(contributions[0].name, "Input");
+        assert_eq!(contributions[0].noise_temperature_k, 270.0);
+        assert_eq!(contributions[1].name, "LNA");
+        assert!((contributions[1].noise_temperature_k - 290.0).abs() < 0.01);
+        assert!((contributions[1].percent - 51.79).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_cascade_block_with_ip2() {
+        let input = Input::new(1.0e9, 1.0e6, -30.0, Some(290.0));
+        let block = Block {
+            name: "LNA".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 2.0,
+            output_ip2_dbm: Some(50.0),
+            ..Default::default()
+        };
+        let node = input.cascade_block(&block);
+
+        assert_eq!(node.cumulative_oip2_dbm, Some(50.0));
+        let noise_floor = -174.0 + 60.0 + node.cumulative_noise_figure_db;
+        assert!((node.sfdr2_db.unwrap() - 0.5 * (50.0 - noise_floor)).abs() < 1e-9);
     }
 
     #[test]
@@ -494,6 +659,7 @@ mod tests {
             noise_figure_db: 10.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let node = input.cascade_block(&atten);
 
@@ -502,4 +668,35 @@ mod tests {
         assert!((node.cumulative_gain_db - (-10.0)).abs() < 0.01);
         assert!((node.cumulative_noise_figure_db - 10.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_cascade_block_mixer_first() {
+        let input = Input::new(11.7e9, 1.0e6, -60.0, Some(290.0));
+        let mixer = Block::mixer(
+            "Mixer",
+            -6.0,
+            crate::MixerNoiseFigure::Dsb(5.0),
+            crate::Mixer::new(10.6e9, crate::Injection::LowSide),
+        );
+        let node = input.cascade_block(&mixer);
+        assert!((node.signal_frequency_hz - 1.1e9).abs() < 1e-3);
+        assert!((node.image_frequency_hz.unwrap() - 9.5e9).abs() < 1e-3);
+        // DSB 5 dB is an SSB noise figure of ~8 dB
+        assert!((node.cumulative_noise_figure_db - 8.01).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_cascade_block_filter_first() {
+        let input = Input::new(1.0e9, 10.0e6, -60.0, Some(290.0));
+        let filter = Block::filter("Preselector", 3.0, crate::Filter::new(1.0e9, 1.0e6));
+        let node = input.cascade_block(&filter);
+        assert_eq!(node.signal_bandwidth_hz, 1.0e6);
+        assert_eq!(node.signal_power_dbm, -63.0);
+        // passive at 290 K: the output noise is kTB in the filter bandwidth
+        assert!(
+            (node.noise_power_dbm - (-113.98)).abs() < 0.01,
+            "got {}",
+            node.noise_power_dbm
+        );
+    }
 }
@@ -0,0 +1,313 @@
+use std::fmt;
+
+use crate::constants;
+
+/// One S-parameter sample: magnitude in dB (20·log10|S|) and phase in degrees.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::SParameterPoint;
+///
+/// let s21 = SParameterPoint { frequency_hz: 1.0e9, magnitude_db: -3.0, phase_deg: -45.0 };
+/// assert!((s21.magnitude_linear() - 0.708).abs() < 1e-3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SParameterPoint {
+    /// Frequency of the sample in Hz.
+    pub frequency_hz: f64,
+    /// Magnitude in dB.
+    pub magnitude_db: f64,
+    /// Phase in degrees.
+    pub phase_deg: f64,
+}
+
+impl fmt::Display for SParameterPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SParameterPoint {{ frequency: {} Hz, magnitude: {} dB, phase: {} deg }}",
+            self.frequency_hz, self.magnitude_db, self.phase_deg
+        )
+    }
+}
+
+impl SParameterPoint {
+    /// Linear (voltage) magnitude `|S|`.
+    #[must_use]
+    pub fn magnitude_linear(&self) -> f64 {
+        10.0_f64.powf(self.magnitude_db / 20.0)
+    }
+}
+
+/// How Touchstone data is interpolated between the frequencies in the file.
+///
+/// Phase is unwrapped before interpolating, so a wrap from −180° to +180° between two
+/// points does not sweep through 0°. Nothing is extrapolated: frequencies outside the
+/// file's range give `None`.
+///
+/// | Method     | Magnitude                      | Phase               |
+/// |------------|--------------------------------|---------------------|
+/// | `Linear`   | linear in `\|S\|`              | linear              |
+/// | `DbLinear` | linear in dB                   | linear              |
+/// | `Cubic`    | natural cubic spline in dB     | natural cubic spline |
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Interpolation, SParameterPoint};
+///
+/// let points = [
+///     SParameterPoint { frequency_hz: 1.0e9, magnitude_db: 0.0, phase_deg: 0.0 },
+///     SParameterPoint { frequency_hz: 2.0e9, magnitude_db: -20.0, phase_deg: -90.0 },
+/// ];
+/// let mid = Interpolation::DbLinear.at(&points, 1.5e9).unwrap();
+/// assert!((mid.magnitude_db - (-10.0)).abs() < 1e-9);
+/// assert!((mid.phase_deg - (-45.0)).abs() < 1e-9);
+///
+/// // |S| halfway between 1.0 and 0.1 is 0.55
+/// let mid = Interpolation::Linear.at(&points, 1.5e9).unwrap();
+/// assert!((mid.magnitude_linear() - 0.55).abs() < 1e-9);
+///
+/// assert!(Interpolation::Linear.at(&points, 3.0e9).is_none());
+/// ```
+#[doc(alias = "interpolate")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Linear in magnitude and phase.
+    #[default]
+    Linear,
+    /// Linear in dB magnitude and phase.
+    DbLinear,
+    /// Natural cubic spline through dB magnitude and phase.
+    Cubic,
+}
+
+impl fmt::Display for Interpolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Interpolation::Linear => write!(f, "linear"),
+            Interpolation::DbLinear => write!(f, "dB-linear"),
+            Interpolation::Cubic => write!(f, "cubic"),
+        }
+    }
+}
+
+/// Where a frequency falls in a table sorted by frequency.
+pub(crate) enum Bracket {
+    /// On the entry at this index.
+    At(usize),
+    /// Between the entries `lower` and `lower + 1`, a fraction `t` of the way.
+    Between { lower: usize, t: f64 },
+}
+
+/// Bracket `frequency_hz` in `frequencies` (sorted), or `None` if it is outside their range.
+pub(crate) fn bracket(frequencies: &[f64], frequency_hz: f64) -> Option<Bracket> {
+    let first = *frequencies.first()?;
+    let last = *frequencies.last()?;
+    let tolerance_hz = constants::FREQUENCY_TOLERANCE * first.abs().max(last.abs());
+    if frequency_hz < first - tolerance_hz || frequency_hz > last + tolerance_hz {
+        return None;
+    }
+    if let Some(index) = frequencies
+        .iter()
+        .position(|&f| (f - frequency_hz).abs() <= tolerance_hz)
+    {
+        return Some(Bracket::At(index));
+    }
+    // first index with a frequency above the requested one; in 1..len after the checks above
+    let upper = frequencies.partition_point(|&f| f <= frequency_hz);
+    let lower = upper - 1;
+    let t = (frequency_hz - frequencies[lower]) / (frequencies[upper] - frequencies[lower]);
+    Some(Bracket::Between { lower, t })
+}
+
+impl Interpolation {
+    /// The S-parameter at `frequency_hz`, interpolated from `points` (sorted by frequency).
+    ///
+    /// Returns `None` if `points` is empty or `frequency_hz` is outside its range.
+    #[must_use]
+    pub fn at(&self, points: &[SParameterPoint], frequency_hz: f64) -> Option<SParameterPoint> {
+        let frequencies: Vec<f64> = points.iter().map(|point| point.frequency_hz).collect();
+        let (lo, t) = match bracket(&frequencies, frequency_hz)? {
+            Bracket::At(index) => {
+                return Some(SParameterPoint {
+                    frequency_hz,
+                    ..points[index]
+                })
+            }
+            Bracket::Between { lower, t } => (lower, t),
+        };
+        let upper = lo + 1;
+        let phases = unwrap_phase_deg(points);
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        let (magnitude_db, phase_deg) = match self {
+            Interpolation::Linear => {
+                let magnitude = lerp(
+                    points[lo].magnitude_linear(),
+                    points[upper].magnitude_linear(),
+                );
+                (20.0 * magnitude.log10(), lerp(phases[lo], phases[upper]))
+            }
+            Interpolation::DbLinear => (
+                lerp(points[lo].magnitude_db, points[upper].magnitude_db),
+                lerp(phases[lo], phases[upper]),
+            ),
+            Interpolation::Cubic => {
+                let magnitudes: Vec<f64> = points.iter().map(|point| point.magnitude_db).collect();
+                (
+                    natural_cubic_spline(&frequencies, &magnitudes, frequency_hz),
+                    natural_cubic_spline(&frequencies, &phases, frequency_hz),
+                )
+            }
+        };
+
+        Some(SParameterPoint {
+            frequency_hz,
+            magnitude_db,
+            phase_deg: wrap_phase_deg(phase_deg),
+        })
+    }
+}
+
+/// Phases of `points` with 360° jumps removed.
+fn unwrap_phase_deg(points: &[SParameterPoint]) -> Vec<f64> {
+    let mut unwrapped: Vec<f64> = Vec::with_capacity(points.len());
+    for point in points {
+        let phase = match unwrapped.last() {
+            Some(&previous) => previous + wrap_phase_deg(point.phase_deg - previous),
+            None => point.phase_deg,
+        };
+        unwrapped.push(phase);
+    }
+    unwrapped
+}
+
+/// Wrap a phase into (−180°, 180°].
+fn wrap_phase_deg(phase_deg: f64) -> f64 {
+    let wrapped = (phase_deg + 180.0).rem_euclid(360.0) - 180.0;
+    if wrapped == -180.0 {
+        180.0
+    } else {
+        wrapped
+    }
+}
+
+/// Natural cubic spline through `(xs, ys)` evaluated at `x` (within the range of `xs`).
+fn natural_cubic_spline(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    let n = xs.len();
+    if n < 3 {
+        // a natural spline through two points is the straight line
+        let t = (x - xs[0]) / (xs[n - 1] - xs[0]);
+        return ys[0] + (ys[n - 1] - ys[0]) * t;
+    }
+
+    // second derivatives from the tridiagonal system (Thomas algorithm)
+    let mut second = vec![0.0; n];
+    let mut c_prime = vec![0.0; n];
+    let mut d_prime = vec![0.0; n];
+    for i in 1..n - 1 {
+        let h0 = xs[i] - xs[i - 1];
+        let h1 = xs[i + 1] - xs[i];
+        let a = h0;
+        let b = 2.0 * (h0 + h1);
+        let c = h1;
+        let d = 6.0 * ((ys[i + 1] - ys[i]) / h1 - (ys[i] - ys[i - 1]) / h0);
+        let denominator = b - a * c_prime[i - 1];
+        c_prime[i] = c / denominator;
+        d_prime[i] = (d - a * d_prime[i - 1]) / denominator;
+    }
+    for i in (1..n - 1).rev() {
+        second[i] = d_prime[i] - c_prime[i] * second[i + 1];
+    }
+
+    let upper = xs.partition_point(|&xi| xi <= x).clamp(1, n - 1);
+    let lo = upper - 1;
+    let h = xs[upper] - xs[lo];
+    let a = (xs[upper] - x) / h;
+    let b = (x - xs[lo]) / h;
+    a * ys[lo]
+        + b * ys[upper]
+        + ((a * a * a - a) * second[lo] + (b * b * b - b) * second[upper]) * h * h / 6.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(frequency_hz: f64, magnitude_db: f64, phase_deg: f64) -> SParameterPoint {
+        SParameterPoint {
+            frequency_hz,
+            magnitude_db,
+            phase_deg,
+        }
+    }
+
+    const METHODS: [Interpolation; 3] = [
+        Interpolation::Linear,
+        Interpolation::DbLinear,
+        Interpolation::Cubic,
+    ];
+
+    #[test]
+    fn exact_points_are_returned_unchanged() {
+        let points = [
+            point(1.0, -1.0, 10.0),
+            point(2.0, -2.0, 20.0),
+            point(3.0, -4.0, 30.0),
+        ];
+        for method in METHODS {
+            let p = method.at(&points, 2.0).unwrap();
+            assert_eq!(p.magnitude_db, -2.0, "{}", method);
+            assert_eq!(p.phase_deg, 20.0, "{}", method);
+        }
+    }
+
+    #[test]
+    fn outside_range_is_none() {
+        let points = [point(1.0e9, 0.0, 0.0), point(2.0e9, 0.0, 0.0)];
+        for method in METHODS {
+            assert!(method.at(&points, 0.5e9).is_none());
+            assert!(method.at(&points, 2.5e9).is_none());
+            assert!(method.at(&[], 1.0e9).is_none());
+        }
+        // float noise on the edges still counts as inside
+        assert!(Interpolation::Linear
+            .at(&points, 2.0e9 * (1.0 + 1e-12))
+            .is_some());
+    }
+
+    #[test]
+    fn phase_is_unwrapped() {
+        let points = [point(1.0, 0.0, 170.0), point(2.0, 0.0, -170.0)];
+        let mid = Interpolation::DbLinear.at(&points, 1.5).unwrap();
+        assert!((mid.phase_deg - 180.0).abs() < 1e-9, "{}", mid.phase_deg);
+        let quarter = Interpolation::DbLinear.at(&points, 1.75).unwrap();
+        assert!((quarter.phase_deg - (-175.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cubic_reproduces_a_straight_line_and_is_smooth() {
+        let line: Vec<SParameterPoint> = (0..5)
+            .map(|i| point(i as f64, -2.0 * i as f64, 0.0))
+            .collect();
+        let p = Interpolation::Cubic.at(&line, 2.5).unwrap();
+        assert!((p.magnitude_db - (-5.0)).abs() < 1e-9);
+
+        // a parabola: the spline lands much closer than the chord
+        let parabola: Vec<SParameterPoint> = (0..6)
+            .map(|i| point(i as f64, -((i * i) as f64), 0.0))
+            .collect();
+        let cubic = Interpolation::Cubic.at(&parabola, 2.5).unwrap();
+        let linear = Interpolation::DbLinear.at(&parabola, 2.5).unwrap();
+        assert!((cubic.magnitude_db - (-6.25)).abs() < (linear.magnitude_db - (-6.25)).abs());
+        assert!((cubic.magnitude_db - (-6.25)).abs() < 0.05);
+    }
+
+    #[test]
+    fn display_interpolation() {
+        assert_eq!(format!("{}", Interpolation::DbLinear), "dB-linear");
+        assert!(format!("{}", point(1.0, -3.0, 45.0)).contains("magnitude: -3 dB"));
+    }
+}
@@ -20,23 +20,43 @@
 //!         noise_figure_db: 1.5,
 //!         output_p1db_dbm: None,
 //!         output_ip3_dbm: None,
+//!         ..Default::default()
 //!     },
 //! ];
 //! let output = cascade_vector_return_output(input, blocks);
 //! assert_eq!(output.signal_power_dbm, 0.0);
 //! ```
 
+mod adc;
+mod aliasing;
+mod array;
 mod block;
+mod branching;
 
 /// Command-line interface for the gainlineup tool.
 #[cfg(feature = "cli")]
 #[allow(missing_docs)]
 pub mod cli;
+mod combiner;
+mod compression;
 mod constants;
+mod digital;
+mod error;
 mod file_operations;
+mod filter;
+mod frequency_response;
+mod harmonics;
 mod input;
+mod interpolation;
+mod lineup;
+mod mixer;
 mod node;
+mod noise_parameters;
 mod open;
+mod phase_noise;
+mod sparameters;
+mod touchstone_file;
+mod validation;
 
 #[cfg(feature = "plot")]
 mod plot;
@@ -44,9 +64,30 @@ mod plot;
 mod amplifier_model;
 
 pub use amplifier_model::{AmplifierModel, AmplifierModelBuilder, AmplifierPoint};
-pub use block::{Block, Imd3Point};
+pub use adc::Adc;
+pub use array::{ArrayGain, ChannelErrors, PhasedArray};
+pub use block::{Block, BlockBuilder, BlockKind, Imd2Point, Imd3Point};
+pub use branching::{Branch, BranchOutput, LineupTree, Stage};
+pub use combiner::{Combiner, Splitter};
+pub use compression::CompressionModel;
+pub use digital::DigitalStage;
+pub use error::GainlineupError;
+pub use filter::Filter;
+pub use frequency_response::{frequency_grid, FrequencyPoint, FrequencyResponse, FrequencySweep};
+pub use harmonics::Harmonic;
 pub use input::Input;
-pub use node::{DynamicRange, SignalNode};
+pub use interpolation::{Interpolation, SParameterPoint};
+pub use lineup::Lineup;
+pub use mixer::{Injection, Mixer, MixerNoiseFigure};
+pub use node::{DynamicRange, LinearityContribution, NoiseContribution, SignalNode};
+pub use noise_parameters::NoiseParameters;
+pub use phase_noise::{PhaseNoise, PhaseNoisePoint};
+pub use sparameters::{
+    cascade_s_parameters, mismatch_interfaces, reflection_coefficient_from_vswr,
+    vswr_from_reflection_coefficient, Complex, MismatchInterface, SParameters,
+};
+pub use touchstone_file::TouchstoneNetwork;
+pub use validation::{validate_lineup, Diagnostic, Severity};
 
 /// Cascade a vector of blocks and return only the final output [`SignalNode`].
 ///
@@ -63,6 +104,7 @@ pub use node::{DynamicRange, SignalNode};
 ///         noise_figure_db: 1.5,
 ///         output_p1db_dbm: None,
 ///         output_ip3_dbm: None,
+///         ..Default::default()
 ///     },
 ///     Block {
 ///         name: "Attenuator".to_string(),
@@ -70,6 +112,7 @@ pub use node::{DynamicRange, SignalNode};
 ///         noise_figure_db: 6.0,
 ///         output_p1db_dbm: None,
 ///         output_ip3_dbm: None,
+///         ..Default::default()
 ///     },
 /// ];
 /// let output = cascade_vector_return_output(input, blocks);
@@ -128,6 +171,7 @@ pub fn cascade_vector_return_output(input: Input, blocks: Vec<Block>) -> SignalN
 ///         noise_figure_db: 1.5,
 ///         output_p1db_dbm: None,
 ///         output_ip3_dbm: None,
+///         ..Default::default()
 ///     },
 ///     Block {
 ///         name: "Filter".to_string(),
@@ -135,6 +179,7 @@ pub fn cascade_vector_return_output(input: Input, blocks: Vec<Block>) -> SignalN
 ///         noise_figure_db: 3.0,
 ///         output_p1db_dbm: None,
 ///         output_ip3_dbm: None,
+///         ..Default::default()
 ///     },
 /// ];
 /// let nodes = cascade_vector_return_vector(input, blocks);
@@ -174,6 +219,120 @@ pub fn cascade_vector_return_vector(input: Input, blocks: Vec<Block>) -> Vec<Sig
     node_vector
 }
 
+/// Cascade a vector of blocks with the mismatch between adjacent blocks included in the gain.
+///
+/// Each block is represented by its [`Block::s_parameters`] (e.g. from a Touchstone file) or
+/// by a unilateral two-port built from its gain and VSWR specs, and the two-ports are
+/// cascaded with their reflections. Each stage's gain then becomes its share of the cascaded
+/// `|S21|²`, so the output node's gain is the lineup's transducer gain. Noise figure and
+/// linearity are cascaded as usual from the adjusted gains, which treats mismatch loss as
+/// noiseless.
+///
+/// Use [`mismatch_interfaces`] for the per-interface mismatch and its uncertainty bounds.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Input, Block, cascade_vector_with_mismatch};
+///
+/// let input = Input::new(1.0e9, 1.0e6, -30.0, Some(290.0));
+/// let amp = Block {
+///     name: "Amp".to_string(),
+///     gain_db: 20.0,
+///     noise_figure_db: 3.0,
+///     output_vswr: Some(2.0),
+///     ..Default::default()
+/// };
+/// let filter = Block {
+///     name: "Filter".to_string(),
+///     gain_db: -1.0,
+///     noise_figure_db: 1.0,
+///     input_vswr: Some(2.0),
+///     ..Default::default()
+/// };
+/// let nodes = cascade_vector_with_mismatch(input, vec![amp, filter]);
+/// // the in-phase real reflections add 1.02 dB over the 19 dB of a matched cascade
+/// assert!((nodes[1].cumulative_gain_db - 20.023).abs() < 1e-3);
+/// ```
+#[doc(alias = "mismatch loss")]
+#[doc(alias = "VSWR")]
+#[must_use]
+pub fn cascade_vector_with_mismatch(input: Input, blocks: Vec<Block>) -> Vec<SignalNode> {
+    cascade_vector_return_vector(input, sparameters::mismatch_adjusted_blocks(&blocks))
+}
+
+/// Cascade a lineup at every frequency of a grid and return the nodes per frequency.
+///
+/// The input is re-centred at each grid frequency (power, bandwidth and noise
+/// temperature are kept), and every block with a [`FrequencyResponse`] is evaluated
+/// at the frequency reaching it.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Input, Block, FrequencyPoint, FrequencyResponse};
+/// use gainlineup::{cascade_frequency_sweep, frequency_grid};
+///
+/// let point = |f: f64, gain: f64, nf: f64| FrequencyPoint {
+///     frequency_hz: f,
+///     gain_db: gain,
+///     noise_figure_db: nf,
+///     output_p1db_dbm: None,
+///     output_ip3_dbm: None,
+/// };
+/// let lna = Block {
+///     name: "LNA".to_string(),
+///     frequency_response: Some(FrequencyResponse::new(vec![
+///         point(10.7e9, 32.0, 0.7),
+///         point(12.75e9, 28.0, 1.0),
+///     ])),
+///     ..Default::default()
+/// };
+/// let cable = Block {
+///     name: "Cable".to_string(),
+///     gain_db: -3.0,
+///     noise_figure_db: 3.0,
+///     ..Default::default()
+/// };
+///
+/// let input = Input::new(11.7e9, 1.0e6, -90.0, Some(290.0));
+/// let grid = frequency_grid(10.7e9, 12.75e9, 11);
+/// let sweep = cascade_frequency_sweep(&input, &[lna, cable], &grid);
+///
+/// assert_eq!(sweep.nodes.len(), 11);
+/// let (_, gain_db) = sweep.minimum_gain().unwrap();
+/// assert!((gain_db - 25.0).abs() < 1e-9); // 28 - 3 at 12.75 GHz
+/// ```
+#[doc(alias = "wideband")]
+#[doc(alias = "frequency sweep")]
+#[must_use]
+pub fn cascade_frequency_sweep(
+    input: &Input,
+    blocks: &[Block],
+    frequencies_hz: &[f64],
+) -> FrequencySweep {
+    tracing::debug!(
+        num_blocks = blocks.len(),
+        num_frequencies = frequencies_hz.len(),
+        "Starting frequency sweep cascade"
+    );
+    let nodes = frequencies_hz
+        .iter()
+        .map(|&frequency_hz| {
+            let input_at_frequency = Input {
+                frequency_hz,
+                ..input.clone()
+            };
+            cascade_vector_return_vector(input_at_frequency, blocks.to_vec())
+        })
+        .collect();
+
+    FrequencySweep {
+        frequencies_hz: frequencies_hz.to_vec(),
+        nodes,
+    }
+}
+
 /// Sweep input power through a cascade of blocks and return the AM-AM curve.
 ///
 /// For each input power, the signal is passed through every block in sequence
@@ -191,6 +350,7 @@ pub fn cascade_vector_return_vector(input: Input, blocks: Vec<Block>) -> Vec<Sig
 ///         noise_figure_db: 3.0,
 ///         output_p1db_dbm: Some(10.0),
 ///         output_ip3_dbm: None,
+///         ..Default::default()
 ///     },
 /// ];
 /// let sweep = cascade_am_am_sweep(&blocks, -40.0, -20.0, 10.0);
@@ -248,6 +408,7 @@ pub fn cascade_am_am_sweep(
 ///         noise_figure_db: 3.0,
 ///         output_p1db_dbm: Some(10.0),
 ///         output_ip3_dbm: None,
+///         ..Default::default()
 ///     },
 /// ];
 /// let sweep = cascade_gain_compression_sweep(&blocks, -40.0, 0.0, 10.0);
@@ -278,6 +439,7 @@ mod tests {
             frequency_hz: 1.0e9, // 1 GHz
             bandwidth_hz: 0.0,   // CW
             noise_temperature_k: Some(270.0),
+            ..Default::default()
         };
         let amplifier = super::Block {
             name: "Low Noise Amplifier".to_string(),
@@ -285,6 +447,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let attenuator = super::Block {
             name: "Attenuator".to_string(),
@@ -292,6 +455,7 @@ mod tests {
             noise_figure_db: 6.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let blocks = vec![amplifier, attenuator];
         let output_node = super::cascade_vector_return_output(input, blocks);
@@ -314,6 +478,7 @@ mod tests {
             frequency_hz: 1.0e9, // 1 GHz
             bandwidth_hz: 0.0,   // CW
             noise_temperature_k: Some(270.0),
+            ..Default::default()
         };
         let amplifier = super::Block {
             name: "Low Noise Amplifier".to_string(),
@@ -321,6 +486,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let attenuator = super::Block {
             name: "Attenuator".to_string(),
@@ -328,6 +494,7 @@ mod tests {
             noise_figure_db: 6.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let blocks = vec![amplifier, attenuator];
         let cascade_vector = super::cascade_vector_return_vector(input, blocks);
@@ -352,6 +519,7 @@ mod tests {
                 noise_figure_db: 3.0,
                 output_p1db_dbm: None,
                 output_ip3_dbm: None,
+                ..Default::default()
             },
             super::Block {
                 name: "Atten".to_string(),
@@ -359,6 +527,7 @@ mod tests {
                 noise_figure_db: 6.0,
                 output_p1db_dbm: None,
                 output_ip3_dbm: None,
+                ..Default::default()
             },
         ];
         let sweep = super::cascade_am_am_sweep(&blocks, -40.0, -20.0, 10.0);
@@ -378,6 +547,7 @@ mod tests {
                 noise_figure_db: 3.0,
                 output_p1db_dbm: Some(5.0),
                 output_ip3_dbm: None,
+                ..Default::default()
             },
             super::Block {
                 name: "Driver".to_string(),
@@ -385,6 +555,7 @@ mod tests {
                 noise_figure_db: 5.0,
                 output_p1db_dbm: Some(15.0),
                 output_ip3_dbm: None,
+                ..Default::default()
             },
         ];
         let sweep = super::cascade_am_am_sweep(&blocks, -50.0, 0.0, 10.0);
@@ -403,6 +574,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: None,
+            ..Default::default()
         }];
         let sweep = super::cascade_gain_compression_sweep(&blocks, -40.0, 0.0, 10.0);
         // At -40: linear, gain = 20
@@ -412,6 +584,53 @@ mod tests {
         assert!(last.1 < 20.0, "Gain should compress at high input");
     }
 
+    #[test]
+    fn cascade_frequency_sweep_evaluates_each_frequency() {
+        let point = |frequency_hz: f64, gain_db: f64, noise_figure_db: f64| {
+            super::FrequencyPoint {
+                frequency_hz,
+                gain_db,
+                noise_figure_db,
+                output_p1db_dbm: None,
+                output_ip3_dbm: None,
+            }
+        };
+        let lna = super::Block {
+            name: "LNA".to_string(),
+            frequency_response: Some(super::FrequencyResponse::new(vec![
+                point(1.0e9, 20.0, 1.0),
+                point(2.0e9, 10.0, 3.0),
+            ])),
+            ..Default::default()
+        };
+        let attenuator = super::Block {
+            name: "Attenuator".to_string(),
+            gain_db: -6.0,
+            noise_figure_db: 6.0,
+            ..Default::default()
+        };
+        let input = super::Input::new(1.5e9, 1.0e6, -50.0, Some(290.0));
+        let sweep =
+            super::cascade_frequency_sweep(&input, &[lna, attenuator], &[1.0e9, 1.5e9, 2.0e9]);
+
+        assert_eq!(sweep.nodes.len(), 3);
+        assert!(sweep.nodes.iter().all(|nodes| nodes.len() == 2));
+
+        let gains: Vec<f64> = sweep
+            .outputs()
+            .iter()
+            .map(|(_, node)| node.cumulative_gain_db)
+            .collect();
+        assert!((gains[0] - 14.0).abs() < 1e-9);
+        assert!((gains[1] - 9.0).abs() < 1e-9);
+        assert!((gains[2] - 4.0).abs() < 1e-9);
+
+        assert_eq!(sweep.nodes[2][1].signal_frequency_hz, 2.0e9);
+        assert!((sweep.gain_flatness_db().unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(sweep.worst_case_noise_figure().unwrap().0, 2.0e9);
+        assert_eq!(sweep.minimum_gain().unwrap().0, 2.0e9);
+    }
+
     #[test]
     fn two_part_node_cascade_vector_return_vector_with_compression() {
         let input_power: f64 = -30.0;
@@ -420,6 +639,7 @@ mod tests {
             frequency_hz: 1.0e9, // 1 GHz
             bandwidth_hz: 0.0,   // CW
             noise_temperature_k: Some(270.0),
+            ..Default::default()
         };
         let low_noise_amplifier = super::Block {
             name: "Low Noise Amplifier".to_string(),
@@ -427,6 +647,8 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: Some(5.0),
             output_ip3_dbm: None,
+            compression_model: crate::CompressionModel::HardClip,
+            ..Default::default()
         };
         let attenuator = super::Block {
             name: "Attenuator".to_string(),
@@ -434,6 +656,7 @@ mod tests {
             noise_figure_db: 6.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let high_power_amplifier = super::Block {
             name: "High Power Amplifier".to_string(),
@@ -441,6 +664,8 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: Some(20.0),
             output_ip3_dbm: None,
+            compression_model: crate::CompressionModel::HardClip,
+            ..Default::default()
         };
         let blocks = vec![low_noise_amplifier, attenuator, high_power_amplifier];
         let cascade_vector = super::cascade_vector_return_vector(input, blocks);
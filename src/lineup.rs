@@ -0,0 +1,431 @@
+use std::fmt;
+
+use crate::block::Block;
+use crate::input::Input;
+use crate::node::SignalNode;
+use crate::validation::{validate_cascaded, Diagnostic};
+
+/// An input signal and an ordered list of blocks, with the cascaded nodes kept up to date.
+///
+/// Editing a stage only recomputes the nodes from that stage onward; the nodes before it
+/// are reused. Blocks can be addressed by index or by name (the first block with that
+/// name).
+///
+/// Index-based methods panic on an out-of-range index, like their `Vec` counterparts.
+/// Name-based methods return `None` when no block has that name.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Input, Block, Lineup};
+///
+/// let input = Input::new(1.0e9, 1.0e6, -30.0, Some(290.0));
+/// let lna = Block {
+///     name: "LNA".to_string(),
+///     gain_db: 20.0,
+///     noise_figure_db: 1.0,
+///     ..Default::default()
+/// };
+/// let pad = Block {
+///     name: "Pad".to_string(),
+///     gain_db: -3.0,
+///     noise_figure_db: 3.0,
+///     ..Default::default()
+/// };
+///
+/// let mut lineup = Lineup::new(input, vec![lna, pad]);
+/// assert_eq!(lineup.output().unwrap().signal_power_dbm, -13.0);
+///
+/// lineup.update_by_name("Pad", |block| block.gain_db = -6.0);
+/// assert_eq!(lineup.output().unwrap().signal_power_dbm, -16.0);
+/// assert_eq!(lineup.node_by_name("LNA").unwrap().signal_power_dbm, -10.0);
+/// ```
+#[doc(alias = "cascade")]
+#[doc(alias = "signal chain")]
+#[derive(Clone, Debug, Default)]
+pub struct Lineup {
+    input: Input,
+    blocks: Vec<Block>,
+    nodes: Vec<SignalNode>,
+}
+
+impl fmt::Display for Lineup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Lineup {{ input: {}, blocks: [", self.input)?;
+        for (i, block) in self.blocks.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", block.name)?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl Lineup {
+    /// Create a lineup and cascade every block.
+    #[must_use]
+    pub fn new(input: Input, blocks: Vec<Block>) -> Self {
+        let mut lineup = Self {
+            input,
+            blocks,
+            nodes: vec![],
+        };
+        lineup.recompute_from(0);
+        lineup
+    }
+
+    /// The input signal.
+    #[must_use]
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    /// The blocks, in signal order.
+    #[must_use]
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// The node after each block, in signal order.
+    #[must_use]
+    pub fn nodes(&self) -> &[SignalNode] {
+        &self.nodes
+    }
+
+    /// Number of blocks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// `true` if the lineup has no blocks.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Final output node, or `None` if the lineup has no blocks.
+    #[must_use]
+    pub fn output(&self) -> Option<&SignalNode> {
+        self.nodes.last()
+    }
+
+    /// Index of the first block named `name`.
+    #[must_use]
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.blocks.iter().position(|block| block.name == name)
+    }
+
+    /// The first block named `name`.
+    #[must_use]
+    pub fn block(&self, name: &str) -> Option<&Block> {
+        self.position(name).map(|index| &self.blocks[index])
+    }
+
+    /// The node at the output of block `index`.
+    #[must_use]
+    pub fn node(&self, index: usize) -> Option<&SignalNode> {
+        self.nodes.get(index)
+    }
+
+    /// The node at the output of the first block named `name`.
+    #[must_use]
+    pub fn node_by_name(&self, name: &str) -> Option<&SignalNode> {
+        self.position(name).map(|index| &self.nodes[index])
+    }
+
+    /// Replace the input signal and recompute the whole lineup.
+    pub fn set_input(&mut self, input: Input) {
+        self.input = input;
+        self.recompute_from(0);
+    }
+
+    /// Append a block to the end of the lineup.
+    pub fn push(&mut self, block: Block) {
+        self.blocks.push(block);
+        self.recompute_from(self.blocks.len() - 1);
+    }
+
+    /// Insert a block so it becomes block `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`.
+    pub fn insert(&mut self, index: usize, block: Block) {
+        self.blocks.insert(index, block);
+        self.recompute_from(index);
+    }
+
+    /// Insert a block directly after the first block named `name`.
+    ///
+    /// Returns the new block's index, or `None` (dropping the block) if no block is named `name`.
+    pub fn insert_after(&mut self, name: &str, block: Block) -> Option<usize> {
+        let index = self.position(name)? + 1;
+        self.insert(index, block);
+        Some(index)
+    }
+
+    /// Remove and return block `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    pub fn remove(&mut self, index: usize) -> Block {
+        let block = self.blocks.remove(index);
+        self.recompute_from(index);
+        block
+    }
+
+    /// Remove and return the first block named `name`.
+    pub fn remove_by_name(&mut self, name: &str) -> Option<Block> {
+        self.position(name).map(|index| self.remove(index))
+    }
+
+    /// Replace block `index`, returning the old block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    pub fn replace(&mut self, index: usize, block: Block) -> Block {
+        let old = std::mem::replace(&mut self.blocks[index], block);
+        self.recompute_from(index);
+        old
+    }
+
+    /// Replace the first block named `name`, returning the old block.
+    pub fn replace_by_name(&mut self, name: &str, block: Block) -> Option<Block> {
+        self.position(name).map(|index| self.replace(index, block))
+    }
+
+    /// Edit block `index` in place and recompute from it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    pub fn update(&mut self, index: usize, edit: impl FnOnce(&mut Block)) {
+        edit(&mut self.blocks[index]);
+        self.recompute_from(index);
+    }
+
+    /// Edit the first block named `name` in place, returning `false` if there is none.
+    pub fn update_by_name(&mut self, name: &str, edit: impl FnOnce(&mut Block)) -> bool {
+        match self.position(name) {
+            Some(index) => {
+                self.update(index, edit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move block `from` so it becomes block `to`, shifting the blocks in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` is `>= len()`.
+    pub fn move_block(&mut self, from: usize, to: usize) {
+        let block = self.blocks.remove(from);
+        self.blocks.insert(to, block);
+        self.recompute_from(from.min(to));
+    }
+
+    /// Move the first block named `name` so it becomes block `to`, returning `false` if there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `to >= len()`.
+    pub fn move_by_name(&mut self, name: &str, to: usize) -> bool {
+        match self.position(name) {
+            Some(from) => {
+                self.move_block(from, to);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Swap blocks `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is `>= len()`.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.blocks.swap(a, b);
+        self.recompute_from(a.min(b));
+    }
+
+    /// Validate the input, the blocks and the cascade, reusing the cached nodes.
+    ///
+    /// See [`validate_lineup`](crate::validate_lineup) for the checks.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate_cascaded(&self.input, &self.blocks, &self.nodes)
+    }
+
+    /// Take the input and blocks back out of the lineup.
+    #[must_use]
+    pub fn into_parts(self) -> (Input, Vec<Block>) {
+        (self.input, self.blocks)
+    }
+
+    /// Drop the nodes from block `index` onward and cascade them again.
+    fn recompute_from(&mut self, index: usize) {
+        self.nodes.truncate(index);
+        tracing::debug!(
+            from_stage = index,
+            num_blocks = self.blocks.len(),
+            "Recomputing lineup"
+        );
+        for block in &self.blocks[self.nodes.len()..] {
+            let node = match self.nodes.last() {
+                Some(previous) => previous.cascade_block(block),
+                None => self.input.cascade_block(block),
+            };
+            self.nodes.push(node);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(name: &str, gain_db: f64, noise_figure_db: f64) -> Block {
+        Block {
+            name: name.to_string(),
+            gain_db,
+            noise_figure_db,
+            ..Default::default()
+        }
+    }
+
+    fn lineup() -> Lineup {
+        Lineup::new(
+            Input::new(1.0e9, 1.0e6, -50.0, Some(290.0)),
+            vec![
+                block("LNA", 20.0, 1.0),
+                block("Filter", -2.0, 2.0),
+                block("Amp", 15.0, 4.0),
+            ],
+        )
+    }
+
+    fn assert_matches_full_cascade(lineup: &Lineup) {
+        let expected =
+            crate::cascade_vector_return_vector(lineup.input().clone(), lineup.blocks().to_vec());
+        assert_eq!(lineup.nodes().len(), expected.len());
+        for (node, expected) in lineup.nodes().iter().zip(expected.iter()) {
+            assert_eq!(node.name, expected.name);
+            assert_eq!(node.signal_power_dbm, expected.signal_power_dbm);
+            assert_eq!(
+                node.cumulative_noise_figure_db,
+                expected.cumulative_noise_figure_db
+            );
+        }
+    }
+
+    #[test]
+    fn new_cascades_every_block() {
+        let lineup = lineup();
+        assert_eq!(lineup.len(), 3);
+        assert_eq!(lineup.output().unwrap().signal_power_dbm, -17.0);
+        assert_matches_full_cascade(&lineup);
+    }
+
+    #[test]
+    fn empty_lineup_has_no_output() {
+        let mut lineup = Lineup::new(Input::default(), vec![]);
+        assert!(lineup.is_empty());
+        assert!(lineup.output().is_none());
+        lineup.push(block("Amp", 10.0, 3.0));
+        assert_eq!(lineup.output().unwrap().signal_power_dbm, 10.0);
+    }
+
+    #[test]
+    fn insert_remove_and_replace() {
+        let mut lineup = lineup();
+        lineup.insert(1, block("Pad", -3.0, 3.0));
+        assert_eq!(lineup.position("Pad"), Some(1));
+        assert_eq!(lineup.output().unwrap().signal_power_dbm, -20.0);
+        assert_matches_full_cascade(&lineup);
+
+        let removed = lineup.remove_by_name("Filter").unwrap();
+        assert_eq!(removed.gain_db, -2.0);
+        assert_matches_full_cascade(&lineup);
+
+        let old = lineup.replace(0, block("Quiet LNA", 25.0, 0.5));
+        assert_eq!(old.name, "LNA");
+        assert_matches_full_cascade(&lineup);
+
+        assert_eq!(
+            lineup.insert_after("Quiet LNA", block("Limiter", -0.5, 0.5)),
+            Some(1)
+        );
+        assert!(lineup
+            .insert_after("Missing", block("X", 0.0, 0.0))
+            .is_none());
+        assert_matches_full_cascade(&lineup);
+        assert!(lineup.remove_by_name("Missing").is_none());
+    }
+
+    #[test]
+    fn update_recomputes_downstream_only() {
+        let mut lineup = lineup();
+        let lna_node = lineup.node(0).unwrap().clone();
+        assert!(lineup.update_by_name("Filter", |b| b.gain_db = -5.0));
+        assert_eq!(
+            lineup.node(0).unwrap().signal_power_dbm,
+            lna_node.signal_power_dbm
+        );
+        assert_eq!(
+            lineup.node_by_name("Filter").unwrap().signal_power_dbm,
+            -35.0
+        );
+        assert_eq!(lineup.output().unwrap().signal_power_dbm, -20.0);
+        assert!(!lineup.update_by_name("Missing", |b| b.gain_db = 0.0));
+        assert_matches_full_cascade(&lineup);
+    }
+
+    #[test]
+    fn reorder_changes_noise_figure() {
+        let mut lineup = lineup();
+        let nf_before = lineup.output().unwrap().cumulative_noise_figure_db;
+        assert!(lineup.move_by_name("Filter", 0));
+        assert_eq!(lineup.blocks()[0].name, "Filter");
+        assert_eq!(lineup.blocks()[1].name, "LNA");
+        // same total gain, but the loss in front degrades the noise figure
+        assert_eq!(lineup.output().unwrap().signal_power_dbm, -17.0);
+        assert!(lineup.output().unwrap().cumulative_noise_figure_db > nf_before);
+        assert_matches_full_cascade(&lineup);
+
+        lineup.swap(0, 1);
+        assert_eq!(lineup.blocks()[0].name, "LNA");
+        assert!((lineup.output().unwrap().cumulative_noise_figure_db - nf_before).abs() < 1e-12);
+    }
+
+    #[test]
+    fn set_input_recomputes_everything() {
+        let mut lineup = lineup();
+        lineup.set_input(Input::new(1.0e9, 1.0e6, -40.0, Some(290.0)));
+        assert_eq!(lineup.node(0).unwrap().signal_power_dbm, -20.0);
+        assert_eq!(lineup.output().unwrap().signal_power_dbm, -7.0);
+        assert_matches_full_cascade(&lineup);
+    }
+
+    #[test]
+    fn validate_uses_cached_nodes() {
+        let mut lineup = lineup();
+        assert!(lineup.validate().is_empty());
+        lineup.update_by_name("Amp", |b| b.output_p1db_dbm = Some(-25.0));
+        let findings = lineup.validate();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].block.as_deref(), Some("Amp"));
+    }
+
+    #[test]
+    fn display_lineup() {
+        let s = format!("{}", lineup());
+        assert!(s.contains("blocks: [LNA, Filter, Amp]"));
+    }
+}
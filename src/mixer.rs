@@ -0,0 +1,248 @@
+use std::fmt;
+
+use crate::phase_noise::PhaseNoise;
+
+/// Which side of the wanted RF signal the local oscillator sits on.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::Injection;
+///
+/// assert_eq!(Injection::default(), Injection::LowSide);
+/// ```
+#[doc(alias = "high-side")]
+#[doc(alias = "low-side")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Injection {
+    /// LO below the RF signal (f_LO = f_RF − f_IF), IF spectrum is not inverted.
+    #[default]
+    LowSide,
+    /// LO above the RF signal (f_LO = f_RF + f_IF), IF spectrum is inverted.
+    HighSide,
+}
+
+impl fmt::Display for Injection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Injection::LowSide => write!(f, "low-side"),
+            Injection::HighSide => write!(f, "high-side"),
+        }
+    }
+}
+
+/// Mixer noise figure as quoted on a datasheet.
+///
+/// Single-sideband (SSB) noise figure is what the cascade uses; a double-sideband (DSB)
+/// figure is 3 dB optimistic for a heterodyne receiver because the image sideband
+/// contributes noise but no signal.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::MixerNoiseFigure;
+///
+/// assert_eq!(MixerNoiseFigure::Ssb(9.0).ssb_db(), 9.0);
+/// assert!((MixerNoiseFigure::Dsb(6.0).ssb_db() - 9.01).abs() < 0.01);
+/// ```
+#[doc(alias = "SSB")]
+#[doc(alias = "DSB")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MixerNoiseFigure {
+    /// Single-sideband noise figure in dB.
+    Ssb(f64),
+    /// Double-sideband noise figure in dB.
+    Dsb(f64),
+}
+
+impl MixerNoiseFigure {
+    /// The equivalent single-sideband noise figure in dB (DSB + 3.01 dB).
+    #[must_use]
+    pub fn ssb_db(&self) -> f64 {
+        match *self {
+            MixerNoiseFigure::Ssb(nf_db) => nf_db,
+            MixerNoiseFigure::Dsb(nf_db) => nf_db + rfconversions::power::linear_to_db(2.0),
+        }
+    }
+}
+
+/// Local oscillator settings of a frequency-converting stage.
+///
+/// A mixer outputs the difference product `|f_RF − f_LO|`; its image is the other RF
+/// frequency that lands on the same IF, `2·f_LO − f_RF`. Attach it to a [`Block`](crate::Block)
+/// through [`BlockKind::Mixer`](crate::BlockKind::Mixer) or [`Block::mixer`](crate::Block::mixer).
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Injection, Mixer};
+///
+/// // 2.4 GHz down to 70 MHz with the LO below the signal
+/// let mixer = Mixer::from_if(2.4e9, 70.0e6, Injection::LowSide);
+/// assert_eq!(mixer.lo_frequency_hz, 2.33e9);
+/// assert_eq!(mixer.output_frequency_hz(2.4e9), 70.0e6);
+/// assert_eq!(mixer.image_frequency_hz(2.4e9), 2.26e9);
+/// ```
+#[doc(alias = "downconverter")]
+#[doc(alias = "LO")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mixer {
+    /// Local oscillator frequency in Hz.
+    pub lo_frequency_hz: f64,
+    /// Intended LO placement relative to the wanted RF signal.
+    pub injection: Injection,
+    /// LO drive level at the mixer LO port in dBm, if specified.
+    pub lo_power_dbm: Option<f64>,
+    /// Phase noise of the LO at the mixer LO port, if characterized.
+    ///
+    /// It transfers unchanged to the converted signal.
+    pub lo_phase_noise: Option<PhaseNoise>,
+}
+
+impl fmt::Display for Mixer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Mixer {{ lo_frequency: {} Hz, injection: {}",
+            self.lo_frequency_hz, self.injection
+        )?;
+        if let Some(lo_power) = self.lo_power_dbm {
+            write!(f, ", lo_power: {} dBm", lo_power)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl Mixer {
+    /// Create a mixer with a fixed LO frequency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Injection, Mixer};
+    ///
+    /// let mixer = Mixer::new(10.6e9, Injection::LowSide).with_lo_power(13.0);
+    /// assert_eq!(mixer.lo_power_dbm, Some(13.0));
+    /// ```
+    #[must_use]
+    pub fn new(lo_frequency_hz: f64, injection: Injection) -> Self {
+        Self {
+            lo_frequency_hz,
+            injection,
+            lo_power_dbm: None,
+            lo_phase_noise: None,
+        }
+    }
+
+    /// Create a mixer whose LO converts `rf_frequency_hz` to `if_frequency_hz` with the given injection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Injection, Mixer};
+    ///
+    /// let mixer = Mixer::from_if(1.0e9, 100.0e6, Injection::HighSide);
+    /// assert_eq!(mixer.lo_frequency_hz, 1.1e9);
+    /// ```
+    #[must_use]
+    pub fn from_if(rf_frequency_hz: f64, if_frequency_hz: f64, injection: Injection) -> Self {
+        let lo_frequency_hz = match injection {
+            Injection::LowSide => rf_frequency_hz - if_frequency_hz,
+            Injection::HighSide => rf_frequency_hz + if_frequency_hz,
+        };
+        Self::new(lo_frequency_hz, injection)
+    }
+
+    /// Set the LO drive level in dBm.
+    #[must_use]
+    pub fn with_lo_power(mut self, lo_power_dbm: f64) -> Self {
+        self.lo_power_dbm = Some(lo_power_dbm);
+        self
+    }
+
+    /// Set the LO phase noise, e.g. a synthesizer profile scaled by an LO multiplier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Injection, Mixer, PhaseNoise};
+    ///
+    /// let synthesizer = PhaseNoise::new(&[(1.0e3, -100.0), (1.0e6, -140.0)]);
+    /// let mixer = Mixer::new(10.0e9, Injection::LowSide)
+    ///     .with_lo_phase_noise(synthesizer.multiplied(4.0));
+    /// let lo = mixer.lo_phase_noise.unwrap();
+    /// assert!((lo.level_at(1.0e3).unwrap() - (-87.96)).abs() < 0.01);
+    /// ```
+    #[must_use]
+    pub fn with_lo_phase_noise(mut self, lo_phase_noise: PhaseNoise) -> Self {
+        self.lo_phase_noise = Some(lo_phase_noise);
+        self
+    }
+
+    /// Output (IF) frequency in Hz for an RF input at `rf_frequency_hz`.
+    ///
+    /// Logs a warning when the RF sits on the opposite side of the LO from the configured
+    /// injection, i.e. the signal is being received through the image response.
+    #[must_use]
+    pub fn output_frequency_hz(&self, rf_frequency_hz: f64) -> f64 {
+        let lo_above_rf = self.lo_frequency_hz > rf_frequency_hz;
+        if lo_above_rf != (self.injection == Injection::HighSide) {
+            tracing::warn!(
+                rf_frequency_hz,
+                lo_frequency_hz = self.lo_frequency_hz,
+                injection = %self.injection,
+                "RF is on the image side of the LO for the configured injection"
+            );
+        }
+        (rf_frequency_hz - self.lo_frequency_hz).abs()
+    }
+
+    /// Image frequency in Hz for an RF input at `rf_frequency_hz` (`2·f_LO − f_RF`).
+    #[must_use]
+    pub fn image_frequency_hz(&self, rf_frequency_hz: f64) -> f64 {
+        2.0 * self.lo_frequency_hz - rf_frequency_hz
+    }
+
+    /// `true` when the IF spectrum is inverted relative to the RF (LO above the RF).
+    #[must_use]
+    pub fn is_spectrum_inverted(&self, rf_frequency_hz: f64) -> bool {
+        self.lo_frequency_hz > rf_frequency_hz
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_if_places_lo() {
+        let low = Mixer::from_if(2.0e9, 200.0e6, Injection::LowSide);
+        assert_eq!(low.lo_frequency_hz, 1.8e9);
+        assert!(!low.is_spectrum_inverted(2.0e9));
+        let high = Mixer::from_if(2.0e9, 200.0e6, Injection::HighSide);
+        assert_eq!(high.lo_frequency_hz, 2.2e9);
+        assert!(high.is_spectrum_inverted(2.0e9));
+    }
+
+    #[test]
+    fn output_and_image_frequency() {
+        let mixer = Mixer::new(2.2e9, Injection::HighSide);
+        assert!((mixer.output_frequency_hz(2.0e9) - 200.0e6).abs() < 1e-3);
+        assert!((mixer.image_frequency_hz(2.0e9) - 2.4e9).abs() < 1e-3);
+        // the image converts to the same IF
+        assert!((mixer.output_frequency_hz(2.4e9) - 200.0e6).abs() < 1e-3);
+    }
+
+    #[test]
+    fn dsb_noise_figure_is_three_db_lower() {
+        let ssb = MixerNoiseFigure::Dsb(7.0).ssb_db();
+        assert!((ssb - 10.0103).abs() < 1e-3, "got {}", ssb);
+    }
+
+    #[test]
+    fn display_mixer() {
+        let s = format!("{}", Mixer::new(1.0e9, Injection::LowSide).with_lo_power(7.0));
+        assert!(s.contains("injection: low-side"));
+        assert!(s.contains("lo_power: 7 dBm"));
+    }
+}
@@ -1,7 +1,14 @@
 use std::default::Default;
 use std::fmt;
 
+use crate::adc;
+use crate::aliasing::{cascade_noise_filters, fold_noise};
+use crate::array::ArrayGain;
 use crate::block::Block;
+use crate::digital;
+use crate::filter::Filter;
+use crate::harmonics::{cascade_harmonics, Harmonic};
+use crate::phase_noise::{combine_phase_noise, PhaseNoise};
 
 /// Summary of dynamic range metrics at a given node in the cascade.
 ///
@@ -17,6 +24,7 @@ use crate::block::Block;
 ///     noise_figure_db: 2.0,
 ///     output_p1db_dbm: Some(10.0),
 ///     output_ip3_dbm: Some(25.0),
+///     ..Default::default()
 /// };
 /// let node = input.cascade_block(&lna);
 /// let dr = node.dynamic_range_summary().unwrap();
@@ -30,28 +38,187 @@ pub struct DynamicRange {
     pub linear_dr_db: f64,
     /// Spur-free dynamic range (dB), from existing SFDR calculation.
     pub sfdr_db: Option<f64>,
+    /// Second-order spur-free dynamic range (dB), limited by IM2 instead of IM3.
+    pub sfdr2_db: Option<f64>,
     /// Minimum detectable signal: noise floor at this node (dBm).
     pub mds_dbm: f64,
     /// Maximum input power before compression: input P1dB = output P1dB − cumulative gain (dBm).
     pub max_input_dbm: f64,
+    /// Stage that limits the cascaded P1dB, if known.
+    pub p1db_limiting_stage: Option<String>,
 }
 
 impl fmt::Display for DynamicRange {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "DynamicRange {{ linear_dr: {:.1} dB, sfdr: {}, mds: {:.1} dBm, max_input: {:.1} dBm }}",
+            "DynamicRange {{ linear_dr: {:.1} dB, sfdr: {}, sfdr2: {}, mds: {:.1} dBm, max_input: {:.1} dBm }}",
             self.linear_dr_db,
             match self.sfdr_db {
                 Some(v) => format!("{:.1} dB", v),
                 None => "N/A".to_string(),
             },
+            match self.sfdr2_db {
+                Some(v) => format!("{:.1} dB", v),
+                None => "N/A".to_string(),
+            },
             self.mds_dbm,
             self.max_input_dbm
+        )?;
+        if let Some(stage) = &self.p1db_limiting_stage {
+            write!(f, " (compression limited by {})", stage)?;
+        }
+        Ok(())
+    }
+}
+
+/// One stage's share of the system noise at a [`SignalNode`] (Friis, referred to the cascade input).
+///
+/// A stage with noise temperature `Te` behind a cumulative gain `G` contributes `Te / G`;
+/// the source contributes its own noise temperature. Percentages are of the total system
+/// noise temperature (source plus every stage) at the node.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Input, Block, cascade_vector_return_output};
+///
+/// let input = Input::new(1.0e9, 1.0e6, -60.0, Some(290.0));
+/// let blocks = vec![
+///     Block { name: "LNA".to_string(), gain_db: 20.0, noise_figure_db: 1.0, ..Default::default() },
+///     Block { name: "Mixer".to_string(), gain_db: -7.0, noise_figure_db: 7.0, ..Default::default() },
+/// ];
+/// let node = cascade_vector_return_output(input, blocks);
+/// let contributions = &node.noise_contributions;
+/// assert_eq!(contributions[0].name, "Input");
+/// assert_eq!(contributions[1].name, "LNA");
+/// assert!((contributions[1].noise_temperature_k - 75.1).abs() < 0.1);
+/// let total: f64 = contributions.iter().map(|c| c.percent).sum();
+/// assert!((total - 100.0).abs() < 1e-9);
+/// ```
+#[doc(alias = "noise budget")]
+#[doc(alias = "Friis")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoiseContribution {
+    /// Stage name, or `"Input"` for the source.
+    pub name: String,
+    /// Input-referred noise temperature contributed by this stage in Kelvin.
+    pub noise_temperature_k: f64,
+    /// Share of the total system noise temperature at the node, in percent.
+    pub percent: f64,
+}
+
+impl fmt::Display for NoiseContribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NoiseContribution {{ name: {}, noise_temperature: {:.2} K, percent: {:.1} % }}",
+            self.name, self.noise_temperature_k, self.percent
         )
     }
 }
 
+impl NoiseContribution {
+    pub(crate) fn new(name: &str, noise_temperature_k: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            noise_temperature_k,
+            percent: 0.0,
+        }
+    }
+}
+
+/// Recompute each contribution's share of the total.
+pub(crate) fn update_noise_percentages(contributions: &mut [NoiseContribution]) {
+    let total_k: f64 = contributions.iter().map(|c| c.noise_temperature_k).sum();
+    for contribution in contributions.iter_mut() {
+        contribution.percent = if total_k > 0.0 {
+            100.0 * contribution.noise_temperature_k / total_k
+        } else {
+            0.0
+        };
+    }
+}
+
+/// One stage's share of the cascaded OIP3 and P1dB at a [`SignalNode`].
+///
+/// The cascade adds the stages' intercepts and compression points as `1/P`, each referred to
+/// the node through the gain after the stage; `oip3_dbm` and `p1db_dbm` are this stage's
+/// specs referred to the node, and the percentages are its share of the cascaded `1/OIP3`
+/// and `1/P1dB`. The stage with the largest share limits the lineup. Fields are `None` when
+/// the stage has no spec or the cascaded value is not available.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Input, Block, cascade_vector_return_output};
+///
+/// let input = Input::new(1.0e9, 1.0e6, -60.0, None);
+/// let blocks = vec![
+///     Block { name: "LNA".to_string(), gain_db: 20.0, output_ip3_dbm: Some(30.0), ..Default::default() },
+///     Block { name: "Mixer".to_string(), gain_db: -7.0, output_ip3_dbm: Some(15.0), ..Default::default() },
+/// ];
+/// let node = cascade_vector_return_output(input, blocks);
+/// assert_eq!(node.oip3_limiting_stage(), Some("Mixer"));
+/// let total: f64 = node.linearity_contributions.iter().filter_map(|c| c.oip3_percent).sum();
+/// assert!((total - 100.0).abs() < 1e-9);
+/// ```
+#[doc(alias = "linearity budget")]
+#[doc(alias = "IP3 budget")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearityContribution {
+    /// Stage name.
+    pub name: String,
+    /// This stage's OIP3 referred to the node in dBm.
+    pub oip3_dbm: Option<f64>,
+    /// Share of the cascaded `1/OIP3` in percent.
+    pub oip3_percent: Option<f64>,
+    /// This stage's output P1dB referred to the node in dBm.
+    pub p1db_dbm: Option<f64>,
+    /// Share of the cascaded `1/P1dB` in percent.
+    pub p1db_percent: Option<f64>,
+}
+
+impl fmt::Display for LinearityContribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LinearityContribution {{ name: {}", self.name)?;
+        if let (Some(oip3), Some(percent)) = (self.oip3_dbm, self.oip3_percent) {
+            write!(f, ", oip3: {:.2} dBm ({:.1} %)", oip3, percent)?;
+        }
+        if let (Some(p1db), Some(percent)) = (self.p1db_dbm, self.p1db_percent) {
+            write!(f, ", p1db: {:.2} dBm ({:.1} %)", p1db, percent)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl LinearityContribution {
+    pub(crate) fn new(name: &str, oip3_dbm: Option<f64>, p1db_dbm: Option<f64>) -> Self {
+        Self {
+            name: name.to_string(),
+            oip3_dbm,
+            oip3_percent: None,
+            p1db_dbm,
+            p1db_percent: None,
+        }
+    }
+}
+
+/// Recompute each contribution's share of the cascaded `1/OIP3` and `1/P1dB`.
+pub(crate) fn update_linearity_percentages(contributions: &mut [LinearityContribution]) {
+    let inverse = |dbm: f64| 1.0 / rfconversions::power::dbm_to_watts(dbm);
+    let total_oip3: f64 = contributions.iter().filter_map(|c| c.oip3_dbm).map(inverse).sum();
+    let total_p1db: f64 = contributions.iter().filter_map(|c| c.p1db_dbm).map(inverse).sum();
+    for contribution in contributions.iter_mut() {
+        contribution.oip3_percent = contribution
+            .oip3_dbm
+            .map(|oip3| 100.0 * inverse(oip3) / total_oip3);
+        contribution.p1db_percent = contribution
+            .p1db_dbm
+            .map(|p1db| 100.0 * inverse(p1db) / total_p1db);
+    }
+}
+
 /// Output signal at a node in the RF cascade, containing power, noise, and gain information.
 ///
 /// # Examples
@@ -66,6 +233,7 @@ impl fmt::Display for DynamicRange {
 ///     noise_figure_db: 1.5,
 ///     output_p1db_dbm: None,
 ///     output_ip3_dbm: None,
+///     ..Default::default()
 /// };
 /// let node = input.cascade_block(&lna);
 /// assert_eq!(node.signal_power_dbm, 0.0);
@@ -99,8 +267,66 @@ pub struct SignalNode {
     pub cumulative_oip3_dbm: Option<f64>,
     /// Spur-free dynamic range in dB, if OIP3 is available.
     pub sfdr_db: Option<f64>,
-    /// Output P1dB at this node in dBm, if applicable.
+    /// Cascaded output-referred IP2 in dBm, if available.
+    pub cumulative_oip2_dbm: Option<f64>,
+    /// Second-order spur-free dynamic range in dB, if OIP2 is available.
+    pub sfdr2_db: Option<f64>,
+    /// Cascaded output P1dB at this node in dBm, combining every stage so far.
+    #[doc(alias = "P1dB")]
     pub output_p1db_dbm: Option<f64>,
+    /// Name of the stage whose own P1dB, referred to this node, is the lowest.
+    pub p1db_limiting_stage: Option<String>,
+    /// Output P1dB of the limiting stage referred to this node in dBm.
+    pub p1db_limiting_stage_dbm: Option<f64>,
+    /// Image frequency in Hz of the conversion into this node, if the stage is a mixer.
+    #[doc(alias = "image")]
+    pub image_frequency_hz: Option<f64>,
+    /// Insertion phase through the cascade in degrees (not wrapped), including AM-PM.
+    #[doc(alias = "insertion phase")]
+    pub cumulative_phase_deg: f64,
+    /// The part of `cumulative_phase_deg` from AM-PM in compressed stages, which changes
+    /// with the drive level.
+    #[doc(alias = "AM-PM")]
+    pub cumulative_am_pm_deg: f64,
+    /// Group delay through the cascade in seconds.
+    #[doc(alias = "delay")]
+    pub cumulative_group_delay_s: f64,
+    /// Phase noise of the signal at this node: the source plus every LO so far.
+    #[doc(alias = "L(f)")]
+    pub phase_noise: Option<PhaseNoise>,
+    /// Phase noise of the LOs so far, which sets the reciprocal mixing of blockers.
+    pub lo_phase_noise: Option<PhaseNoise>,
+    /// Harmonics generated so far, sorted by order and frequency, after every stage since.
+    #[doc(alias = "H2")]
+    #[doc(alias = "H3")]
+    pub harmonics: Vec<Harmonic>,
+    /// The phased array combined upstream of this node, for its G/T and SNR improvement.
+    #[doc(alias = "G/T")]
+    pub array: Option<ArrayGain>,
+    /// Peak-to-average power ratio of the signal envelope in dB, from the input.
+    #[doc(alias = "PAPR")]
+    pub papr_db: f64,
+    /// Margin in dB from the signal peaks to the full scale of the ADC that produced this
+    /// node; negative when the converter clips. `None` after any other stage.
+    #[doc(alias = "clipping")]
+    pub adc_headroom_db: Option<f64>,
+    /// Filters upstream of this node, moved through any mixers, with the share of the
+    /// node's noise that passed through each. They shape the out-of-band noise an ADC folds.
+    pub noise_filters: Vec<(Filter, f64)>,
+    /// SNR lost in dB to out-of-band noise folded into the signal band by an ADC with a
+    /// noise bandwidth, at or upstream of this node.
+    #[doc(alias = "aliasing")]
+    #[doc(alias = "noise folding")]
+    pub alias_snr_degradation_db: Option<f64>,
+    /// Sample rate in Hz, set by an ADC and divided by decimation; `None` before any ADC.
+    pub sample_rate_hz: Option<f64>,
+    /// The level in dBm, referred like `signal_power_dbm`, that is 0 dBFS; set by an ADC.
+    #[doc(alias = "dBFS")]
+    pub full_scale_dbm: Option<f64>,
+    /// Source and per-stage contributions to the system noise at this node.
+    pub noise_contributions: Vec<NoiseContribution>,
+    /// Per-stage shares of the cascaded OIP3 and P1dB at this node.
+    pub linearity_contributions: Vec<LinearityContribution>,
 }
 
 impl fmt::Display for SignalNode {
@@ -126,7 +352,27 @@ impl Default for SignalNode {
             cumulative_noise_temperature: None,
             cumulative_oip3_dbm: None,
             sfdr_db: None,
+            cumulative_oip2_dbm: None,
+            sfdr2_db: None,
             output_p1db_dbm: None,
+            p1db_limiting_stage: None,
+            p1db_limiting_stage_dbm: None,
+            image_frequency_hz: None,
+            cumulative_phase_deg: 0.0,
+            cumulative_am_pm_deg: 0.0,
+            cumulative_group_delay_s: 0.0,
+            phase_noise: None,
+            lo_phase_noise: None,
+            harmonics: vec![],
+            array: None,
+            papr_db: 0.0,
+            adc_headroom_db: None,
+            noise_filters: vec![],
+            alias_snr_degradation_db: None,
+            sample_rate_hz: None,
+            full_scale_dbm: None,
+            noise_contributions: vec![],
+            linearity_contributions: vec![],
         }
     }
 }
@@ -146,6 +392,7 @@ impl SignalNode {
     ///     noise_figure_db: 2.0,
     ///     output_p1db_dbm: None,
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let node = input.cascade_block(&lna);
     /// let nsd = node.noise_spectral_density();
@@ -179,6 +426,7 @@ impl SignalNode {
     ///     noise_figure_db: 2.0,
     ///     output_p1db_dbm: None,
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let node = input.cascade_block(&lna);
     /// let snr = node.signal_to_noise_ratio_db();
@@ -208,6 +456,7 @@ impl SignalNode {
     ///     noise_figure_db: 3.0,
     ///     output_p1db_dbm: None,
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let atten = Block {
     ///     name: "Attenuator".to_string(),
@@ -215,6 +464,7 @@ impl SignalNode {
     ///     noise_figure_db: 6.0,
     ///     output_p1db_dbm: None,
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let after_lna = input.cascade_block(&lna);
     /// let after_atten = after_lna.cascade_block(&atten);
@@ -222,6 +472,33 @@ impl SignalNode {
     /// ```
     #[must_use]
     pub fn cascade_block(&self, block: &Block) -> SignalNode {
+        // evaluate frequency-dependent blocks at the frequency reaching them
+        let evaluated_block;
+        let block = if block.frequency_response.is_some() {
+            evaluated_block = block.at_frequency(self.signal_frequency_hz);
+            &evaluated_block
+        } else {
+            block
+        };
+        // converters add jitter noise that depends on the signal reaching them
+        let converter_block;
+        let block = match adc::at_signal(block, self.signal_power_dbm, self.signal_frequency_hz) {
+            Some(evaluated) => {
+                converter_block = evaluated;
+                &converter_block
+            }
+            None => block,
+        };
+        // digital stages round relative to the full scale and sample rate reaching them
+        let digital_block;
+        let block = match digital::at_node(block, self.full_scale_dbm, self.sample_rate_hz) {
+            Some(evaluated) => {
+                digital_block = evaluated;
+                &digital_block
+            }
+            None => block,
+        };
+
         
         tracing::debug!("START NODE Cascade_block");
 
@@ -236,18 +513,16 @@ impl SignalNode {
         let cumulative_gain_linear =
             rfconversions::power::db_to_linear(self.cumulative_gain_db);
 
+        // filters narrow the bandwidth and reject signals outside their passband
+        let output_bandwidth_hz = block.output_bandwidth_hz(self.signal_bandwidth_hz);
+        let bandwidth_ratio_db = 10.0 * (output_bandwidth_hz / self.signal_bandwidth_hz).log10();
+        let rejection_db = block.rejection_db(self.signal_frequency_hz);
+
         // handle compression point
         // this is a simplification in that you can compress the block with noise
-        let output_power_without_compression = self.signal_power_dbm + block.gain_db;
-        let output_power_dbm = if let Some(output_p1db_dbm) = block.output_p1db_dbm {
-            if output_power_without_compression > output_p1db_dbm + 1.0 {
-                output_p1db_dbm + 1.0
-            } else {
-                output_power_without_compression
-            }
-        } else {
-            output_power_without_compression
-        };
+        let output_power_without_compression =
+            self.signal_power_dbm + block.gain_db - rejection_db;
+        let output_power_dbm = block.compress(output_power_without_compression);
 
         let stage_power_gain = output_power_dbm - self.signal_power_dbm;
 
@@ -264,26 +539,36 @@ impl SignalNode {
                 Some(270.0 + block_noise_temperature / cumulative_gain_linear)
             };
 
+        // per-stage Friis contributions, referred to the cascade input
+        let mut noise_contributions = self.noise_contributions.clone();
+        if noise_contributions.is_empty() && self.cumulative_noise_figure_db > 0.0 {
+            // a node built by hand: lump everything upstream together
+            noise_contributions.push(NoiseContribution::new(
+                &self.name,
+                rfconversions::noise::noise_temperature_from_noise_figure(
+                    self.cumulative_noise_figure_db,
+                ),
+            ));
+        }
+        noise_contributions.push(NoiseContribution::new(
+            &block.name,
+            block_noise_temperature / cumulative_gain_linear,
+        ));
+        update_noise_percentages(&mut noise_contributions);
+
         let input_noise_power_dbm = self.noise_power_dbm;
 
         
         tracing::debug!("Input Noise Power: (dBm) {}", input_noise_power_dbm);
 
         // handle compression point separately (as they are separate signals)
-        let output_noise_power_without_compression = input_noise_power_dbm + block.gain_db;
-        let output_noise_power_from_node_dbm = if let Some(output_p1db_dbm) = block.output_p1db_dbm
-        {
-            if output_noise_power_without_compression > output_p1db_dbm + 1.0 {
-                output_p1db_dbm + 1.0
-            } else {
-                output_noise_power_without_compression
-            }
-        } else {
-            output_noise_power_without_compression
-        };
+        // only the noise inside the output bandwidth gets through
+        let output_noise_power_without_compression =
+            input_noise_power_dbm + block.gain_db + bandwidth_ratio_db;
+        let output_noise_power_from_node_dbm = block.compress(output_noise_power_without_compression);
 
         // output noise power from block (independent of compression TODO: check this)
-        let output_noise_power_from_block_dbm = block.output_noise_power(self.signal_bandwidth_hz);
+        let output_noise_power_from_block_dbm = block.output_noise_power(output_bandwidth_hz);
 
         
         tracing::debug!(
@@ -321,36 +606,116 @@ impl SignalNode {
             total_noise_power_at_output_dbm
         );
 
-        let output_frequency_hz = self.signal_frequency_hz;
-        let output_bandwidth_hz = self.signal_bandwidth_hz;
-
-        // TODO: handle frequency and bandwidth changes, i.e. mixers, filters, etc.
+        let output_frequency_hz = block.output_frequency_hz(self.signal_frequency_hz);
 
         
         tracing::debug!("END NODE Cascade_block");
 
-        // Cascaded OIP3 calculation
+        // Cascaded OIP3 calculation: 1/OIP3 = 1/(G * OIP3_prev) + 1/OIP3_block
         let cumulative_oip3_dbm = match (self.cumulative_oip3_dbm, block.output_ip3_dbm) {
             (Some(prev_oip3_dbm), Some(block_oip3_dbm)) => {
                 let prev_oip3_linear = rfconversions::power::dbm_to_watts(prev_oip3_dbm);
                 let block_oip3_linear = rfconversions::power::dbm_to_watts(block_oip3_dbm);
                 let gain_linear = rfconversions::power::db_to_linear(block.gain_db);
-                let inv_cascade = gain_linear / prev_oip3_linear + 1.0 / block_oip3_linear;
+                let inv_cascade = 1.0 / (gain_linear * prev_oip3_linear) + 1.0 / block_oip3_linear;
                 Some(rfconversions::power::watts_to_dbm(1.0 / inv_cascade))
             }
             (None, Some(block_oip3_dbm)) => Some(block_oip3_dbm),
             _ => None,
         };
 
+        // Cascaded OIP2 calculation
+        // IM2 products add in voltage: 1/sqrt(OIP2) = 1/sqrt(G * OIP2_prev) + 1/sqrt(OIP2_block)
+        let cumulative_oip2_dbm = match (self.cumulative_oip2_dbm, block.output_ip2_dbm) {
+            (Some(prev_oip2_dbm), Some(block_oip2_dbm)) => {
+                let prev_oip2_linear = rfconversions::power::dbm_to_watts(prev_oip2_dbm);
+                let block_oip2_linear = rfconversions::power::dbm_to_watts(block_oip2_dbm);
+                let gain_linear = rfconversions::power::db_to_linear(block.gain_db);
+                let inv_sqrt_cascade =
+                    1.0 / (gain_linear * prev_oip2_linear).sqrt() + 1.0 / block_oip2_linear.sqrt();
+                Some(rfconversions::power::watts_to_dbm(
+                    1.0 / (inv_sqrt_cascade * inv_sqrt_cascade),
+                ))
+            }
+            (None, Some(block_oip2_dbm)) => Some(block_oip2_dbm),
+            _ => None,
+        };
+
+        // Cascaded output P1dB, with the same rule as OIP3 above:
+        // 1/P1dB = 1/(G * P1dB_prev) + 1/P1dB_block
+        // a stage without a P1dB is treated as perfectly linear and only scales the previous one
+        let (output_p1db_dbm, p1db_limiting_stage, p1db_limiting_stage_dbm) =
+            match (self.output_p1db_dbm, block.output_p1db_dbm) {
+                (Some(prev_p1db_dbm), Some(block_p1db_dbm)) => {
+                    let prev_p1db_linear = rfconversions::power::dbm_to_watts(prev_p1db_dbm);
+                    let block_p1db_linear = rfconversions::power::dbm_to_watts(block_p1db_dbm);
+                    let gain_linear = rfconversions::power::db_to_linear(block.gain_db);
+                    let inv_cascade =
+                        1.0 / (gain_linear * prev_p1db_linear) + 1.0 / block_p1db_linear;
+                    let cascaded_dbm = rfconversions::power::watts_to_dbm(1.0 / inv_cascade);
+
+                    let prev_limit_dbm =
+                        self.p1db_limiting_stage_dbm.unwrap_or(prev_p1db_dbm) + block.gain_db;
+                    if block_p1db_dbm <= prev_limit_dbm {
+                        (Some(cascaded_dbm), Some(block.name.clone()), Some(block_p1db_dbm))
+                    } else {
+                        (
+                            Some(cascaded_dbm),
+                            self.p1db_limiting_stage.clone(),
+                            Some(prev_limit_dbm),
+                        )
+                    }
+                }
+                (Some(prev_p1db_dbm), None) => (
+                    Some(prev_p1db_dbm + block.gain_db),
+                    self.p1db_limiting_stage.clone(),
+                    Some(self.p1db_limiting_stage_dbm.unwrap_or(prev_p1db_dbm) + block.gain_db),
+                ),
+                (None, Some(block_p1db_dbm)) => (
+                    Some(block_p1db_dbm),
+                    Some(block.name.clone()),
+                    Some(block_p1db_dbm),
+                ),
+                (None, None) => (None, None, None),
+            };
+
+        // per-stage shares of the cascaded OIP3 and P1dB, following the same combination rules
+        let mut linearity_contributions = self.linearity_contributions.clone();
+        if linearity_contributions.is_empty()
+            && (self.cumulative_oip3_dbm.is_some() || self.output_p1db_dbm.is_some())
+        {
+            // a node built by hand: lump everything upstream together
+            linearity_contributions.push(LinearityContribution::new(
+                &self.name,
+                self.cumulative_oip3_dbm,
+                self.output_p1db_dbm,
+            ));
+        }
+        let keep_oip3 = self.cumulative_oip3_dbm.is_some() && block.output_ip3_dbm.is_some();
+        for contribution in linearity_contributions.iter_mut() {
+            contribution.oip3_dbm = contribution
+                .oip3_dbm
+                .filter(|_| keep_oip3)
+                .map(|oip3| oip3 + block.gain_db);
+            contribution.p1db_dbm = contribution.p1db_dbm.map(|p1db| p1db + block.gain_db);
+        }
+        linearity_contributions.push(LinearityContribution::new(
+            &block.name,
+            block.output_ip3_dbm,
+            block.output_p1db_dbm,
+        ));
+        update_linearity_percentages(&mut linearity_contributions);
+
         // SFDR calculation
         let new_cumulative_gain_db = self.cumulative_gain_db + stage_power_gain;
-        let sfdr_db = cumulative_oip3_dbm.map(|oip3| {
-            let noise_floor_dbm =
-                -174.0 + 10.0 * output_bandwidth_hz.log10() + cumulative_noise_figure;
-            2.0 / 3.0 * (oip3 - noise_floor_dbm)
-        });
+        let noise_floor_dbm = -174.0 + 10.0 * output_bandwidth_hz.log10() + cumulative_noise_figure;
+        let sfdr_db = adc::limit_sfdr(
+            block,
+            cumulative_oip3_dbm.map(|oip3| 2.0 / 3.0 * (oip3 - noise_floor_dbm)),
+        );
+        let sfdr2_db = cumulative_oip2_dbm.map(|oip2| 0.5 * (oip2 - noise_floor_dbm));
 
-        SignalNode {
+        let mut node = SignalNode {
             name: output_node_name,
             signal_frequency_hz: output_frequency_hz,
             signal_bandwidth_hz: output_bandwidth_hz,
@@ -361,8 +726,51 @@ impl SignalNode {
             cumulative_noise_temperature,
             cumulative_oip3_dbm,
             sfdr_db,
-            output_p1db_dbm: block.output_p1db_dbm,
-        }
+            cumulative_oip2_dbm,
+            sfdr2_db,
+            output_p1db_dbm,
+            p1db_limiting_stage,
+            p1db_limiting_stage_dbm,
+            image_frequency_hz: block.image_frequency_hz(self.signal_frequency_hz),
+            cumulative_phase_deg: self.cumulative_phase_deg + block.phase_at(self.signal_power_dbm),
+            cumulative_am_pm_deg: self.cumulative_am_pm_deg
+                + block.am_pm_phase_at(self.signal_power_dbm),
+            cumulative_group_delay_s: self.cumulative_group_delay_s
+                + block.group_delay_s.unwrap_or(0.0),
+            phase_noise: combine_phase_noise(self.phase_noise.as_ref(), block.lo_phase_noise()),
+            lo_phase_noise: combine_phase_noise(
+                self.lo_phase_noise.as_ref(),
+                block.lo_phase_noise(),
+            ),
+            harmonics: cascade_harmonics(
+                &self.harmonics,
+                block,
+                self.signal_frequency_hz,
+                self.signal_power_dbm,
+            ),
+            array: self.array,
+            papr_db: self.papr_db,
+            adc_headroom_db: adc::headroom_db(block, self.signal_power_dbm, self.papr_db),
+            noise_filters: cascade_noise_filters(
+                &self.noise_filters,
+                block,
+                output_noise_power_from_node_watts / total_noise_power_at_output_watts,
+            ),
+            alias_snr_degradation_db: self.alias_snr_degradation_db,
+            sample_rate_hz: digital::sample_rate_hz(block, self.sample_rate_hz),
+            full_scale_dbm: digital::full_scale_dbm(block, self.full_scale_dbm),
+            noise_contributions,
+            linearity_contributions,
+        };
+        // converters and decimators fold the out-of-band noise reaching them into the signal band
+        fold_noise(
+            &mut node,
+            block,
+            &self.noise_filters,
+            self.noise_spectral_density(),
+            self.sample_rate_hz,
+        );
+        node
     }
 
     /// Cumulative noise factor (linear) at this node.
@@ -379,6 +787,7 @@ impl SignalNode {
     ///     noise_figure_db: 3.0,
     ///     output_p1db_dbm: None,
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let node = input.cascade_block(&lna);
     /// let nf = node.noise_factor();
@@ -403,6 +812,7 @@ impl SignalNode {
     ///     noise_figure_db: 3.0,
     ///     output_p1db_dbm: None,
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let node = input.cascade_block(&lna);
     /// let temp = node.noise_temperature();
@@ -413,6 +823,45 @@ impl SignalNode {
         rfconversions::noise::noise_temperature_from_noise_figure(self.cumulative_noise_figure_db)
     }
 
+    /// Cascaded input-referred P1dB in dBm (`OP1dB − G + 1` with the cumulative gain).
+    ///
+    /// Returns `None` if no stage so far has a P1dB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Input, Block};
+    ///
+    /// let input = Input::new(1.0e9, 1.0e6, -60.0, None);
+    /// let lna = Block {
+    ///     name: "LNA".to_string(),
+    ///     gain_db: 20.0,
+    ///     noise_figure_db: 2.0,
+    ///     output_p1db_dbm: Some(10.0),
+    ///     ..Default::default()
+    /// };
+    /// let node = input.cascade_block(&lna);
+    /// assert!((node.input_p1db_dbm().unwrap() - (-9.0)).abs() < 1e-6);
+    /// ```
+    #[doc(alias = "IP1dB")]
+    #[must_use]
+    pub fn input_p1db_dbm(&self) -> Option<f64> {
+        self.output_p1db_dbm
+            .map(|op1db| rfconversions::p1db::output_to_input_db(op1db, self.cumulative_gain_db))
+    }
+
+    /// Name of the stage with the largest share of the cascaded OIP3 at this node.
+    ///
+    /// See [`LinearityContribution`]; the P1dB equivalent is `p1db_limiting_stage`.
+    #[must_use]
+    pub fn oip3_limiting_stage(&self) -> Option<&str> {
+        self.linearity_contributions
+            .iter()
+            .filter_map(|c| c.oip3_percent.map(|percent| (c.name.as_str(), percent)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(name, _)| name)
+    }
+
     /// Linear dynamic range at this node in dB.
     ///
     /// `output_p1db_dbm - noise_power_dbm`
@@ -431,6 +880,7 @@ impl SignalNode {
     ///     noise_figure_db: 2.0,
     ///     output_p1db_dbm: Some(10.0),
     ///     output_ip3_dbm: None,
+    ///     ..Default::default()
     /// };
     /// let node = input.cascade_block(&lna);
     /// let dr = node.dynamic_range_db().unwrap();
@@ -444,7 +894,8 @@ impl SignalNode {
 
     /// Build a [`DynamicRange`] summary from this node's fields.
     ///
-    /// Returns `None` if `output_p1db_dbm` is not set.
+    /// Uses the cascaded `output_p1db_dbm`, so an earlier stage that compresses first
+    /// sets the limit. Returns `None` if `output_p1db_dbm` is not set.
     ///
     /// # Examples
     ///
@@ -458,6 +909,7 @@ impl SignalNode {
     ///     noise_figure_db: 2.0,
     ///     output_p1db_dbm: Some(10.0),
     ///     output_ip3_dbm: Some(25.0),
+    ///     ..Default::default()
     /// };
     /// let node = input.cascade_block(&lna);
     /// let summary = node.dynamic_range_summary().unwrap();
@@ -472,10 +924,149 @@ impl SignalNode {
         Some(DynamicRange {
             linear_dr_db,
             sfdr_db: self.sfdr_db,
+            sfdr2_db: self.sfdr2_db,
             mds_dbm: self.noise_power_dbm,
             max_input_dbm,
+            p1db_limiting_stage: self.p1db_limiting_stage.clone(),
         })
     }
+
+    /// RMS phase error in degrees of the signal at this node, integrating its
+    /// `phase_noise` from `start_hz` to `stop_hz`.
+    ///
+    /// Returns `None` without phase noise or if the offsets are outside its profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Block, Injection, Input, Mixer, MixerNoiseFigure, PhaseNoise};
+    ///
+    /// let lo = PhaseNoise::new(&[(1.0e3, -90.0), (1.0e5, -110.0), (1.0e7, -140.0)]);
+    /// let mixer = Block::mixer(
+    ///     "Mixer",
+    ///     -7.0,
+    ///     MixerNoiseFigure::Ssb(7.0),
+    ///     Mixer::new(2.33e9, Injection::LowSide).with_lo_phase_noise(lo),
+    /// );
+    /// let input = Input::new(2.4e9, 1.0e6, -50.0, Some(290.0));
+    /// let node = input.cascade_block(&mixer);
+    /// let deg = node.rms_phase_error_deg(1.0e3, 1.0e7).unwrap();
+    /// assert!(deg > 0.1 && deg < 10.0);
+    /// ```
+    #[doc(alias = "integrated phase noise")]
+    #[must_use]
+    pub fn rms_phase_error_deg(&self, start_hz: f64, stop_hz: f64) -> Option<f64> {
+        self.phase_noise
+            .as_ref()?
+            .rms_phase_error_deg(start_hz, stop_hz)
+    }
+
+    /// RMS jitter in seconds of the signal at this node, integrating its `phase_noise`
+    /// from `start_hz` to `stop_hz` at the node's signal frequency.
+    #[doc(alias = "jitter")]
+    #[must_use]
+    pub fn rms_jitter_s(&self, start_hz: f64, stop_hz: f64) -> Option<f64> {
+        self.phase_noise
+            .as_ref()?
+            .rms_jitter_s(self.signal_frequency_hz, start_hz, stop_hz)
+    }
+
+    /// Reciprocal mixing noise in dBm at this node from a blocker `blocker_offset_hz` away
+    /// from the signal, with `blocker_power_dbm` at the cascade input.
+    ///
+    /// The blocker is taken through the cumulative gain (filter rejection of the blocker is
+    /// not included) and mixed by `lo_phase_noise` into the signal bandwidth. Returns `None`
+    /// if no LO phase noise is set or the offset is outside its profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Block, Injection, Input, Mixer, MixerNoiseFigure, PhaseNoise};
+    ///
+    /// let lo = PhaseNoise::new(&[(1.0e4, -100.0), (1.0e6, -130.0)]);
+    /// let mixer = Block::mixer(
+    ///     "Mixer",
+    ///     -7.0,
+    ///     MixerNoiseFigure::Ssb(7.0),
+    ///     Mixer::new(2.33e9, Injection::LowSide).with_lo_phase_noise(lo),
+    /// );
+    /// let input = Input::new(2.4e9, 200.0e3, -90.0, Some(290.0));
+    /// let node = input.cascade_block(&mixer);
+    /// // -30 dBm blocker 1 MHz away: -30 - 7 - 130 + 53 dB(200 kHz)
+    /// let noise = node.reciprocal_mixing_noise_dbm(-30.0, 1.0e6).unwrap();
+    /// assert!((noise - (-113.99)).abs() < 0.01);
+    /// ```
+    #[doc(alias = "blocker")]
+    #[must_use]
+    pub fn reciprocal_mixing_noise_dbm(
+        &self,
+        blocker_power_dbm: f64,
+        blocker_offset_hz: f64,
+    ) -> Option<f64> {
+        let dbc = self
+            .lo_phase_noise
+            .as_ref()?
+            .reciprocal_mixing_dbc(blocker_offset_hz, self.signal_bandwidth_hz)?;
+        Some(blocker_power_dbm + self.cumulative_gain_db + dbc)
+    }
+
+    /// Level in dBc of the strongest harmonic of `order` at this node, relative to the
+    /// signal, or `None` if no stage generated one.
+    #[must_use]
+    pub fn harmonic_dbc(&self, order: u32) -> Option<f64> {
+        self.harmonics
+            .iter()
+            .filter(|harmonic| harmonic.order == order)
+            .map(|harmonic| harmonic.power_dbm - self.signal_power_dbm)
+            .max_by(f64::total_cmp)
+    }
+
+    /// G/T of the phased array upstream of this node in dB/K: the gain of every element
+    /// together against the system noise temperature here (the total of
+    /// `noise_contributions`), or `None` without an array.
+    #[doc(alias = "G/T")]
+    #[must_use]
+    pub fn g_over_t_db(&self) -> Option<f64> {
+        let array = self.array.as_ref()?;
+        let system_temperature_k: f64 = self
+            .noise_contributions
+            .iter()
+            .map(|c| c.noise_temperature_k)
+            .sum();
+        Some(rfconversions::noise::g_over_t(
+            array.array_gain_dbi(),
+            system_temperature_k,
+        ))
+    }
+
+    /// Signal-to-noise ratio at this node over that of one element channel of the phased
+    /// array upstream, in dB, or `None` without an array.
+    #[must_use]
+    pub fn array_snr_improvement_db(&self) -> Option<f64> {
+        Some(self.signal_to_noise_ratio_db() - self.array.as_ref()?.element_snr_db)
+    }
+
+    /// Signal power in dBFS, against the full scale of the ADC upstream, or `None` before
+    /// any ADC.
+    #[doc(alias = "dBFS")]
+    #[must_use]
+    pub fn signal_power_dbfs(&self) -> Option<f64> {
+        Some(self.signal_power_dbm - self.full_scale_dbm?)
+    }
+
+    /// Noise power in the signal bandwidth in dBFS, or `None` before any ADC.
+    #[must_use]
+    pub fn noise_power_dbfs(&self) -> Option<f64> {
+        Some(self.noise_power_dbm - self.full_scale_dbm?)
+    }
+
+    /// Processing gain in dB of the signal bandwidth in the Nyquist zone at this node's
+    /// sample rate, `10·log10(fs / 2B)`: how far the SNR here is above the SNR over the
+    /// whole zone. `None` before any ADC.
+    #[must_use]
+    pub fn processing_gain_db(&self) -> Option<f64> {
+        Some(10.0 * (self.sample_rate_hz? / (2.0 * self.signal_bandwidth_hz)).log10())
+    }
 }
 
 #[cfg(test)]
@@ -496,6 +1087,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
         let amplifier = super::Block {
             name: "Simple Amplifier".to_string(),
@@ -503,6 +1095,7 @@ mod tests {
             noise_figure_db: 5.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let output_node = input_node.cascade_block(&amplifier);
 
@@ -533,6 +1126,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
         let amplifier = super::Block {
             name: "Low Noise Amplifier".to_string(),
@@ -540,6 +1134,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
 
         let output_node = input_node.cascade_block(&amplifier);
@@ -571,6 +1166,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
         let amplifier = super::Block {
             name: "Low Noise Amplifier".to_string(),
@@ -578,6 +1174,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let attenuator = super::Block {
             name: "Attenuator".to_string(),
@@ -585,6 +1182,7 @@ mod tests {
             noise_figure_db: 6.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
         let intermediate_node = input_node.cascade_block(&amplifier);
 
@@ -619,6 +1217,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
 
         // Case 1: Standard ~290K noise temperature (NF=3dB implies F=2, T=290K if T0=290K? No, T = T0 * (F-1). If F=2, T=290. Total Noise Temp = T_source + T_added. SOurce is usually 290K.
@@ -655,6 +1254,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
         // SNR = -100 - (-174) = 74 dB
         let snr_db = node.signal_to_noise_ratio_db();
@@ -678,6 +1278,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
 
         // 1. Verify input node has None for cumulative_noise_temperature
@@ -700,6 +1301,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
 
         let output_node = input_node.cascade_block(&block);
@@ -733,6 +1335,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
 
         // 1. Verify input node has None for cumulative_noise_temperature
@@ -755,6 +1358,7 @@ mod tests {
             noise_figure_db: 6.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
 
         let output_node = input_node.cascade_block(&block);
@@ -787,6 +1391,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
 
         // Block with 20 dB gain and output P1dB at 10 dBm
@@ -798,6 +1403,8 @@ mod tests {
             noise_figure_db: 6.0,
             output_p1db_dbm: Some(10.0), // Compression point at 10 dBm output
             output_ip3_dbm: None,
+            compression_model: crate::CompressionModel::HardClip,
+            ..Default::default()
         };
 
         let output_node = input_node.cascade_block(&block);
@@ -851,6 +1458,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
 
         let lna = super::Block {
@@ -859,6 +1467,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: Some(20.0),
+            ..Default::default()
         };
 
         let attenuator = super::Block {
@@ -867,6 +1476,7 @@ mod tests {
             noise_figure_db: 6.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         };
 
         let after_lna = input_node.cascade_block(&lna);
@@ -892,6 +1502,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
 
         let lna = super::Block {
@@ -900,6 +1511,7 @@ mod tests {
             noise_figure_db: 2.0,
             output_p1db_dbm: None,
             output_ip3_dbm: Some(30.0),
+            ..Default::default()
         };
 
         let mixer = super::Block {
@@ -908,6 +1520,7 @@ mod tests {
             noise_figure_db: 8.0,
             output_p1db_dbm: None,
             output_ip3_dbm: Some(15.0),
+            ..Default::default()
         };
 
         let if_amp = super::Block {
@@ -916,22 +1529,23 @@ mod tests {
             noise_figure_db: 4.0,
             output_p1db_dbm: None,
             output_ip3_dbm: Some(25.0),
+            ..Default::default()
         };
 
         let n1 = input_node.cascade_block(&lna);
         assert_eq!(n1.cumulative_oip3_dbm, Some(30.0));
 
         let n2 = n1.cascade_block(&mixer);
-        // 1/OIP3_new = G_mixer_linear/OIP3_lna_linear + 1/OIP3_mixer_linear
+        // 1/OIP3_new = 1/(G_mixer_linear * OIP3_lna_linear) + 1/OIP3_mixer_linear
         // G_mixer = 10^(-8/10) = 0.158489
         // OIP3_lna = 10^(30/10) * 0.001 = 1.0 W
         // OIP3_mixer = 10^(15/10) * 0.001 = 0.031623 W
-        // 1/OIP3_new = 0.158489/1.0 + 1/0.031623 = 0.158489 + 31.623 = 31.7815
-        // OIP3_new = 0.031465 W = 10*log10(0.031465/0.001) = 14.978 dBm
+        // 1/OIP3_new = 1/0.158489 + 1/0.031623 = 6.3096 + 31.623 = 37.932
+        // OIP3_new = 0.026363 W = 10*log10(0.026363/0.001) = 14.210 dBm
         let oip3_2 = n2.cumulative_oip3_dbm.unwrap();
         assert!(
-            (oip3_2 - 14.978).abs() < 0.1,
-            "Expected ~14.978, got {}",
+            (oip3_2 - 14.210).abs() < 0.01,
+            "Expected ~14.210, got {}",
             oip3_2
         );
 
@@ -957,6 +1571,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: None,
+            ..Default::default()
         };
 
         let lna = super::Block {
@@ -965,6 +1580,7 @@ mod tests {
             noise_figure_db: 3.0,
             output_p1db_dbm: None,
             output_ip3_dbm: Some(30.0),
+            ..Default::default()
         };
 
         let node = input_node.cascade_block(&lna);
@@ -981,6 +1597,267 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cascaded_oip2_and_sfdr2() {
+        // LNA (gain=20, OIP2=+50) → Mixer (gain=-7, OIP2=+40)
+        let input_node = super::SignalNode {
+            name: "Input".to_string(),
+            signal_power_dbm: -30.0,
+            signal_frequency_hz: 1.0e9,
+            signal_bandwidth_hz: 1.0e6,
+            noise_power_dbm: -100.0,
+            cumulative_gain_db: 0.0,
+            ..Default::default()
+        };
+
+        let lna = super::Block {
+            name: "LNA".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 2.0,
+            output_ip2_dbm: Some(50.0),
+            ..Default::default()
+        };
+
+        let mixer = super::Block {
+            name: "Mixer".to_string(),
+            gain_db: -7.0,
+            noise_figure_db: 7.0,
+            output_ip2_dbm: Some(40.0),
+            ..Default::default()
+        };
+
+        let n1 = input_node.cascade_block(&lna);
+        assert_eq!(n1.cumulative_oip2_dbm, Some(50.0));
+        assert_eq!(n1.sfdr_db, None);
+
+        let n2 = n1.cascade_block(&mixer);
+        // LNA OIP2 referred to the mixer output: 50 - 7 = 43 dBm = 19.95 W, mixer: 10 W
+        // 1/sqrt(OIP2) = 1/sqrt(19.95) + 1/sqrt(10) = 0.5401 → OIP2 = 3.428 W = 35.35 dBm
+        let oip2 = n2.cumulative_oip2_dbm.unwrap();
+        assert!((oip2 - 35.35).abs() < 0.01, "Expected ~35.35, got {}", oip2);
+
+        // SFDR2 = 1/2 * (OIP2 - noise_floor)
+        let noise_floor = -174.0 + 60.0 + n2.cumulative_noise_figure_db;
+        let sfdr2 = n2.sfdr2_db.unwrap();
+        assert!((sfdr2 - 0.5 * (oip2 - noise_floor)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cascaded_p1db_and_limiting_stage() {
+        // LNA (gain=20, P1dB=+10) → Pad (gain=-6, no P1dB) → Driver (gain=15, P1dB=+20)
+        let input_node = super::SignalNode {
+            name: "Input".to_string(),
+            signal_power_dbm: -60.0,
+            signal_frequency_hz: 1.0e9,
+            signal_bandwidth_hz: 1.0e6,
+            noise_power_dbm: -110.0,
+            cumulative_gain_db: 0.0,
+            ..Default::default()
+        };
+
+        let lna = super::Block {
+            name: "LNA".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 2.0,
+            output_p1db_dbm: Some(10.0),
+            ..Default::default()
+        };
+        let pad = super::Block {
+            name: "Pad".to_string(),
+            gain_db: -6.0,
+            noise_figure_db: 6.0,
+            ..Default::default()
+        };
+        let driver = super::Block {
+            name: "Driver".to_string(),
+            gain_db: 15.0,
+            noise_figure_db: 5.0,
+            output_p1db_dbm: Some(20.0),
+            ..Default::default()
+        };
+
+        let n1 = input_node.cascade_block(&lna);
+        assert_eq!(n1.output_p1db_dbm, Some(10.0));
+        assert_eq!(n1.p1db_limiting_stage.as_deref(), Some("LNA"));
+
+        // the pad only scales the LNA's P1dB
+        let n2 = n1.cascade_block(&pad);
+        assert_eq!(n2.output_p1db_dbm, Some(4.0));
+        assert_eq!(n2.p1db_limiting_stage.as_deref(), Some("LNA"));
+
+        // LNA referred to the output is +19 dBm, below the driver's +20 dBm
+        // 1/P1dB = 1/79.4 mW + 1/100 mW → 44.3 mW = 16.46 dBm
+        let n3 = n2.cascade_block(&driver);
+        let p1db = n3.output_p1db_dbm.unwrap();
+        assert!((p1db - 16.461).abs() < 0.01, "Expected ~16.461, got {}", p1db);
+        assert_eq!(n3.p1db_limiting_stage.as_deref(), Some("LNA"));
+        assert!((n3.p1db_limiting_stage_dbm.unwrap() - 19.0).abs() < 1e-9);
+
+        let summary = n3.dynamic_range_summary().unwrap();
+        assert!((summary.linear_dr_db - (p1db - n3.noise_power_dbm)).abs() < 1e-9);
+        assert_eq!(summary.p1db_limiting_stage.as_deref(), Some("LNA"));
+
+        // a weaker final stage takes over as the limit
+        let weak_driver = super::Block {
+            output_p1db_dbm: Some(12.0),
+            ..driver.clone()
+        };
+        let n3 = n2.cascade_block(&weak_driver);
+        assert_eq!(n3.p1db_limiting_stage.as_deref(), Some("Driver"));
+        assert!(n3.output_p1db_dbm.unwrap() < 12.0);
+    }
+
+    #[test]
+    fn input_p1db_refers_to_cumulative_gain() {
+        let node = super::SignalNode {
+            cumulative_gain_db: 30.0,
+            output_p1db_dbm: Some(20.0),
+            ..Default::default()
+        };
+        assert_eq!(node.input_p1db_dbm(), Some(-9.0));
+        assert_eq!(super::SignalNode::default().input_p1db_dbm(), None);
+    }
+
+    #[test]
+    fn noise_contributions_sum_to_cascaded_noise_temperature() {
+        let input = crate::Input::new(1.0e9, 1.0e6, -60.0, Some(50.0));
+        let lna = super::Block {
+            name: "LNA".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 1.0,
+            ..Default::default()
+        };
+        let mixer = super::Block {
+            name: "Mixer".to_string(),
+            gain_db: -7.0,
+            noise_figure_db: 7.0,
+            ..Default::default()
+        };
+        let if_amp = super::Block {
+            name: "IF Amp".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 3.0,
+            ..Default::default()
+        };
+
+        let node = input
+            .cascade_block(&lna)
+            .cascade_block(&mixer)
+            .cascade_block(&if_amp);
+        let contributions = &node.noise_contributions;
+        let names: Vec<&str> = contributions.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Input", "LNA", "Mixer", "IF Amp"]);
+        assert_eq!(contributions[0].noise_temperature_k, 50.0);
+
+        // Mixer: Te = 290 * (10^0.7 - 1) = 1163 K behind 20 dB of gain
+        assert!((contributions[2].noise_temperature_k - 11.63).abs() < 0.01);
+        // IF Amp: Te = 288.6 K behind 13 dB
+        assert!((contributions[3].noise_temperature_k - 14.46).abs() < 0.01);
+
+        // stage contributions add up to the cascaded noise temperature
+        let stages_k: f64 = contributions[1..].iter().map(|c| c.noise_temperature_k).sum();
+        assert!((stages_k - node.noise_temperature()).abs() < 1e-6);
+
+        let total_percent: f64 = contributions.iter().map(|c| c.percent).sum();
+        assert!((total_percent - 100.0).abs() < 1e-9);
+        assert!(contributions[1].percent > contributions[2].percent);
+    }
+
+    #[test]
+    fn noise_contributions_from_hand_built_node() {
+        let node = super::SignalNode {
+            name: "Front End".to_string(),
+            signal_bandwidth_hz: 1.0e6,
+            cumulative_noise_figure_db: 3.0,
+            cumulative_gain_db: 30.0,
+            ..Default::default()
+        };
+        let amp = super::Block {
+            name: "Amp".to_string(),
+            gain_db: 10.0,
+            noise_figure_db: 10.0,
+            ..Default::default()
+        };
+        let next = node.cascade_block(&amp);
+        assert_eq!(next.noise_contributions.len(), 2);
+        assert_eq!(next.noise_contributions[0].name, "Front End");
+        assert!((next.noise_contributions[0].noise_temperature_k - 288.6).abs() < 0.1);
+        // 2610 K behind 30 dB
+        assert!((next.noise_contributions[1].noise_temperature_k - 2.61).abs() < 0.01);
+    }
+
+    #[test]
+    fn linearity_contributions_match_cascade() {
+        // LNA (gain=20, OIP3=+30, P1dB=+20) → Mixer (gain=-8, OIP3=+15, P1dB=+5) → Pad (gain=-3)
+        let input = crate::Input::new(1.0e9, 1.0e6, -60.0, None);
+        let lna = super::Block {
+            name: "LNA".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 2.0,
+            output_p1db_dbm: Some(20.0),
+            output_ip3_dbm: Some(30.0),
+            ..Default::default()
+        };
+        let mixer = super::Block {
+            name: "Mixer".to_string(),
+            gain_db: -8.0,
+            noise_figure_db: 8.0,
+            output_p1db_dbm: Some(5.0),
+            output_ip3_dbm: Some(15.0),
+            ..Default::default()
+        };
+        let pad = super::Block {
+            name: "Pad".to_string(),
+            gain_db: -3.0,
+            noise_figure_db: 3.0,
+            ..Default::default()
+        };
+
+        let n1 = input.cascade_block(&lna);
+        assert_eq!(n1.linearity_contributions[0].oip3_percent, Some(100.0));
+        assert_eq!(n1.linearity_contributions[0].p1db_percent, Some(100.0));
+
+        let n2 = n1.cascade_block(&mixer);
+        let inverse = |dbm: f64| 1.0 / rfconversions::power::dbm_to_watts(dbm);
+        let contributions = &n2.linearity_contributions;
+        let oip3_sum: f64 = contributions.iter().filter_map(|c| c.oip3_dbm).map(inverse).sum();
+        assert!((oip3_sum - inverse(n2.cumulative_oip3_dbm.unwrap())).abs() < 1e-9);
+        let p1db_sum: f64 = contributions.iter().filter_map(|c| c.p1db_dbm).map(inverse).sum();
+        assert!((p1db_sum - inverse(n2.output_p1db_dbm.unwrap())).abs() < 1e-9);
+
+        // 6.31 of 37.93 (see test_cascaded_oip3_three_stage): the LNA's +30 dBm OIP3 is
+        // +22 dBm at the mixer output, 7 dB above the mixer's
+        assert!((contributions[0].oip3_percent.unwrap() - 16.63).abs() < 0.01);
+        assert_eq!(contributions[0].oip3_dbm, Some(22.0));
+        assert_eq!(n2.oip3_limiting_stage(), Some("Mixer"));
+        // LNA P1dB referred to the mixer output is +12 dBm, the mixer's +5 dBm
+        assert_eq!(contributions[0].p1db_dbm, Some(12.0));
+        assert!(contributions[1].p1db_percent.unwrap() > 80.0);
+
+        // the pad has no OIP3, so the OIP3 cascade (and its breakdown) ends; P1dB carries on
+        let n3 = n2.cascade_block(&pad);
+        assert_eq!(n3.linearity_contributions.len(), 3);
+        assert!(n3.linearity_contributions.iter().all(|c| c.oip3_percent.is_none()));
+        assert_eq!(n3.oip3_limiting_stage(), None);
+        assert_eq!(n3.linearity_contributions[1].p1db_dbm, Some(2.0));
+        assert_eq!(n3.linearity_contributions[2].p1db_dbm, None);
+        assert_eq!(n3.p1db_limiting_stage.as_deref(), Some("Mixer"));
+    }
+
+    #[test]
+    fn display_linearity_contribution() {
+        let c = super::LinearityContribution {
+            name: "Mixer".to_string(),
+            oip3_dbm: Some(15.0),
+            oip3_percent: Some(99.5),
+            p1db_dbm: None,
+            p1db_percent: None,
+        };
+        let s = format!("{}", c);
+        assert!(s.contains("oip3: 15.00 dBm (99.5 %)"));
+        assert!(!s.contains("p1db"));
+    }
+
     // ----- Phase 4: Dynamic Range at Node Level -----
 
     #[test]
@@ -997,6 +1874,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: Some(10.0),
+            ..Default::default()
         };
         let dr = node.dynamic_range_db().unwrap();
         assert!((dr - 110.0).abs() < 1e-10, "Expected 110 dB, got {}", dr);
@@ -1022,12 +1900,15 @@ mod tests {
             cumulative_oip3_dbm: Some(30.0),
             sfdr_db: Some(80.0),
             output_p1db_dbm: Some(10.0),
+            ..Default::default()
         };
         let summary = node.dynamic_range_summary().unwrap();
         // linear_dr = 10 - (-100) = 110
         assert!((summary.linear_dr_db - 110.0).abs() < 1e-10);
         // sfdr from node
         assert_eq!(summary.sfdr_db, Some(80.0));
+        assert_eq!(summary.sfdr2_db, None);
+        assert_eq!(summary.p1db_limiting_stage, None);
         // mds = noise_power_dbm
         assert!((summary.mds_dbm - (-100.0)).abs() < 1e-10);
         // max_input = output_p1db - gain = 10 - 20 = -10
@@ -1048,6 +1929,7 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: Some(10.0),
+            ..Default::default()
         };
         let summary = node.dynamic_range_summary().unwrap();
         assert!((summary.mds_dbm - node.noise_power_dbm).abs() < 1e-10);
@@ -1067,9 +1949,178 @@ mod tests {
             cumulative_oip3_dbm: None,
             sfdr_db: None,
             output_p1db_dbm: Some(15.0),
+            ..Default::default()
         };
         let summary = node.dynamic_range_summary().unwrap();
         // 15 - 25 = -10
         assert!((summary.max_input_dbm - (-10.0)).abs() < 1e-10);
     }
+
+    #[test]
+    fn mixer_translates_frequency_and_reports_image() {
+        let input_node = super::SignalNode {
+            name: "LNA Output".to_string(),
+            signal_power_dbm: -60.0,
+            signal_frequency_hz: 2.0e9,
+            signal_bandwidth_hz: 1.0e6,
+            noise_power_dbm: -100.0,
+            cumulative_noise_figure_db: 2.0,
+            cumulative_gain_db: 20.0,
+            ..Default::default()
+        };
+        let mixer = super::Block::mixer(
+            "Mixer",
+            -7.0,
+            crate::MixerNoiseFigure::Ssb(7.0),
+            crate::Mixer::new(2.2e9, crate::Injection::HighSide),
+        );
+        let output_node = input_node.cascade_block(&mixer);
+        assert!((output_node.signal_frequency_hz - 200.0e6).abs() < 1e-3);
+        assert!((output_node.image_frequency_hz.unwrap() - 2.4e9).abs() < 1e-3);
+        assert_eq!(output_node.signal_power_dbm, -67.0);
+        assert_eq!(output_node.cumulative_gain_db, 13.0);
+
+        // the IF carries through the following stages, the image is only reported at the mixer
+        let amplifier = super::Block {
+            name: "IF Amplifier".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 3.0,
+            ..Default::default()
+        };
+        let if_node = output_node.cascade_block(&amplifier);
+        assert!((if_node.signal_frequency_hz - 200.0e6).abs() < 1e-3);
+        assert_eq!(if_node.image_frequency_hz, None);
+    }
+
+    #[test]
+    fn filter_narrows_bandwidth_and_noise() {
+        let input_node = super::SignalNode {
+            name: "IF Amplifier Output".to_string(),
+            signal_power_dbm: -50.0,
+            signal_frequency_hz: 70.0e6,
+            signal_bandwidth_hz: 10.0e6,
+            noise_power_dbm: -80.0,
+            cumulative_noise_figure_db: 3.0,
+            cumulative_gain_db: 40.0,
+            ..Default::default()
+        };
+        let filter = super::Block::filter("IF Filter", 2.0, crate::Filter::new(70.0e6, 1.0e6));
+        let output_node = input_node.cascade_block(&filter);
+
+        assert_eq!(output_node.signal_bandwidth_hz, 1.0e6);
+        assert_eq!(output_node.signal_power_dbm, -52.0);
+        // 10 dB less bandwidth and 2 dB loss; the filter's own noise is negligible at this level
+        assert!(
+            (output_node.noise_power_dbm - (-92.0)).abs() < 0.01,
+            "got {}",
+            output_node.noise_power_dbm
+        );
+        // noise spectral density only drops by the insertion loss
+        let nsd_change = output_node.noise_spectral_density() - input_node.noise_spectral_density();
+        assert!((nsd_change - (-2.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn filter_rejects_signal_outside_passband() {
+        let input_node = super::SignalNode {
+            name: "LNA Output".to_string(),
+            signal_power_dbm: -40.0,
+            signal_frequency_hz: 1.85e9,
+            signal_bandwidth_hz: 1.0e6,
+            noise_power_dbm: -90.0,
+            cumulative_noise_figure_db: 1.0,
+            cumulative_gain_db: 20.0,
+            ..Default::default()
+        };
+        let filter = super::Block::filter(
+            "Image Reject Filter",
+            1.0,
+            crate::Filter::new(2.45e9, 100.0e6).with_rejection(vec![(600.0e6, 50.0)]),
+        );
+        let output_node = input_node.cascade_block(&filter);
+        assert!((output_node.signal_power_dbm - (-91.0)).abs() < 1e-9);
+        // noise in the passband only sees the insertion loss
+        assert!((output_node.noise_power_dbm - (-91.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn second_lo_adds_phase_noise() {
+        let profile = crate::PhaseNoise::new(&[(1.0e3, -100.0), (1.0e6, -130.0)]);
+        let input_node = super::SignalNode {
+            name: "First Mixer Output".to_string(),
+            signal_frequency_hz: 300.0e6,
+            signal_bandwidth_hz: 1.0e6,
+            phase_noise: Some(profile.clone()),
+            lo_phase_noise: Some(profile.clone()),
+            ..Default::default()
+        };
+        let second_mixer = super::Block::mixer(
+            "Second Mixer",
+            -7.0,
+            crate::MixerNoiseFigure::Ssb(7.0),
+            crate::Mixer::new(289.3e6, crate::Injection::LowSide).with_lo_phase_noise(profile),
+        );
+        let output_node = input_node.cascade_block(&second_mixer);
+        // two equal, uncorrelated LOs: +3 dB
+        let level = output_node.phase_noise.unwrap().level_at(1.0e3).unwrap();
+        assert!((level - (-96.99)).abs() < 0.01);
+        let lo_level = output_node.lo_phase_noise.unwrap().level_at(1.0e3).unwrap();
+        assert!((lo_level - (-96.99)).abs() < 0.01);
+
+        // an amplifier passes it through unchanged
+        let amp = super::Block {
+            name: "Amp".to_string(),
+            gain_db: 20.0,
+            ..Default::default()
+        };
+        let node = input_node.cascade_block(&amp);
+        assert_eq!(node.phase_noise, input_node.phase_noise);
+        assert!(node.reciprocal_mixing_noise_dbm(-30.0, 1.0e6).is_some());
+        assert!(super::SignalNode::default()
+            .rms_phase_error_deg(1.0e3, 1.0e6)
+            .is_none());
+    }
+
+    #[test]
+    fn phase_and_group_delay_accumulate() {
+        // a driver and a PA whose AM-PM starts at 0 dBm and 10 dBm in
+        let driver = super::Block::builder("Driver")
+            .gain_db(20.0)
+            .output_p1db_dbm(20.0)
+            .am_pm_coefficient_deg_per_db(2.0)
+            .phase_deg(30.0)
+            .group_delay_s(1.0e-9)
+            .build()
+            .unwrap();
+        let pa = super::Block::builder("PA")
+            .gain_db(10.0)
+            .output_p1db_dbm(20.0)
+            .am_pm_coefficient_deg_per_db(5.0)
+            .phase_deg(-50.0)
+            .group_delay_s(0.5e-9)
+            .build()
+            .unwrap();
+        let cascade = |input_power_dbm: f64| {
+            let input = crate::Input::new(1.0e9, 1.0e6, input_power_dbm, None);
+            let driver_output = input.cascade_block(&driver);
+            let pa_output = driver_output.cascade_block(&pa);
+            (driver_output, pa_output)
+        };
+
+        let (_, backed_off) = cascade(-20.0);
+        assert_eq!(backed_off.cumulative_am_pm_deg, 0.0);
+        assert!((backed_off.cumulative_phase_deg - (-20.0)).abs() < 1e-9);
+        assert!((backed_off.cumulative_group_delay_s - 1.5e-9).abs() < 1e-18);
+
+        // the driver is 1 dB into AM-PM, and drives the PA well into its own
+        let (driver_output, driven) = cascade(1.0);
+        let pa_am_pm = 5.0 * (driver_output.signal_power_dbm - 10.0);
+        assert!((driver_output.cumulative_am_pm_deg - 2.0).abs() < 1e-9);
+        assert!((driven.cumulative_am_pm_deg - (2.0 + pa_am_pm)).abs() < 1e-9);
+        assert!((driven.cumulative_phase_deg - (-20.0 + 2.0 + pa_am_pm)).abs() < 1e-9);
+        assert_eq!(
+            driven.cumulative_group_delay_s,
+            backed_off.cumulative_group_delay_s
+        );
+    }
 }
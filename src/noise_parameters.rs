@@ -0,0 +1,170 @@
+use std::fmt;
+
+use crate::interpolation::{bracket, Bracket};
+use crate::sparameters::Complex;
+
+/// Two-port noise parameters at one frequency, as tabulated in a Touchstone noise block.
+///
+/// The noise figure depends on the source reflection coefficient `Γs` presented to the
+/// device:
+///
+/// `F = Fmin + 4·rn·|Γs − Γopt|² / ((1 − |Γs|²)·|1 + Γopt|²)`
+///
+/// where `rn` is the noise resistance normalized to the reference impedance.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Complex, NoiseParameters};
+///
+/// let lna = NoiseParameters {
+///     frequency_hz: 3.0e9,
+///     minimum_noise_figure_db: 0.5,
+///     optimum_reflection: Complex::from_polar_deg(0.5, 120.0),
+///     normalized_noise_resistance: 0.16,
+/// };
+/// // a noise-matched source gives Fmin
+/// assert!((lna.noise_figure_db(lna.optimum_reflection) - 0.5).abs() < 1e-12);
+/// // a 50 Ω source does worse
+/// assert!(lna.noise_figure_db(Complex::ZERO) > 0.5);
+/// ```
+#[doc(alias = "Fmin")]
+#[doc(alias = "Gamma opt")]
+#[doc(alias = "Rn")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoiseParameters {
+    /// Frequency in Hz.
+    pub frequency_hz: f64,
+    /// Minimum noise figure `Fmin` in dB, reached when `Γs = Γopt`.
+    pub minimum_noise_figure_db: f64,
+    /// Source reflection coefficient `Γopt` that gives `Fmin`.
+    pub optimum_reflection: Complex,
+    /// Effective noise resistance `Rn / Z0`.
+    pub normalized_noise_resistance: f64,
+}
+
+impl fmt::Display for NoiseParameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NoiseParameters {{ frequency: {} Hz, fmin: {} dB, gamma_opt: {} < {} deg, rn: {} }}",
+            self.frequency_hz,
+            self.minimum_noise_figure_db,
+            self.optimum_reflection.abs(),
+            self.optimum_reflection.arg_deg(),
+            self.normalized_noise_resistance
+        )
+    }
+}
+
+impl NoiseParameters {
+    /// Noise figure in dB with the source reflection coefficient `source_reflection`.
+    ///
+    /// Use [`Complex::ZERO`] for a matched (e.g. 50 Ω) source.
+    #[must_use]
+    pub fn noise_figure_db(&self, source_reflection: Complex) -> f64 {
+        let fmin = rfconversions::power::db_to_linear(self.minimum_noise_figure_db);
+        let distance = (source_reflection - self.optimum_reflection).abs();
+        let source_magnitude = source_reflection.abs();
+        let one_plus_optimum = (Complex::ONE + self.optimum_reflection).abs();
+        let noise_factor = fmin
+            + 4.0 * self.normalized_noise_resistance * distance * distance
+                / ((1.0 - source_magnitude * source_magnitude)
+                    * one_plus_optimum
+                    * one_plus_optimum);
+        rfconversions::power::linear_to_db(noise_factor)
+    }
+
+    /// Noise parameters at `frequency_hz`, linearly interpolated from `points` (sorted by
+    /// frequency).
+    ///
+    /// `Fmin` (dB), `rn` and the real and imaginary parts of `Γopt` are interpolated.
+    /// Returns `None` if `points` is empty or `frequency_hz` is outside its range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Complex, NoiseParameters};
+    ///
+    /// let point = |frequency_hz: f64, fmin_db: f64| NoiseParameters {
+    ///     frequency_hz,
+    ///     minimum_noise_figure_db: fmin_db,
+    ///     optimum_reflection: Complex::ZERO,
+    ///     normalized_noise_resistance: 0.2,
+    /// };
+    /// let table = [point(2.0e9, 0.4), point(4.0e9, 0.8)];
+    /// let mid = NoiseParameters::interpolate(&table, 3.0e9).unwrap();
+    /// assert!((mid.minimum_noise_figure_db - 0.6).abs() < 1e-12);
+    /// assert!(NoiseParameters::interpolate(&table, 5.0e9).is_none());
+    /// ```
+    #[must_use]
+    pub fn interpolate(points: &[NoiseParameters], frequency_hz: f64) -> Option<NoiseParameters> {
+        let frequencies: Vec<f64> = points.iter().map(|point| point.frequency_hz).collect();
+        let (lower, t) = match bracket(&frequencies, frequency_hz)? {
+            Bracket::At(index) => {
+                return Some(NoiseParameters {
+                    frequency_hz,
+                    ..points[index]
+                })
+            }
+            Bracket::Between { lower, t } => (lower, t),
+        };
+        let lo = &points[lower];
+        let hi = &points[lower + 1];
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        Some(NoiseParameters {
+            frequency_hz,
+            minimum_noise_figure_db: lerp(lo.minimum_noise_figure_db, hi.minimum_noise_figure_db),
+            optimum_reflection: Complex::new(
+                lerp(lo.optimum_reflection.re, hi.optimum_reflection.re),
+                lerp(lo.optimum_reflection.im, hi.optimum_reflection.im),
+            ),
+            normalized_noise_resistance: lerp(
+                lo.normalized_noise_resistance,
+                hi.normalized_noise_resistance,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::touchstone_file::TouchstoneNetwork;
+
+    const V1: &str = "! LNA\n# GHz S MA R 50\n2.0 0.4 -120 5.6 80 0.05 60 0.3 -90\n4.0 0.5 -150 5.0 40 0.06 50 0.35 -120\n! noise\n2.0 0.45 0.55 95 0.20\n4.0 0.65 0.45 155 0.12\n";
+
+    #[test]
+    fn noise_figure_at_optimum_and_matched_source() {
+        let np = NoiseParameters {
+            frequency_hz: 1.0e9,
+            minimum_noise_figure_db: 1.0,
+            optimum_reflection: Complex::ZERO,
+            normalized_noise_resistance: 0.25,
+        };
+        assert!((np.noise_figure_db(Complex::ZERO) - 1.0).abs() < 1e-12);
+        // F = Fmin + 4·0.25·0.25 / (1 − 0.25) = 1.2589 + 0.3333
+        let nf = np.noise_figure_db(Complex::new(0.5, 0.0));
+        let expected =
+            rfconversions::power::linear_to_db(rfconversions::power::db_to_linear(1.0) + 1.0 / 3.0);
+        assert!((nf - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn interpolate_between_noise_points() {
+        let noise = TouchstoneNetwork::parse(V1, 2).unwrap().noise;
+        let mid = NoiseParameters::interpolate(&noise, 3.0e9).unwrap();
+        assert!((mid.minimum_noise_figure_db - 0.55).abs() < 1e-12);
+        assert!((mid.normalized_noise_resistance - 0.16).abs() < 1e-12);
+        assert!(NoiseParameters::interpolate(&noise, 1.0e9).is_none());
+        let single = &noise[..1];
+        assert!(NoiseParameters::interpolate(single, 2.0e9).is_some());
+    }
+
+    #[test]
+    fn display_noise_parameters() {
+        let noise = TouchstoneNetwork::parse(V1, 2).unwrap().noise;
+        let s = format!("{}", noise[0]);
+        assert!(s.contains("fmin: 0.45 dB"), "{}", s);
+    }
+}
@@ -0,0 +1,422 @@
+use std::fmt;
+
+use crate::constants;
+
+/// One point of a phase noise profile: single-sideband phase noise `L(f)` at an offset
+/// from the carrier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhaseNoisePoint {
+    /// Offset from the carrier in Hz.
+    pub offset_hz: f64,
+    /// Single-sideband phase noise in dBc/Hz.
+    pub level_dbc_per_hz: f64,
+}
+
+/// Oscillator phase noise as a table of `L(f)` in dBc/Hz against offset from the carrier.
+///
+/// Between points the profile is a straight line on a log-frequency axis (a power law),
+/// which is how datasheets tabulate it. Attach it to a mixer LO with
+/// [`Mixer::with_lo_phase_noise`](crate::Mixer::with_lo_phase_noise) or to the source with
+/// [`Input::with_phase_noise`](crate::Input::with_phase_noise).
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::PhaseNoise;
+///
+/// let reference = PhaseNoise::new(&[(1.0e3, -120.0), (1.0e4, -130.0), (1.0e6, -150.0)]);
+/// // a ×10 multiplier raises the profile by 20 dB
+/// let lo = reference.multiplied(10.0);
+/// assert!((lo.level_at(1.0e4).unwrap() - (-110.0)).abs() < 1e-9);
+/// // one decade above 1 kHz at -10 dB/decade
+/// assert!((reference.level_at(3.162e3).unwrap() - (-125.0)).abs() < 0.01);
+/// ```
+#[doc(alias = "L(f)")]
+#[doc(alias = "dBc/Hz")]
+#[doc(alias = "oscillator")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhaseNoise {
+    /// Profile points, sorted by offset.
+    pub points: Vec<PhaseNoisePoint>,
+}
+
+impl fmt::Display for PhaseNoise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PhaseNoise {{ ")?;
+        for (i, point) in self.points.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "{} dBc/Hz @ {} Hz",
+                point.level_dbc_per_hz, point.offset_hz
+            )?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl PhaseNoise {
+    /// Create a profile from `(offset_hz, dBc/Hz)` pairs, in any order.
+    ///
+    /// Offsets must be positive.
+    #[must_use]
+    pub fn new(points: &[(f64, f64)]) -> Self {
+        let mut points: Vec<PhaseNoisePoint> = points
+            .iter()
+            .map(|&(offset_hz, level_dbc_per_hz)| PhaseNoisePoint {
+                offset_hz,
+                level_dbc_per_hz,
+            })
+            .collect();
+        points.sort_by(|a, b| a.offset_hz.total_cmp(&b.offset_hz));
+        Self { points }
+    }
+
+    /// Lowest and highest offset of the profile in Hz, or `None` if it is empty.
+    #[must_use]
+    pub fn offset_range_hz(&self) -> Option<(f64, f64)> {
+        Some((
+            self.points.first()?.offset_hz,
+            self.points.last()?.offset_hz,
+        ))
+    }
+
+    /// Phase noise in dBc/Hz at `offset_hz`, or `None` outside the profile (no
+    /// extrapolation).
+    #[must_use]
+    pub fn level_at(&self, offset_hz: f64) -> Option<f64> {
+        let (lowest, highest) = self.offset_range_hz()?;
+        let tolerance_hz = constants::FREQUENCY_TOLERANCE * highest;
+        if offset_hz < lowest - tolerance_hz || offset_hz > highest + tolerance_hz {
+            return None;
+        }
+        Some(self.clamped_level(offset_hz))
+    }
+
+    /// The profile of this oscillator after a frequency multiplier of ratio `n`.
+    ///
+    /// Multiplying the frequency by `n` multiplies the phase deviation by `n`, raising the
+    /// profile by `20·log10(n)`.
+    #[must_use]
+    pub fn multiplied(&self, n: f64) -> Self {
+        let scale_db = 20.0 * n.log10();
+        Self {
+            points: self
+                .points
+                .iter()
+                .map(|point| PhaseNoisePoint {
+                    level_dbc_per_hz: point.level_dbc_per_hz + scale_db,
+                    ..*point
+                })
+                .collect(),
+        }
+    }
+
+    /// The profile of this oscillator after a frequency divider of ratio `n`, lowered by
+    /// `20·log10(n)`.
+    ///
+    /// A real divider has a noise floor of its own; add it with [`PhaseNoise::combined`].
+    #[must_use]
+    pub fn divided(&self, n: f64) -> Self {
+        self.multiplied(1.0 / n)
+    }
+
+    /// The sum of two uncorrelated phase noise profiles, over the offsets of both.
+    ///
+    /// Outside its own offset range a profile contributes its nearest tabulated level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::PhaseNoise;
+    ///
+    /// let a = PhaseNoise::new(&[(1.0e3, -100.0), (1.0e6, -130.0)]);
+    /// let sum = a.combined(&a);
+    /// assert!((sum.level_at(1.0e3).unwrap() - (-96.99)).abs() < 0.01);
+    /// ```
+    #[must_use]
+    pub fn combined(&self, other: &PhaseNoise) -> Self {
+        if self.points.is_empty() {
+            return other.clone();
+        }
+        if other.points.is_empty() {
+            return self.clone();
+        }
+        let mut offsets: Vec<f64> = self
+            .points
+            .iter()
+            .chain(other.points.iter())
+            .map(|point| point.offset_hz)
+            .collect();
+        offsets.sort_by(f64::total_cmp);
+        offsets.dedup();
+        Self {
+            points: offsets
+                .into_iter()
+                .map(|offset_hz| PhaseNoisePoint {
+                    offset_hz,
+                    level_dbc_per_hz: rfconversions::power::linear_to_db(
+                        rfconversions::power::db_to_linear(self.clamped_level(offset_hz))
+                            + rfconversions::power::db_to_linear(other.clamped_level(offset_hz)),
+                    ),
+                })
+                .collect(),
+        }
+    }
+
+    /// Integrated single-sideband phase noise in dBc from `start_hz` to `stop_hz`.
+    ///
+    /// Each segment is integrated exactly as a power law. Returns `None` if the offsets
+    /// are not inside the profile or `start_hz` is not below `stop_hz`.
+    #[must_use]
+    pub fn integrated_dbc(&self, start_hz: f64, stop_hz: f64) -> Option<f64> {
+        self.integrated_linear(start_hz, stop_hz)
+            .map(rfconversions::power::linear_to_db)
+    }
+
+    /// RMS phase error in degrees from both sidebands between `start_hz` and `stop_hz`,
+    /// `√(2·∫L(f)df)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::PhaseNoise;
+    ///
+    /// // flat -100 dBc/Hz over 10 kHz: 2·1e-10·1e4 = 2e-6 rad²
+    /// let flat = PhaseNoise::new(&[(1.0e3, -100.0), (11.0e3, -100.0)]);
+    /// let deg = flat.rms_phase_error_deg(1.0e3, 11.0e3).unwrap();
+    /// assert!((deg - 2.0e-6_f64.sqrt().to_degrees()).abs() < 1e-9);
+    /// ```
+    #[doc(alias = "integrated phase noise")]
+    #[must_use]
+    pub fn rms_phase_error_deg(&self, start_hz: f64, stop_hz: f64) -> Option<f64> {
+        self.rms_phase_error_rad(start_hz, stop_hz)
+            .map(f64::to_degrees)
+    }
+
+    /// RMS jitter in seconds of a carrier at `carrier_frequency_hz` with this profile,
+    /// integrated from `start_hz` to `stop_hz`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::PhaseNoise;
+    ///
+    /// let lo = PhaseNoise::new(&[(1.0e3, -90.0), (1.0e5, -110.0), (1.0e7, -140.0)]);
+    /// let jitter = lo.rms_jitter_s(1.0e9, 1.0e3, 1.0e7).unwrap();
+    /// assert!(jitter > 0.0 && jitter < 1.0e-12);
+    /// ```
+    #[doc(alias = "jitter")]
+    #[must_use]
+    pub fn rms_jitter_s(
+        &self,
+        carrier_frequency_hz: f64,
+        start_hz: f64,
+        stop_hz: f64,
+    ) -> Option<f64> {
+        self.rms_phase_error_rad(start_hz, stop_hz)
+            .map(|phase_rad| phase_rad / (2.0 * std::f64::consts::PI * carrier_frequency_hz))
+    }
+
+    /// Reciprocal mixing level in dBc: the noise a blocker `offset_hz` away puts into a
+    /// channel of `bandwidth_hz`, relative to the blocker power.
+    ///
+    /// Uses the phase noise at the blocker offset over the whole channel. Returns `None`
+    /// outside the profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::PhaseNoise;
+    ///
+    /// let lo = PhaseNoise::new(&[(1.0e4, -100.0), (1.0e6, -130.0)]);
+    /// // -130 dBc/Hz at 1 MHz in a 200 kHz channel
+    /// let dbc = lo.reciprocal_mixing_dbc(1.0e6, 200.0e3).unwrap();
+    /// assert!((dbc - (-76.99)).abs() < 0.01);
+    /// ```
+    #[doc(alias = "blocker")]
+    #[must_use]
+    pub fn reciprocal_mixing_dbc(&self, offset_hz: f64, bandwidth_hz: f64) -> Option<f64> {
+        self.level_at(offset_hz)
+            .map(|level| level + 10.0 * bandwidth_hz.log10())
+    }
+
+    fn rms_phase_error_rad(&self, start_hz: f64, stop_hz: f64) -> Option<f64> {
+        self.integrated_linear(start_hz, stop_hz)
+            .map(|ssb| (2.0 * ssb).sqrt())
+    }
+
+    /// `∫L(f)df` (linear) from `start_hz` to `stop_hz`.
+    fn integrated_linear(&self, start_hz: f64, stop_hz: f64) -> Option<f64> {
+        let (lowest, highest) = self.offset_range_hz()?;
+        let tolerance_hz = constants::FREQUENCY_TOLERANCE * highest;
+        if start_hz >= stop_hz
+            || start_hz < lowest - tolerance_hz
+            || stop_hz > highest + tolerance_hz
+        {
+            return None;
+        }
+        let start_hz = start_hz.max(lowest);
+        let stop_hz = stop_hz.min(highest);
+        let total = self
+            .points
+            .windows(2)
+            .filter_map(|segment| {
+                let from = segment[0].offset_hz.max(start_hz);
+                let to = segment[1].offset_hz.min(stop_hz);
+                if from >= to {
+                    return None;
+                }
+                let level_from = rfconversions::power::db_to_linear(self.clamped_level(from));
+                let level_to = rfconversions::power::db_to_linear(self.clamped_level(to));
+                // L(f) = L(from)·(f/from)^slope
+                let ratio = to / from;
+                let slope = (level_to / level_from).ln() / ratio.ln();
+                Some(if (slope + 1.0).abs() < 1e-12 {
+                    level_from * from * ratio.ln()
+                } else {
+                    level_from * from * (ratio.powf(slope + 1.0) - 1.0) / (slope + 1.0)
+                })
+            })
+            .sum();
+        Some(total)
+    }
+
+    /// Level at `offset_hz`, holding the edge levels outside the profile.
+    fn clamped_level(&self, offset_hz: f64) -> f64 {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return f64::NEG_INFINITY;
+        };
+        if offset_hz <= first.offset_hz {
+            return first.level_dbc_per_hz;
+        }
+        if offset_hz >= last.offset_hz {
+            return last.level_dbc_per_hz;
+        }
+        let upper = self
+            .points
+            .partition_point(|point| point.offset_hz <= offset_hz);
+        let lo = &self.points[upper - 1];
+        let hi = &self.points[upper];
+        let t = (offset_hz / lo.offset_hz).log10() / (hi.offset_hz / lo.offset_hz).log10();
+        lo.level_dbc_per_hz + (hi.level_dbc_per_hz - lo.level_dbc_per_hz) * t
+    }
+}
+
+/// Uncorrelated sum of two optional profiles.
+pub(crate) fn combine_phase_noise(
+    a: Option<&PhaseNoise>,
+    b: Option<&PhaseNoise>,
+) -> Option<PhaseNoise> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.combined(b)),
+        (Some(one), None) | (None, Some(one)) => Some(one.clone()),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> PhaseNoise {
+        PhaseNoise::new(&[
+            (1.0e6, -140.0),
+            (1.0e3, -90.0),
+            (1.0e4, -100.0),
+            (1.0e5, -120.0),
+        ])
+    }
+
+    #[test]
+    fn new_sorts_points() {
+        let pn = profile();
+        assert_eq!(pn.offset_range_hz(), Some((1.0e3, 1.0e6)));
+        assert_eq!(pn.points[1].level_dbc_per_hz, -100.0);
+    }
+
+    #[test]
+    fn level_interpolates_on_log_frequency() {
+        let pn = profile();
+        assert_eq!(pn.level_at(1.0e4), Some(-100.0));
+        // halfway through the 10-100 kHz decade
+        let mid = pn.level_at(10.0_f64.powf(4.5)).unwrap();
+        assert!((mid - (-110.0)).abs() < 1e-9);
+        assert!(pn.level_at(100.0).is_none());
+        assert!(pn.level_at(2.0e6).is_none());
+        assert!(PhaseNoise::default().level_at(1.0e3).is_none());
+    }
+
+    #[test]
+    fn multiplier_and_divider_scale_by_20_log_n() {
+        let pn = profile();
+        let x4 = pn.multiplied(4.0);
+        assert!((x4.level_at(1.0e3).unwrap() - (-90.0 + 12.041)).abs() < 1e-3);
+        let div2 = pn.divided(2.0);
+        assert!((div2.level_at(1.0e3).unwrap() - (-90.0 - 6.0206)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn integration_matches_numerical_sum() {
+        let pn = profile();
+        let exact = pn.integrated_linear(1.0e3, 1.0e6).unwrap();
+        // trapezoids on a fine log grid
+        let steps = 20_000;
+        let mut numerical = 0.0;
+        for i in 0..steps {
+            let f0 = 1.0e3 * 1000.0_f64.powf(i as f64 / steps as f64);
+            let f1 = 1.0e3 * 1000.0_f64.powf((i + 1) as f64 / steps as f64);
+            let l0 = rfconversions::power::db_to_linear(pn.clamped_level(f0));
+            let l1 = rfconversions::power::db_to_linear(pn.clamped_level(f1));
+            numerical += 0.5 * (l0 + l1) * (f1 - f0);
+        }
+        assert!((exact - numerical).abs() / numerical < 1e-6);
+    }
+
+    #[test]
+    fn integration_over_a_partial_range() {
+        // -10 dB/decade is a 1/f law, which integrates to L·f·ln(ratio)
+        let pn = PhaseNoise::new(&[(1.0e3, -100.0), (1.0e5, -120.0)]);
+        let integrated = pn.integrated_linear(1.0e3, 1.0e4).unwrap();
+        assert!((integrated - 1.0e-10 * 1.0e3 * 10.0_f64.ln()).abs() < 1e-18);
+        assert!(pn.integrated_dbc(1.0e4, 1.0e3).is_none());
+        assert!(pn.integrated_dbc(100.0, 1.0e4).is_none());
+    }
+
+    #[test]
+    fn jitter_from_phase_error() {
+        let pn = profile();
+        let rad = pn.rms_phase_error_rad(1.0e3, 1.0e6).unwrap();
+        let jitter = pn.rms_jitter_s(2.0e9, 1.0e3, 1.0e6).unwrap();
+        assert!((jitter - rad / (2.0 * std::f64::consts::PI * 2.0e9)).abs() < 1e-24);
+        assert!((pn.rms_phase_error_deg(1.0e3, 1.0e6).unwrap() - rad.to_degrees()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn combined_profiles_add_in_power() {
+        let a = PhaseNoise::new(&[(1.0e3, -100.0), (1.0e5, -120.0)]);
+        let b = PhaseNoise::new(&[(1.0e4, -150.0), (1.0e6, -150.0)]);
+        let sum = a.combined(&b);
+        assert_eq!(sum.offset_range_hz(), Some((1.0e3, 1.0e6)));
+        // a holds -120 dBc/Hz beyond 100 kHz, b adds -150 dBc/Hz
+        let far = sum.level_at(1.0e6).unwrap();
+        assert!((far - (-119.996)).abs() < 1e-3);
+        assert_eq!(
+            combine_phase_noise(Some(&a), None).unwrap(),
+            a,
+            "a single profile passes through"
+        );
+        assert!(combine_phase_noise(None, None).is_none());
+    }
+
+    #[test]
+    fn display_phase_noise() {
+        let s = format!("{}", PhaseNoise::new(&[(1.0e3, -90.0), (1.0e6, -140.0)]));
+        assert_eq!(
+            s,
+            "PhaseNoise { -90 dBc/Hz @ 1000 Hz, -140 dBc/Hz @ 1000000 Hz }"
+        );
+    }
+}
@@ -96,8 +96,9 @@ pub fn generate_html_table(
     writeln!(file, "<tr>")?;
     writeln!(file, "<th colspan=\"1\">Identity</th>")?;
     writeln!(file, "<th colspan=\"4\">Block Definition</th>")?;
+    writeln!(file, "<th colspan=\"3\">Frequency</th>")?;
     writeln!(file, "<th colspan=\"3\">Signal Power</th>")?;
-    writeln!(file, "<th colspan=\"2\">Cumulative Stats</th>")?;
+    writeln!(file, "<th colspan=\"3\">Cumulative Stats</th>")?;
     writeln!(file, "<th colspan=\"6\">Noise Analysis</th>")?;
     writeln!(file, "<th colspan=\"1\">Signal Quality</th>")?;
     writeln!(file, "</tr>")?;
@@ -107,11 +108,15 @@ pub fn generate_html_table(
     writeln!(file, "<th>Gain (dB)</th>")?;
     writeln!(file, "<th>NF (dB)</th>")?;
     writeln!(file, "<th>Output P1dB (dBm)</th>")?;
+    writeln!(file, "<th>Output Frequency</th>")?;
+    writeln!(file, "<th>Image Frequency</th>")?;
+    writeln!(file, "<th>Bandwidth</th>")?;
     writeln!(file, "<th>Input Power (dBm)</th>")?;
     writeln!(file, "<th>Output Power (dBm)</th>")?;
     writeln!(file, "<th>Power Gain (dB)</th>")?;
     writeln!(file, "<th>Cumulative Gain (dB)</th>")?;
     writeln!(file, "<th>Cumulative NF (dB)</th>")?;
+    writeln!(file, "<th>Cumulative P1dB (dBm)</th>")?;
     writeln!(file, "<th>Input Noise Temperature (K)</th>")?;
     writeln!(file, "<th>Output Noise Temperature (K)</th>")?;
     writeln!(file, "<th>Input Noise Spectral Density (dBm/Hz)</th>")?;
@@ -136,6 +141,17 @@ pub fn generate_html_table(
             writeln!(file, "<td>-</td>")?;
         }
 
+        let (freq_val, freq_unit) = format_hz(node.signal_frequency_hz);
+        writeln!(file, "<td>{:.2} {}</td>", freq_val, freq_unit)?;
+        if let Some(image_frequency_hz) = node.image_frequency_hz {
+            let (image_val, image_unit) = format_hz(image_frequency_hz);
+            writeln!(file, "<td>{:.2} {}</td>", image_val, image_unit)?;
+        } else {
+            writeln!(file, "<td>-</td>")?;
+        }
+        let (bw_val, bw_unit) = format_hz(node.signal_bandwidth_hz);
+        writeln!(file, "<td>{:.2} {}</td>", bw_val, bw_unit)?;
+
         let actual_input_power = if i == 0 {
             input.power_dbm
         } else {
@@ -147,6 +163,11 @@ pub fn generate_html_table(
         writeln!(file, "<td>{:.2}</td>", block.power_gain(actual_input_power))?;
         writeln!(file, "<td>{:.2}</td>", node.cumulative_gain_db)?;
         writeln!(file, "<td>{:.2}</td>", node.cumulative_noise_figure_db)?;
+        if let Some(p1db) = node.output_p1db_dbm {
+            writeln!(file, "<td>{:.2}</td>", p1db)?;
+        } else {
+            writeln!(file, "<td>-</td>")?;
+        }
 
         // input noise temperature
         if i == 0 {
@@ -190,6 +211,86 @@ pub fn generate_html_table(
         writeln!(file, "</tr>")?;
     }
 
+    writeln!(file, "</table>")?;
+    writeln!(file, "<br>")?;
+
+    // one row per contributor (source first), one column per node; stages after a node are blank
+    writeln!(file, "<h2>Noise Contributions (input-referred)</h2>")?;
+    writeln!(file, "<table class=\"cascade\">")?;
+    writeln!(file, "<tr>")?;
+    writeln!(file, "<th>Contributor</th>")?;
+    for node in cascade {
+        writeln!(file, "<th>{}</th>", node.name)?;
+    }
+    writeln!(file, "</tr>")?;
+    let contributors = cascade
+        .last()
+        .map(|node| node.noise_contributions.as_slice())
+        .unwrap_or_default();
+    for (row, contributor) in contributors.iter().enumerate() {
+        writeln!(file, "<tr>")?;
+        writeln!(file, "<td>{}</td>", contributor.name)?;
+        for node in cascade {
+            if let Some(contribution) = node.noise_contributions.get(row) {
+                writeln!(
+                    file,
+                    "<td>{:.2} K ({:.1}%)</td>",
+                    contribution.noise_temperature_k, contribution.percent
+                )?;
+            } else {
+                writeln!(file, "<td>-</td>")?;
+            }
+        }
+        writeln!(file, "</tr>")?;
+    }
+    writeln!(file, "</table>")?;
+    writeln!(file, "<br>")?;
+
+    // share of the cascaded 1/OIP3 and 1/P1dB, laid out like the noise contributions
+    writeln!(file, "<h2>Linearity Contributions (OIP3 % / P1dB %)</h2>")?;
+    writeln!(file, "<table class=\"cascade\">")?;
+    writeln!(file, "<tr>")?;
+    writeln!(file, "<th>Stage</th>")?;
+    for node in cascade {
+        writeln!(file, "<th>{}</th>", node.name)?;
+    }
+    writeln!(file, "</tr>")?;
+    let format_percent = |percent: Option<f64>| match percent {
+        Some(percent) => format!("{:.1}%", percent),
+        None => "-".to_string(),
+    };
+    let stages = cascade
+        .last()
+        .map(|node| node.linearity_contributions.as_slice())
+        .unwrap_or_default();
+    for (row, stage) in stages.iter().enumerate() {
+        writeln!(file, "<tr>")?;
+        writeln!(file, "<td>{}</td>", stage.name)?;
+        for node in cascade {
+            if let Some(contribution) = node.linearity_contributions.get(row) {
+                writeln!(
+                    file,
+                    "<td>{} / {}</td>",
+                    format_percent(contribution.oip3_percent),
+                    format_percent(contribution.p1db_percent)
+                )?;
+            } else {
+                writeln!(file, "<td>-</td>")?;
+            }
+        }
+        writeln!(file, "</tr>")?;
+    }
+    writeln!(file, "<tr>")?;
+    writeln!(file, "<th>Limiting Stage</th>")?;
+    for node in cascade {
+        writeln!(
+            file,
+            "<th>{} / {}</th>",
+            node.oip3_limiting_stage().unwrap_or("-"),
+            node.p1db_limiting_stage.as_deref().unwrap_or("-")
+        )?;
+    }
+    writeln!(file, "</tr>")?;
     writeln!(file, "</table>")?;
     writeln!(file, "</body>")?;
     writeln!(file, "</html>")?;
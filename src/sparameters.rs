@@ -0,0 +1,680 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::block::Block;
+
+/// A complex number, used for S-parameters and reflection coefficients.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::Complex;
+///
+/// let gamma = Complex::from_polar_deg(0.5, 90.0);
+/// assert!(gamma.re.abs() < 1e-12);
+/// assert!((gamma.im - 0.5).abs() < 1e-12);
+/// assert!(((gamma * gamma).re - (-0.25)).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex {
+    /// Real part.
+    pub re: f64,
+    /// Imaginary part.
+    pub im: f64,
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.im < 0.0 {
+            write!(f, "{} - {}j", self.re, -self.im)
+        } else {
+            write!(f, "{} + {}j", self.re, self.im)
+        }
+    }
+}
+
+impl Complex {
+    /// `0 + 0j`.
+    pub const ZERO: Complex = Complex { re: 0.0, im: 0.0 };
+    /// `1 + 0j`.
+    pub const ONE: Complex = Complex { re: 1.0, im: 0.0 };
+
+    /// Create a complex number from its real and imaginary parts.
+    #[must_use]
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Create a complex number from a magnitude and a phase in degrees.
+    #[must_use]
+    pub fn from_polar_deg(magnitude: f64, phase_deg: f64) -> Self {
+        let phase = phase_deg.to_radians();
+        Self::new(magnitude * phase.cos(), magnitude * phase.sin())
+    }
+
+    /// Create a voltage ratio from a magnitude in dB (20·log10) and a phase in degrees.
+    #[must_use]
+    pub fn from_db_deg(magnitude_db: f64, phase_deg: f64) -> Self {
+        Self::from_polar_deg(10.0_f64.powf(magnitude_db / 20.0), phase_deg)
+    }
+
+    /// Magnitude `|z|`.
+    #[must_use]
+    pub fn abs(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Magnitude in dB (20·log10|z|).
+    #[must_use]
+    pub fn abs_db(&self) -> f64 {
+        20.0 * self.abs().log10()
+    }
+
+    /// Phase in degrees.
+    #[must_use]
+    pub fn arg_deg(&self) -> f64 {
+        self.im.atan2(self.re).to_degrees()
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, rhs: Complex) -> Complex {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+        Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / denominator,
+            (self.im * rhs.re - self.re * rhs.im) / denominator,
+        )
+    }
+}
+
+/// Reflection coefficient magnitude `|Γ| = (VSWR − 1) / (VSWR + 1)`.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::reflection_coefficient_from_vswr;
+///
+/// assert!((reflection_coefficient_from_vswr(2.0) - 1.0 / 3.0).abs() < 1e-12);
+/// assert_eq!(reflection_coefficient_from_vswr(1.0), 0.0);
+/// ```
+#[doc(alias = "gamma")]
+#[must_use]
+pub fn reflection_coefficient_from_vswr(vswr: f64) -> f64 {
+    (vswr - 1.0) / (vswr + 1.0)
+}
+
+/// VSWR `(1 + |Γ|) / (1 − |Γ|)` for a reflection coefficient magnitude.
+#[must_use]
+pub fn vswr_from_reflection_coefficient(gamma: f64) -> f64 {
+    (1.0 + gamma) / (1.0 - gamma)
+}
+
+/// Two-port S-parameters at one frequency, referenced to the system impedance.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::SParameters;
+///
+/// // two matched 10 dB amplifiers
+/// let amp = SParameters::from_gain_and_vswr(10.0, None, None);
+/// let cascade = amp.cascade(&amp);
+/// assert!((cascade.gain_db() - 20.0).abs() < 1e-9);
+///
+/// // a VSWR 2:1 output into a VSWR 2:1 input, with the reflections in phase
+/// let a = SParameters::from_gain_and_vswr(10.0, None, Some(2.0));
+/// let b = SParameters::from_gain_and_vswr(10.0, Some(2.0), None);
+/// let cascade = a.cascade(&b);
+/// assert!((cascade.gain_db() - (20.0 + 1.023)).abs() < 1e-3);
+/// ```
+#[doc(alias = "S11")]
+#[doc(alias = "S21")]
+#[doc(alias = "two-port")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SParameters {
+    /// Input reflection coefficient.
+    pub s11: Complex,
+    /// Reverse transmission.
+    pub s12: Complex,
+    /// Forward transmission.
+    pub s21: Complex,
+    /// Output reflection coefficient.
+    pub s22: Complex,
+}
+
+impl Default for SParameters {
+    /// A matched, lossless through connection.
+    fn default() -> Self {
+        Self {
+            s11: Complex::ZERO,
+            s12: Complex::ONE,
+            s21: Complex::ONE,
+            s22: Complex::ZERO,
+        }
+    }
+}
+
+impl fmt::Display for SParameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SParameters {{ s11: {:.2} dB, s21: {:.2} dB, s12: {:.2} dB, s22: {:.2} dB }}",
+            self.s11.abs_db(),
+            self.s21.abs_db(),
+            self.s12.abs_db(),
+            self.s22.abs_db()
+        )
+    }
+}
+
+impl SParameters {
+    /// A unilateral (S12 = 0) two-port with real port reflections set from VSWR specs.
+    ///
+    /// Ports without a VSWR are matched. The phase of S21 and of the reflections is 0°,
+    /// since a datasheet VSWR does not give it.
+    #[must_use]
+    pub fn from_gain_and_vswr(
+        gain_db: f64,
+        input_vswr: Option<f64>,
+        output_vswr: Option<f64>,
+    ) -> Self {
+        let gamma = |vswr: Option<f64>| {
+            Complex::new(vswr.map_or(0.0, reflection_coefficient_from_vswr), 0.0)
+        };
+        Self {
+            s11: gamma(input_vswr),
+            s12: Complex::ZERO,
+            s21: Complex::from_db_deg(gain_db, 0.0),
+            s22: gamma(output_vswr),
+        }
+    }
+
+    /// S-parameters of `self` followed by `next`, including the multiple reflections
+    /// between them.
+    #[must_use]
+    pub fn cascade(&self, next: &SParameters) -> SParameters {
+        let a = self;
+        let b = next;
+        // 1 / (1 − Γ_out,a · Γ_in,b) sums the reflections bouncing between the two
+        let loop_gain = Complex::ONE / (Complex::ONE - a.s22 * b.s11);
+        SParameters {
+            s11: a.s11 + a.s12 * b.s11 * a.s21 * loop_gain,
+            s12: a.s12 * b.s12 * loop_gain,
+            s21: a.s21 * b.s21 * loop_gain,
+            s22: b.s22 + b.s21 * a.s22 * b.s12 * loop_gain,
+        }
+    }
+
+    /// The two-port from `input_port` to `output_port` (1-based) of an N-port S-matrix.
+    ///
+    /// Every other port is terminated in a load with reflection coefficient
+    /// `termination_reflection` ([`Complex::ZERO`] for the reference impedance), and the
+    /// reflections from those loads are folded into the result. Swapping the ports uses
+    /// the network in the reverse direction.
+    ///
+    /// Returns `None` if a port is out of range, the ports are equal, `matrix` is not
+    /// square, or the terminations make the network singular.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Complex, SParameters};
+    ///
+    /// // an ideal 3 dB splitter: port 1 splits equally to ports 2 and 3
+    /// let k = Complex::new(1.0 / 2.0_f64.sqrt(), 0.0);
+    /// let z = Complex::ZERO;
+    /// let splitter = vec![vec![z, k, k], vec![k, z, z], vec![k, z, z]];
+    ///
+    /// let path = SParameters::from_multiport(&splitter, 1, 3, Complex::ZERO).unwrap();
+    /// assert!((path.gain_db() - (-3.0103)).abs() < 1e-4);
+    ///
+    /// // reverse direction, port 3 to port 1
+    /// let reverse = SParameters::from_multiport(&splitter, 3, 1, Complex::ZERO).unwrap();
+    /// assert!((reverse.gain_db() - path.gain_db()).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn from_multiport(
+        matrix: &[Vec<Complex>],
+        input_port: usize,
+        output_port: usize,
+        termination_reflection: Complex,
+    ) -> Option<SParameters> {
+        let n = matrix.len();
+        if matrix.iter().any(|row| row.len() != n)
+            || input_port == output_port
+            || !(1..=n).contains(&input_port)
+            || !(1..=n).contains(&output_port)
+        {
+            return None;
+        }
+        let kept = [input_port - 1, output_port - 1];
+        let unused: Vec<usize> = (0..n).filter(|port| !kept.contains(port)).collect();
+        let s = |j: usize, k: usize| matrix[j][k];
+
+        // b_u = (I − Γ·S_uu)⁻¹ · S_uk · a_k, so the kept ports see
+        // S_kk + Γ·S_ku · (I − Γ·S_uu)⁻¹ · S_uk
+        let system: Vec<Vec<Complex>> = unused
+            .iter()
+            .map(|&j| {
+                unused
+                    .iter()
+                    .map(|&k| {
+                        let identity = if j == k { Complex::ONE } else { Complex::ZERO };
+                        identity - termination_reflection * s(j, k)
+                    })
+                    .collect()
+            })
+            .collect();
+        let right_hand_side: Vec<Vec<Complex>> = unused
+            .iter()
+            .map(|&j| kept.iter().map(|&k| s(j, k)).collect())
+            .collect();
+        let reflected = solve(system, right_hand_side)?;
+
+        let reduced = |row: usize, column: usize| {
+            unused
+                .iter()
+                .enumerate()
+                .fold(s(kept[row], kept[column]), |sum, (i, &u)| {
+                    sum + termination_reflection * s(kept[row], u) * reflected[i][column]
+                })
+        };
+        Some(SParameters {
+            s11: reduced(0, 0),
+            s12: reduced(0, 1),
+            s21: reduced(1, 0),
+            s22: reduced(1, 1),
+        })
+    }
+
+    /// Transducer gain `|S21|²` in dB between matched source and load.
+    #[must_use]
+    pub fn gain_db(&self) -> f64 {
+        self.s21.abs_db()
+    }
+
+    /// Input VSWR from |S11|.
+    #[must_use]
+    pub fn input_vswr(&self) -> f64 {
+        vswr_from_reflection_coefficient(self.s11.abs())
+    }
+
+    /// Output VSWR from |S22|.
+    #[must_use]
+    pub fn output_vswr(&self) -> f64 {
+        vswr_from_reflection_coefficient(self.s22.abs())
+    }
+}
+
+/// Solve `a · x = b` by Gaussian elimination with partial pivoting, `None` if `a` is singular.
+fn solve(mut a: Vec<Vec<Complex>>, mut b: Vec<Vec<Complex>>) -> Option<Vec<Vec<Complex>>> {
+    let n = a.len();
+    for column in 0..n {
+        let pivot =
+            (column..n).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() < 1e-15 {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+        let pivot_row = a[column].clone();
+        let pivot_rhs = b[column].clone();
+        for row in column + 1..n {
+            let factor = a[row][column] / pivot_row[column];
+            for (value, pivot) in a[row].iter_mut().zip(&pivot_row).skip(column) {
+                *value = *value - factor * *pivot;
+            }
+            for (value, pivot) in b[row].iter_mut().zip(&pivot_rhs) {
+                *value = *value - factor * *pivot;
+            }
+        }
+    }
+    for column in (0..n).rev() {
+        for k in 0..b[column].len() {
+            let mut value = b[column][k];
+            for j in column + 1..n {
+                value = value - a[column][j] * b[j][k];
+            }
+            b[column][k] = value / a[column][column];
+        }
+    }
+    Some(b)
+}
+
+/// Mismatch between the output of one block and the input of the next.
+///
+/// `mismatch_db` is the change in cascaded gain caused by the reflections at this
+/// interface (using the S-parameter phases). When only VSWRs are known the true phase is
+/// unknown, so the gain change lies somewhere in `uncertainty_min_db..=uncertainty_max_db`,
+/// i.e. `−20·log10(1 ± |Γ_source|·|Γ_load|)`.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Block, mismatch_interfaces};
+///
+/// let amp = Block {
+///     name: "Amp".to_string(),
+///     gain_db: 20.0,
+///     noise_figure_db: 3.0,
+///     output_vswr: Some(2.0),
+///     ..Default::default()
+/// };
+/// let filter = Block {
+///     name: "Filter".to_string(),
+///     gain_db: -1.0,
+///     noise_figure_db: 1.0,
+///     input_vswr: Some(1.5),
+///     ..Default::default()
+/// };
+/// let interfaces = mismatch_interfaces(&[amp, filter]);
+/// assert_eq!(interfaces.len(), 1);
+/// let interface = &interfaces[0];
+/// assert!((interface.uncertainty_max_db - 0.60).abs() < 0.01);
+/// assert!((interface.uncertainty_min_db - (-0.56)).abs() < 0.01);
+/// ```
+#[doc(alias = "mismatch uncertainty")]
+#[doc(alias = "VSWR")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MismatchInterface {
+    /// Block driving the interface.
+    pub from: String,
+    /// Block loading the interface.
+    pub to: String,
+    /// |S22| of the driving block.
+    pub source_reflection: f64,
+    /// |S11| of the loading block.
+    pub load_reflection: f64,
+    /// Change in cascaded gain due to this interface in dB.
+    pub mismatch_db: f64,
+    /// Lowest possible gain change over all reflection phases in dB.
+    pub uncertainty_min_db: f64,
+    /// Highest possible gain change over all reflection phases in dB.
+    pub uncertainty_max_db: f64,
+}
+
+impl fmt::Display for MismatchInterface {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MismatchInterface {{ {} -> {}: mismatch: {:.3} dB, uncertainty: {:.3} to {:.3} dB }}",
+            self.from, self.to, self.mismatch_db, self.uncertainty_min_db, self.uncertainty_max_db
+        )
+    }
+}
+
+/// S-parameters of a whole lineup, from each block's [`Block::s_parameters`] or VSWR specs.
+///
+/// Returns a matched through connection for an empty lineup.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Block, cascade_s_parameters};
+///
+/// let pad = Block { name: "Pad".to_string(), gain_db: -3.0, noise_figure_db: 3.0, ..Default::default() };
+/// let s = cascade_s_parameters(&[pad.clone(), pad]);
+/// assert!((s.gain_db() - (-6.0)).abs() < 1e-9);
+/// ```
+#[must_use]
+pub fn cascade_s_parameters(blocks: &[Block]) -> SParameters {
+    blocks
+        .iter()
+        .map(Block::two_port)
+        .fold(None, |cascade: Option<SParameters>, s| {
+            Some(match cascade {
+                Some(cascade) => cascade.cascade(&s),
+                None => s,
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Per-interface mismatch of a lineup, one entry per pair of adjacent blocks.
+#[must_use]
+pub fn mismatch_interfaces(blocks: &[Block]) -> Vec<MismatchInterface> {
+    let gains_db = cascaded_gains_db(blocks);
+    blocks
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let source = pair[0].two_port();
+            let load = pair[1].two_port();
+            let source_reflection = source.s22.abs();
+            let load_reflection = load.s11.abs();
+            let product = source_reflection * load_reflection;
+            MismatchInterface {
+                from: pair[0].name.clone(),
+                to: pair[1].name.clone(),
+                source_reflection,
+                load_reflection,
+                mismatch_db: gains_db[i + 1] - gains_db[i] - load.gain_db(),
+                uncertainty_min_db: -20.0 * (1.0 + product).log10(),
+                uncertainty_max_db: -20.0 * (1.0 - product).log10(),
+            }
+        })
+        .collect()
+}
+
+/// Copies of `blocks` whose gains include the mismatch at their input.
+///
+/// Block `k`'s gain becomes the change in `|S21|²` of the cascade when it is added, so the
+/// gains still add up to the lineup's transducer gain. Noise figures are left unchanged.
+#[must_use]
+pub(crate) fn mismatch_adjusted_blocks(blocks: &[Block]) -> Vec<Block> {
+    let gains_db = cascaded_gains_db(blocks);
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(i, block)| {
+            let previous_db = if i == 0 { 0.0 } else { gains_db[i - 1] };
+            Block {
+                gain_db: gains_db[i] - previous_db,
+                ..block.clone()
+            }
+        })
+        .collect()
+}
+
+/// Cascaded `|S21|²` in dB after each block.
+fn cascaded_gains_db(blocks: &[Block]) -> Vec<f64> {
+    let mut cascade: Option<SParameters> = None;
+    blocks
+        .iter()
+        .map(|block| {
+            let s = block.two_port();
+            let next = match &cascade {
+                Some(cascade) => cascade.cascade(&s),
+                None => s,
+            };
+            cascade = Some(next);
+            next.gain_db()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(name: &str, gain_db: f64, input_vswr: Option<f64>, output_vswr: Option<f64>) -> Block {
+        Block {
+            name: name.to_string(),
+            gain_db,
+            noise_figure_db: 3.0,
+            input_vswr,
+            output_vswr,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn complex_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+        assert_eq!(a + b, Complex::new(4.0, 1.0));
+        assert_eq!(a - b, Complex::new(-2.0, 3.0));
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        let q = (a * b) / b;
+        assert!((q.re - 1.0).abs() < 1e-12 && (q.im - 2.0).abs() < 1e-12);
+        assert!((Complex::from_db_deg(-6.0206, 0.0).abs() - 0.5).abs() < 1e-4);
+        assert_eq!(format!("{}", Complex::new(1.0, -2.0)), "1 - 2j");
+    }
+
+    #[test]
+    fn vswr_round_trip() {
+        for vswr in [1.0, 1.5, 2.0, 3.0] {
+            let gamma = reflection_coefficient_from_vswr(vswr);
+            assert!((vswr_from_reflection_coefficient(gamma) - vswr).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn matched_blocks_have_no_mismatch() {
+        let blocks = vec![block("A", 10.0, None, None), block("B", -3.0, None, None)];
+        let interfaces = mismatch_interfaces(&blocks);
+        assert!(interfaces[0].mismatch_db.abs() < 1e-12);
+        assert_eq!(interfaces[0].uncertainty_min_db, 0.0);
+        assert_eq!(interfaces[0].uncertainty_max_db, 0.0);
+        assert!((cascade_s_parameters(&blocks).gain_db() - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn in_phase_reflections_hit_the_upper_bound() {
+        // real, positive reflections on both sides add in phase
+        let blocks = vec![
+            block("A", 10.0, None, Some(3.0)),
+            block("B", 10.0, Some(3.0), None),
+        ];
+        let interface = &mismatch_interfaces(&blocks)[0];
+        assert!((interface.mismatch_db - interface.uncertainty_max_db).abs() < 1e-9);
+        // |Γ| = 0.5 on both sides: −20·log10(1 ∓ 0.25)
+        assert!((interface.uncertainty_max_db - 2.499).abs() < 1e-3);
+        assert!((interface.uncertainty_min_db - (-1.938)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn measured_s_parameters_take_precedence() {
+        // a quarter-wave line turns the reflection around, so it subtracts
+        let through = SParameters {
+            s11: Complex::new(-0.5, 0.0),
+            s12: Complex::ONE,
+            s21: Complex::ONE,
+            s22: Complex::new(-0.5, 0.0),
+        };
+        let line = Block {
+            s_parameters: Some(through),
+            ..block("Line", 0.0, Some(1.0), Some(1.0))
+        };
+        let amp = block("Amp", 10.0, Some(3.0), None);
+        let interface = &mismatch_interfaces(&[line, amp])[0];
+        assert_eq!(interface.source_reflection, 0.5);
+        assert!((interface.mismatch_db - interface.uncertainty_min_db).abs() < 1e-9);
+    }
+
+    #[test]
+    fn adjusted_gains_add_up_to_cascade() {
+        let blocks = vec![
+            block("A", 20.0, Some(1.5), Some(2.0)),
+            block("B", -2.0, Some(1.8), Some(1.3)),
+            block("C", 15.0, Some(2.5), Some(1.2)),
+        ];
+        let adjusted = mismatch_adjusted_blocks(&blocks);
+        let total: f64 = adjusted.iter().map(|b| b.gain_db).sum();
+        assert!((total - cascade_s_parameters(&blocks).gain_db()).abs() < 1e-9);
+        // the first block only sees its own S21 (the source is matched)
+        assert!((adjusted[0].gain_db - 20.0).abs() < 1e-9);
+        let interfaces = mismatch_interfaces(&blocks);
+        assert!((adjusted[1].gain_db - (-2.0 + interfaces[0].mismatch_db)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn multiport_with_matched_terminations_is_a_submatrix() {
+        let matrix: Vec<Vec<Complex>> = (0..3)
+            .map(|j| {
+                (0..3)
+                    .map(|k| Complex::new(0.1 * (j * 3 + k) as f64, 0.01 * j as f64))
+                    .collect()
+            })
+            .collect();
+        let path = SParameters::from_multiport(&matrix, 3, 2, Complex::ZERO).unwrap();
+        assert_eq!(path.s11, matrix[2][2]);
+        assert_eq!(path.s21, matrix[1][2]);
+        assert_eq!(path.s12, matrix[2][1]);
+        assert_eq!(path.s22, matrix[1][1]);
+
+        assert!(SParameters::from_multiport(&matrix, 1, 1, Complex::ZERO).is_none());
+        assert!(SParameters::from_multiport(&matrix, 1, 4, Complex::ZERO).is_none());
+        assert!(SParameters::from_multiport(&matrix, 0, 2, Complex::ZERO).is_none());
+    }
+
+    #[test]
+    fn multiport_termination_reflections_are_folded_in() {
+        // a through path (1 -> 2) with a 3rd port that couples back to port 1 only:
+        // S11' = S11 + S13·Γ·S31 / (1 − Γ·S33)
+        let z = Complex::ZERO;
+        let c = Complex::new(0.5, 0.0);
+        let matrix = vec![
+            vec![z, Complex::ONE, c],
+            vec![Complex::ONE, z, z],
+            vec![c, z, Complex::new(0.2, 0.0)],
+        ];
+        let gamma = Complex::new(-1.0, 0.0); // short
+        let path = SParameters::from_multiport(&matrix, 1, 2, gamma).unwrap();
+        let expected = -0.25 / (1.0 + 0.2);
+        assert!((path.s11.re - expected).abs() < 1e-12);
+        assert!(path.s11.im.abs() < 1e-12);
+        assert_eq!(path.s21, Complex::ONE);
+
+        // four ports, two of them terminated
+        let matrix: Vec<Vec<Complex>> = (0..4)
+            .map(|j| {
+                (0..4)
+                    .map(|k| {
+                        if j == k {
+                            Complex::new(0.1, 0.0)
+                        } else {
+                            Complex::new(0.3, 0.1)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let open = SParameters::from_multiport(&matrix, 1, 2, Complex::ONE).unwrap();
+        let matched = SParameters::from_multiport(&matrix, 1, 2, Complex::ZERO).unwrap();
+        assert_ne!(open.s21, matched.s21);
+    }
+
+    #[test]
+    fn display_s_parameters() {
+        let s = SParameters::from_gain_and_vswr(10.0, Some(2.0), None);
+        let text = format!("{}", s);
+        assert!(text.contains("s21: 10.00 dB"), "{}", text);
+        assert!(text.contains("s11: -9.54 dB"), "{}", text);
+    }
+}
@@ -0,0 +1,522 @@
+use crate::interpolation::SParameterPoint;
+use crate::noise_parameters::NoiseParameters;
+use crate::sparameters::Complex;
+
+/// The contents of a Touchstone (`.sNp`) file: S-parameters at each file frequency and,
+/// for a 2-port, its noise parameters.
+///
+/// Touchstone 1 and 2 files are read, with data entries spread over any number of lines.
+/// 2-port entries are `N11 N21 N12 N22` unless `[Two-Port Data Order] 12_21` says
+/// otherwise; larger networks are row-major. Touchstone 1 noise data starts at the first
+/// 5-value line whose frequency is not above the last network frequency; Touchstone 2
+/// noise data follows the `[Noise Data]` keyword. Noise parameters are
+/// `frequency Fmin(dB) |Γopt| ∠Γopt(deg) Rn/Z0` in every format. A `[Reference]` line
+/// (which may continue onto the next lines) replaces the option line's impedance, as long
+/// as every port has the same one. Mixed-mode data, other matrix formats and unknown
+/// keywords are errors.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::TouchstoneNetwork;
+///
+/// let content = "# MHz S DB R 50\n100 -20 0 15 90 -30 0 -18 0\n200 -20 0 14 80 -30 0 -18 0\n";
+/// let network = TouchstoneNetwork::parse(content, 2).unwrap();
+/// assert_eq!(network.frequencies_hz, vec![100.0e6, 200.0e6]);
+/// assert!((network.s_parameter_points(2, 1)[1].magnitude_db - 14.0).abs() < 1e-9);
+/// assert!(TouchstoneNetwork::parse("# MHz S DB R 50\n100 -20 0 15\n", 2).is_err());
+/// ```
+#[doc(alias = "s2p")]
+#[doc(alias = "sNp")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TouchstoneNetwork {
+    /// Number of ports.
+    pub port_count: usize,
+    /// Reference impedance in ohms from the option line.
+    pub z0: f64,
+    /// File frequencies in Hz, in file order.
+    pub frequencies_hz: Vec<f64>,
+    /// The S-matrix at each file frequency, indexed `[j][k]` from 0 for S<sub>(j+1)(k+1)</sub>.
+    pub s_matrices: Vec<Vec<Vec<Complex>>>,
+    /// Noise parameters, sorted by frequency (empty if the file has none).
+    pub noise: Vec<NoiseParameters>,
+}
+
+/// Option line values: Hz per frequency unit, data format and reference impedance.
+struct Options {
+    frequency_scale: f64,
+    format: Format,
+    z0: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        // GHz S MA R 50 is the Touchstone default
+        Self {
+            frequency_scale: 1.0e9,
+            format: Format::MagnitudeAngle,
+            z0: 50.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    RealImaginary,
+    MagnitudeAngle,
+    DecibelAngle,
+}
+
+impl Format {
+    fn complex(self, a: f64, b: f64) -> Complex {
+        match self {
+            Format::RealImaginary => Complex::new(a, b),
+            Format::MagnitudeAngle => Complex::from_polar_deg(a, b),
+            Format::DecibelAngle => Complex::from_db_deg(a, b),
+        }
+    }
+}
+
+impl TouchstoneNetwork {
+    /// Parse the contents of a Touchstone file for a network of `port_count` ports.
+    ///
+    /// Returns a description of the first problem for malformed contents.
+    pub fn parse(content: &str, port_count: usize) -> Result<Self, String> {
+        if port_count == 0 {
+            return Err("a network needs at least one port".to_string());
+        }
+        let entry_values = 1 + 2 * port_count * port_count;
+        let mut options: Option<Options> = None;
+        let mut network = TouchstoneNetwork {
+            port_count,
+            ..Default::default()
+        };
+        let mut values: Vec<f64> = vec![];
+        let mut noise_values: Vec<Vec<f64>> = vec![];
+        let mut in_noise = false;
+        let mut order_12_21 = false;
+        let mut expected_frequencies: Option<usize> = None;
+        let mut expected_noise_frequencies: Option<usize> = None;
+        let mut in_information = false;
+        // [Reference] impedances, which may continue onto the following lines
+        let mut references: Option<Vec<f64>> = None;
+
+        for line in content.lines() {
+            let data = line.split('!').next().unwrap_or("").trim();
+            if data.is_empty() {
+                continue;
+            }
+            if in_information {
+                in_information = normalize_keyword(data) != "[end information]";
+                continue;
+            }
+            if data.starts_with('#') {
+                if options.is_some() {
+                    return Err("more than one option line".to_string());
+                }
+                options = Some(parse_option_line(data)?);
+                continue;
+            }
+            if let Some(keyword) = data.strip_prefix('[') {
+                let (keyword, argument) = keyword
+                    .split_once(']')
+                    .ok_or_else(|| format!("unterminated keyword '{}'", data))?;
+                let argument = argument.trim();
+                if references.as_ref().is_some_and(|r| r.len() < port_count) {
+                    return Err(format!(
+                        "[Reference] has fewer than {} impedances",
+                        port_count
+                    ));
+                }
+                match normalize_keyword(keyword).as_str() {
+                    "version" | "network data" => {}
+                    "number of ports" if parse_count(argument)? != port_count => {
+                        return Err(format!(
+                            "[Number of Ports] {} does not match the {}-port file extension",
+                            argument, port_count
+                        ));
+                    }
+                    "two port data order" => match argument {
+                        "12_21" => order_12_21 = true,
+                        "21_12" => order_12_21 = false,
+                        _ => return Err(format!("unknown [Two-Port Data Order] {}", argument)),
+                    },
+                    "number of ports" => {}
+                    "number of frequencies" => expected_frequencies = Some(parse_count(argument)?),
+                    "number of noise frequencies" => {
+                        expected_noise_frequencies = Some(parse_count(argument)?)
+                    }
+                    "reference" => {
+                        references = Some(
+                            argument
+                                .split_whitespace()
+                                .map(parse_value)
+                                .collect::<Result<_, _>>()?,
+                        );
+                    }
+                    "matrix format" if !argument.eq_ignore_ascii_case("full") => {
+                        return Err(format!("[Matrix Format] {} is not supported", argument));
+                    }
+                    "matrix format" => {}
+                    "mixed mode order" => {
+                        return Err(
+                            "mixed-mode data ([Mixed-Mode Order]) is not supported".to_string()
+                        );
+                    }
+                    "begin information" => in_information = true,
+                    "noise data" => in_noise = true,
+                    "end" => break,
+                    _ => return Err(format!("unknown keyword '{}'", data)),
+                }
+                continue;
+            }
+
+            let line_values = data
+                .split_whitespace()
+                .map(parse_value)
+                .collect::<Result<Vec<f64>, String>>()?;
+            if let Some(references) = references.as_mut().filter(|r| r.len() < port_count) {
+                // a [Reference] line continues until every port has an impedance
+                references.extend(line_values);
+                continue;
+            }
+            if port_count == 2 && !in_noise && values.is_empty() && line_values.len() == 5 {
+                // Touchstone 1: noise data begins with a frequency at or below the last one
+                in_noise = network
+                    .frequencies_hz
+                    .last()
+                    .is_some_and(|&last| line_values[0] * scale(&options) <= last);
+            }
+            if in_noise {
+                noise_values.push(line_values);
+                continue;
+            }
+
+            values.extend(line_values);
+            if values.len() > entry_values {
+                return Err(format!(
+                    "data entry has {} values, expected {}",
+                    values.len(),
+                    entry_values
+                ));
+            }
+            if values.len() == entry_values {
+                let format = options
+                    .as_ref()
+                    .map_or(Format::MagnitudeAngle, |o| o.format);
+                network.frequencies_hz.push(values[0] * scale(&options));
+                network
+                    .s_matrices
+                    .push(s_matrix(&values[1..], port_count, format, order_12_21));
+                values.clear();
+            }
+        }
+
+        if !values.is_empty() {
+            return Err(format!(
+                "last data entry has {} values, expected {}",
+                values.len(),
+                entry_values
+            ));
+        }
+        if network.frequencies_hz.is_empty() {
+            return Err("no network data".to_string());
+        }
+        if let Some(expected) = expected_frequencies {
+            if expected != network.frequencies_hz.len() {
+                return Err(format!(
+                    "[Number of Frequencies] is {}, but the file has {}",
+                    expected,
+                    network.frequencies_hz.len()
+                ));
+            }
+        }
+        if let Some(expected) = expected_noise_frequencies {
+            if expected != noise_values.len() {
+                return Err(format!(
+                    "[Number of Noise Frequencies] is {}, but the file has {}",
+                    expected,
+                    noise_values.len()
+                ));
+            }
+        }
+
+        network.noise = noise_values
+            .into_iter()
+            .map(|values| {
+                if values.len() != 5 {
+                    return Err(format!(
+                        "noise parameter line has {} values, expected 5",
+                        values.len()
+                    ));
+                }
+                Ok(NoiseParameters {
+                    frequency_hz: values[0] * scale(&options),
+                    minimum_noise_figure_db: values[1],
+                    optimum_reflection: Complex::from_polar_deg(values[2], values[3]),
+                    normalized_noise_resistance: values[4],
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        network
+            .noise
+            .sort_by(|a, b| a.frequency_hz.total_cmp(&b.frequency_hz));
+        network.z0 = match references {
+            // [Reference] replaces the option line's R
+            Some(references) => {
+                if references.len() != port_count {
+                    return Err(format!(
+                        "[Reference] has {} impedances, expected {}",
+                        references.len(),
+                        port_count
+                    ));
+                }
+                if references.iter().any(|&z| z != references[0]) {
+                    return Err(
+                        "different reference impedances per port are not supported".to_string()
+                    );
+                }
+                references[0]
+            }
+            None => options.map_or(50.0, |o| o.z0),
+        };
+        Ok(network)
+    }
+
+    /// S<sub>jk</sub> as dB/degree points (1-based ports), sorted by frequency.
+    ///
+    /// Empty if `j` or `k` is not a port of the network.
+    #[must_use]
+    pub fn s_parameter_points(&self, j: usize, k: usize) -> Vec<SParameterPoint> {
+        if j == 0 || k == 0 || j > self.port_count || k > self.port_count {
+            return vec![];
+        }
+        let mut points: Vec<SParameterPoint> = self
+            .frequencies_hz
+            .iter()
+            .zip(&self.s_matrices)
+            .map(|(&frequency_hz, matrix)| SParameterPoint {
+                frequency_hz,
+                magnitude_db: matrix[j - 1][k - 1].abs_db(),
+                phase_deg: matrix[j - 1][k - 1].arg_deg(),
+            })
+            .collect();
+        points.sort_by(|a, b| a.frequency_hz.total_cmp(&b.frequency_hz));
+        points
+    }
+}
+
+/// The S-matrix of one data entry (without its frequency).
+fn s_matrix(
+    pairs: &[f64],
+    port_count: usize,
+    format: Format,
+    order_12_21: bool,
+) -> Vec<Vec<Complex>> {
+    let entry = |index: usize| format.complex(pairs[2 * index], pairs[2 * index + 1]);
+    (0..port_count)
+        .map(|j| {
+            (0..port_count)
+                .map(|k| match (port_count, order_12_21) {
+                    // 2-port entries are column-major by default: N11 N21 N12 N22
+                    (2, false) => entry(k * 2 + j),
+                    _ => entry(j * port_count + k),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn parse_option_line(line: &str) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut tokens = line.trim_start_matches('#').split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token.to_ascii_lowercase().as_str() {
+            "hz" => options.frequency_scale = 1.0,
+            "khz" => options.frequency_scale = 1.0e3,
+            "mhz" => options.frequency_scale = 1.0e6,
+            "ghz" => options.frequency_scale = 1.0e9,
+            "thz" => options.frequency_scale = 1.0e12,
+            "s" => {}
+            "y" | "z" | "h" | "g" => {
+                return Err(format!(
+                    "only S-parameters are supported, not {}-parameters",
+                    token.to_ascii_uppercase()
+                ))
+            }
+            "ri" => options.format = Format::RealImaginary,
+            "ma" => options.format = Format::MagnitudeAngle,
+            "db" => options.format = Format::DecibelAngle,
+            "r" => {
+                let value = tokens
+                    .next()
+                    .ok_or_else(|| "option line has no value after R".to_string())?;
+                options.z0 = parse_value(value)?;
+            }
+            _ => return Err(format!("unknown option '{}'", token)),
+        }
+    }
+    Ok(options)
+}
+
+/// Hz per unit of the option line's frequency unit.
+fn scale(options: &Option<Options>) -> f64 {
+    options
+        .as_ref()
+        .map_or(Options::default().frequency_scale, |o| o.frequency_scale)
+}
+
+fn normalize_keyword(keyword: &str) -> String {
+    keyword
+        .to_ascii_lowercase()
+        .replace('-', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_count(value: &str) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .map_err(|_| format!("'{}' is not a count", value))
+}
+
+fn parse_value(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .map_err(|_| format!("'{}' is not a number", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1: &str = "! LNA\n# GHz S MA R 50\n2.0 0.4 -120 5.6 80 0.05 60 0.3 -90\n4.0 0.5 -150 5.0 40 0.06 50 0.35 -120\n! noise\n2.0 0.45 0.55 95 0.20\n4.0 0.65 0.45 155 0.12\n";
+
+    #[test]
+    fn reads_touchstone_1_noise_data() {
+        let network = TouchstoneNetwork::parse(V1, 2).unwrap();
+        assert_eq!(network.frequencies_hz, vec![2.0e9, 4.0e9]);
+        assert_eq!(network.noise.len(), 2);
+        assert_eq!(network.noise[1].frequency_hz, 4.0e9);
+        assert_eq!(network.noise[0].minimum_noise_figure_db, 0.45);
+        assert!((network.noise[0].optimum_reflection.abs() - 0.55).abs() < 1e-12);
+        // N11 N21 N12 N22
+        assert!((network.s_matrices[0][1][0].abs() - 5.6).abs() < 1e-12);
+        assert!((network.s_matrices[0][0][1].abs() - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn file_without_noise_has_no_noise_parameters() {
+        let content = "# MHz S RI R 75\n100 0.1 0 0.9 0 0.9 0 0.1 0\n200 0.1 0 0.9 0 0.9 0 0.1 0\n";
+        let network = TouchstoneNetwork::parse(content, 2).unwrap();
+        assert!(network.noise.is_empty());
+        assert_eq!(network.z0, 75.0);
+        assert_eq!(network.s_parameter_points(2, 1).len(), 2);
+        assert!(network.s_parameter_points(3, 1).is_empty());
+    }
+
+    #[test]
+    fn reads_touchstone_2_keywords_and_order() {
+        let content = "[Version] 2.0\n# MHz S MA R 50\n[Number of Ports] 2\n[Two-Port Data Order] 12_21\n[Number of Frequencies] 1\n[Number of Noise Frequencies] 1\n[Network Data]\n1000 0.1 0 0.01 0 3.0 90 0.2 0\n[Noise Data]\n1000 1.5 0.3 45 0.25\n[End]\n";
+        let network = TouchstoneNetwork::parse(content, 2).unwrap();
+        // 12_21: S21 (3.0 ∠ 90) is the third pair
+        assert!((network.s_matrices[0][1][0].abs() - 3.0).abs() < 1e-12);
+        assert!((network.s_matrices[0][0][1].abs() - 0.01).abs() < 1e-12);
+        assert_eq!(network.noise.len(), 1);
+        assert_eq!(network.noise[0].frequency_hz, 1.0e9);
+    }
+
+    #[test]
+    fn multiport_entries_are_row_major_over_several_lines() {
+        let content = "# Hz S RI R 50\n1 11 0 12 0 13 0\n21 0 22 0 23 0\n31 0 32 0 33 0\n";
+        let network = TouchstoneNetwork::parse(content, 3).unwrap();
+        assert_eq!(network.s_matrices[0][1][2], Complex::new(23.0, 0.0));
+        assert_eq!(network.s_matrices[0][2][0], Complex::new(31.0, 0.0));
+    }
+
+    fn fixture(path: &str, port_count: usize) -> TouchstoneNetwork {
+        TouchstoneNetwork::parse(&std::fs::read_to_string(path).unwrap(), port_count).unwrap()
+    }
+
+    #[test]
+    fn reads_touchstone_1_fixtures() {
+        let network = fixture("files/touchstone_options/ntwk3.s2p", 2);
+        assert_eq!(network.frequencies_hz.len(), 91);
+        assert_eq!(network.frequencies_hz[1], 1.1e9);
+        assert_eq!(
+            network.s_matrices[0][1][0],
+            Complex::new(0.868084385, -0.218773483)
+        );
+        assert_eq!(
+            network.s_matrices[0][1][1],
+            Complex::new(0.0534813948, -0.130370589)
+        );
+        assert!(network.noise.is_empty());
+
+        let lna = fixture("files/touchstone_noise/lna.s2p", 2);
+        assert_eq!(lna.frequencies_hz.len(), 5);
+        assert_eq!(lna.noise.len(), 3);
+        assert_eq!(lna.noise[2].frequency_hz, 4.0e9);
+        assert_eq!(lna.noise[2].normalized_noise_resistance, 0.13);
+        assert!((lna.s_matrices[4][1][0].abs() - 5.13).abs() < 1e-12);
+        assert!((lna.s_matrices[4][0][1].abs() - 0.056).abs() < 1e-12);
+
+        let splitter = fixture("files/touchstone_multiport/splitter.s3p", 3);
+        assert_eq!(splitter.frequencies_hz, vec![1.0e9, 2.0e9, 3.0e9]);
+        assert!((splitter.s_matrices[2][1][2].abs() - 0.05).abs() < 1e-12);
+        assert!((splitter.s_matrices[2][2][0].arg_deg() - 179.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reads_touchstone_2_fixture() {
+        let network = fixture("files/touchstone_v2/amplifier.s2p", 2);
+        // the [Reference] continuation line and the information block are not data
+        assert_eq!(network.z0, 75.0);
+        assert_eq!(network.frequencies_hz, vec![1.0e9, 2.0e9, 3.0e9]);
+        // 12_21 order, with the 2 GHz entry continued on the next line
+        let s21 = network.s_parameter_points(2, 1);
+        assert!((s21[1].magnitude_db - 14.5).abs() < 1e-9);
+        assert!((s21[1].phase_deg - 95.0).abs() < 1e-9);
+        assert!((network.s_parameter_points(1, 2)[0].magnitude_db + 30.0).abs() < 1e-9);
+        assert!((network.s_parameter_points(2, 2)[1].magnitude_db + 15.0).abs() < 1e-9);
+        assert_eq!(network.noise.len(), 2);
+        assert_eq!(network.noise[1].minimum_noise_figure_db, 1.4);
+    }
+
+    #[test]
+    fn unsupported_touchstone_2_keywords_are_errors() {
+        let error = |content: &str| TouchstoneNetwork::parse(content, 2).unwrap_err();
+        let entry = "1.0 0 0 0 0 0 0 0 0\n";
+        assert!(error(&format!(
+            "[Version] 2.0\n# GHz S MA R 50\n[Number of Ports] 2\n[Mixed-Mode Order] D2,1 C2,1\n{}",
+            entry
+        ))
+        .contains("Mixed-Mode"));
+        assert!(error(&format!("[Reference] 50 75\n{}", entry)).contains("per port"));
+        assert!(
+            error(&format!("[Reference] 50\n[Network Data]\n{}", entry)).contains("fewer than 2")
+        );
+        assert!(error(&format!("[Network Type] S\n{}", entry)).contains("unknown keyword"));
+        assert!(error(&format!("[Number of Noise Frequencies] 1\n{}", entry)).contains("has 0"));
+    }
+
+    #[test]
+    fn malformed_contents_are_errors() {
+        let error = |content: &str| TouchstoneNetwork::parse(content, 2).unwrap_err();
+        assert!(error(
+            "# GHz S MA R 50\n2.0 0.4 -120 5.6 80 0.05 60 0.3 -90\n[Noise Data]\n2.0 0.45 0.55\n"
+        )
+        .contains("expected 5"));
+        assert!(
+            error("# GHz S MA R 50\n# Hz S RI R 50\n1.0 0 0 0 0 0 0 0 0\n")
+                .contains("more than one option line")
+        );
+        assert!(error("# GHz Z MA R 50\n1.0 0 0 0 0 0 0 0 0\n").contains("Z-parameters"));
+        assert!(error("# GHz S MA R 50\n1.0 0 0 x 0 0 0 0 0\n").contains("'x'"));
+        assert!(error("# GHz S MA R 50\n1.0 0 0 0 0\n").contains("expected 9"));
+        assert!(error("[Number of Ports] 3\n1.0 0 0 0 0 0 0 0 0\n").contains("3"));
+        assert!(error("[Number of Frequencies] 2\n1.0 0 0 0 0 0 0 0 0\n").contains("has 1"));
+        assert!(error("! nothing\n").contains("no network data"));
+    }
+}
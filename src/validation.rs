@@ -0,0 +1,968 @@
+use std::fmt;
+
+use crate::array::PhasedArray;
+use crate::block::{Block, BlockKind};
+use crate::branching::{Branch, BranchOutput, Stage};
+use crate::combiner::{Combiner, Splitter};
+use crate::input::Input;
+use crate::node::SignalNode;
+
+/// Gain above which a single stage is flagged as suspicious.
+const MAX_STAGE_GAIN_DB: f64 = 80.0;
+
+/// Largest difference in dB tolerated between a passive block's noise figure and its loss.
+const PASSIVE_NOISE_FIGURE_TOLERANCE_DB: f64 = 0.5;
+
+/// How serious a [`Diagnostic`] is.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::Severity;
+///
+/// assert!(Severity::Error > Severity::Warning);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Physically unusual, but the cascade can still be computed.
+    Warning,
+    /// The cascade results would be meaningless (NaN, infinite or negative where impossible).
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single validation finding for the input, a block or the whole lineup.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Block, Severity};
+///
+/// let block = Block {
+///     name: "LNA".to_string(),
+///     gain_db: f64::NAN,
+///     noise_figure_db: 1.0,
+///     ..Default::default()
+/// };
+/// let findings = block.validate();
+/// assert_eq!(findings[0].severity, Severity::Error);
+/// assert_eq!(findings[0].parameter, "gain_db");
+/// assert_eq!(findings[0].to_string(), "error: block 'LNA' gain_db: NaN is not a finite number");
+/// ```
+#[doc(alias = "lint")]
+#[doc(alias = "check")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// Whether this is a warning or an error.
+    pub severity: Severity,
+    /// Block the finding is about, or `None` for the input or the lineup as a whole.
+    pub block: Option<String>,
+    /// The parameter at fault, e.g. `noise_figure_db` or `bandwidth_hz`.
+    pub parameter: String,
+    /// What is wrong and, where possible, what to do about it.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.block {
+            Some(block) => write!(
+                f,
+                "{}: block '{}' {}: {}",
+                self.severity, block, self.parameter, self.message
+            ),
+            None => write!(f, "{}: {}: {}", self.severity, self.parameter, self.message),
+        }
+    }
+}
+
+impl Diagnostic {
+    fn new(
+        severity: Severity,
+        block: Option<&str>,
+        parameter: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            block: block.map(str::to_string),
+            parameter: parameter.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// `true` for [`Severity::Error`] findings.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+/// Push an error for `value` if it is NaN or infinite, returning `false` in that case.
+fn check_finite(
+    findings: &mut Vec<Diagnostic>,
+    block: Option<&str>,
+    parameter: &str,
+    value: f64,
+) -> bool {
+    if value.is_finite() {
+        return true;
+    }
+    findings.push(Diagnostic::new(
+        Severity::Error,
+        block,
+        parameter,
+        format!("{} is not a finite number", value),
+    ));
+    false
+}
+
+/// `true` if `value` is greater than zero (and so not NaN).
+fn is_positive(value: f64) -> bool {
+    value > 0.0
+}
+
+impl Block {
+    /// Check the block's parameters for impossible or suspicious values.
+    ///
+    /// Errors: non-finite gain, NF, P1dB, IP3 or IP2, a negative noise figure, a VSWR
+    /// below 1, a Rapp compression model with a smoothness that is not positive or a
+    /// saturation power at or below P1dB + 1 dB, a filter without a positive bandwidth, a mixer without a positive LO
+    /// frequency or a splitter or combiner with no ports.
+    /// Warnings: more than 80 dB of gain, a passive (lossy generic, splitter or combiner) block whose noise
+    /// figure differs from its loss by more than 0.5 dB, and an OIP3 below the OP1dB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::{Block, Severity};
+    ///
+    /// let pad = Block {
+    ///     name: "Pad".to_string(),
+    ///     gain_db: -6.0,
+    ///     noise_figure_db: 2.0,
+    ///     ..Default::default()
+    /// };
+    /// let findings = pad.validate();
+    /// assert_eq!(findings.len(), 1);
+    /// assert_eq!(findings[0].severity, Severity::Warning);
+    /// assert_eq!(findings[0].parameter, "noise_figure_db");
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut findings = vec![];
+        let name = Some(self.name.as_str());
+
+        let gain_ok = check_finite(&mut findings, name, "gain_db", self.gain_db);
+        let noise_figure_ok =
+            check_finite(&mut findings, name, "noise_figure_db", self.noise_figure_db);
+        for (parameter, value) in [
+            ("output_p1db_dbm", self.output_p1db_dbm),
+            ("output_ip3_dbm", self.output_ip3_dbm),
+            ("output_ip2_dbm", self.output_ip2_dbm),
+            ("output_h2_intercept_dbm", self.output_h2_intercept_dbm),
+            ("output_h3_intercept_dbm", self.output_h3_intercept_dbm),
+            ("am_pm_coefficient_deg_per_db", self.am_pm_coefficient_deg_per_db),
+            ("phase_deg", self.phase_deg),
+            ("group_delay_s", self.group_delay_s),
+        ] {
+            if let Some(value) = value {
+                check_finite(&mut findings, name, parameter, value);
+            }
+        }
+
+        if noise_figure_ok && self.noise_figure_db < 0.0 {
+            findings.push(Diagnostic::new(
+                Severity::Error,
+                name,
+                "noise_figure_db",
+                format!(
+                    "{} dB is negative; a noise figure is at least 0 dB",
+                    self.noise_figure_db
+                ),
+            ));
+        }
+
+        if gain_ok && self.gain_db > MAX_STAGE_GAIN_DB {
+            findings.push(Diagnostic::new(
+                Severity::Warning,
+                name,
+                "gain_db",
+                format!(
+                    "{} dB is unusually high for one stage; split it into several blocks",
+                    self.gain_db
+                ),
+            ));
+        }
+
+        let is_passive = matches!(
+            self.kind,
+            BlockKind::Generic | BlockKind::Splitter(_) | BlockKind::Combiner(_)
+        ) && self.gain_db < 0.0;
+        if gain_ok
+            && noise_figure_ok
+            && is_passive
+            && (self.noise_figure_db + self.gain_db).abs() > PASSIVE_NOISE_FIGURE_TOLERANCE_DB
+        {
+            findings.push(Diagnostic::new(
+                Severity::Warning,
+                name,
+                "noise_figure_db",
+                format!(
+                    "{:.2} dB differs from the {:.2} dB loss of a passive block; a passive block at 290 K has NF equal to its loss",
+                    self.noise_figure_db, -self.gain_db
+                ),
+            ));
+        }
+
+        if let (Some(oip3), Some(op1db)) = (self.output_ip3_dbm, self.output_p1db_dbm) {
+            if oip3 < op1db {
+                findings.push(Diagnostic::new(
+                    Severity::Warning,
+                    name,
+                    "output_ip3_dbm",
+                    format!(
+                        "{} dBm is below the {} dBm output P1dB; OIP3 is typically about 10 dB above P1dB",
+                        oip3, op1db
+                    ),
+                ));
+            }
+        }
+
+        for (parameter, vswr) in [
+            ("input_vswr", self.input_vswr),
+            ("output_vswr", self.output_vswr),
+        ] {
+            if let Some(vswr) = vswr {
+                if vswr.is_nan() || vswr < 1.0 {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        parameter,
+                        format!("{} is below 1; a VSWR is at least 1:1", vswr),
+                    ));
+                }
+            }
+        }
+
+        if let Some(problem) = self.compression_model.parameter_problem(self.output_p1db_dbm) {
+            findings.push(Diagnostic::new(
+                Severity::Error,
+                name,
+                "compression_model",
+                problem,
+            ));
+        }
+
+        match &self.kind {
+            BlockKind::Generic => {}
+            BlockKind::Filter(filter) => {
+                if !is_positive(filter.bandwidth_hz) {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        "bandwidth_hz",
+                        format!(
+                            "filter bandwidth {} Hz must be positive",
+                            filter.bandwidth_hz
+                        ),
+                    ));
+                }
+                if let Some(noise_bandwidth_hz) = filter.noise_bandwidth_hz {
+                    if !is_positive(noise_bandwidth_hz) {
+                        findings.push(Diagnostic::new(
+                            Severity::Error,
+                            name,
+                            "noise_bandwidth_hz",
+                            format!("{} Hz must be positive", noise_bandwidth_hz),
+                        ));
+                    }
+                }
+            }
+            BlockKind::Mixer(mixer) => {
+                if !is_positive(mixer.lo_frequency_hz) {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        "lo_frequency_hz",
+                        format!("{} Hz must be positive", mixer.lo_frequency_hz),
+                    ));
+                }
+            }
+            BlockKind::Splitter(Splitter { ways }) | BlockKind::Combiner(Combiner { ways, .. }) => {
+                if *ways == 0 {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        "ways",
+                        "0 ports; a splitter or combiner has at least one",
+                    ));
+                }
+            }
+            BlockKind::Adc(adc) => {
+                if !is_positive(adc.sample_rate_hz) {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        "sample_rate_hz",
+                        format!("{} Hz must be positive", adc.sample_rate_hz),
+                    ));
+                }
+                if !is_positive(adc.snr_db) {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        "snr_db",
+                        format!("{} dB must be positive", adc.snr_db),
+                    ));
+                }
+                if let Some(noise_bandwidth_hz) = adc.noise_bandwidth_hz {
+                    if !is_positive(noise_bandwidth_hz) {
+                        findings.push(Diagnostic::new(
+                            Severity::Error,
+                            name,
+                            "noise_bandwidth_hz",
+                            format!("{} Hz must be positive", noise_bandwidth_hz),
+                        ));
+                    }
+                }
+                if let Some(jitter_s) = adc.aperture_jitter_s {
+                    if jitter_s.is_nan() || jitter_s < 0.0 {
+                        findings.push(Diagnostic::new(
+                            Severity::Error,
+                            name,
+                            "aperture_jitter_s",
+                            format!("{} s is negative", jitter_s),
+                        ));
+                    }
+                }
+            }
+            BlockKind::Digital(stage) => {
+                if let Some(nco_frequency_hz) = stage.nco_frequency_hz {
+                    check_finite(&mut findings, name, "nco_frequency_hz", nco_frequency_hz);
+                }
+                if stage.decimation == 0 {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        "decimation",
+                        "0; a stage without decimation has a factor of 1",
+                    ));
+                }
+                if stage.output_bits == Some(0) {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        name,
+                        "output_bits",
+                        "0 bits; a rounded output has at least one",
+                    ));
+                }
+                if let Some(filter) = &stage.filter {
+                    if !is_positive(filter.noise_bandwidth_hz()) {
+                        findings.push(Diagnostic::new(
+                            Severity::Error,
+                            name,
+                            "noise_bandwidth_hz",
+                            format!(
+                                "filter noise bandwidth {} Hz must be positive",
+                                filter.noise_bandwidth_hz()
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+impl Input {
+    /// Check the input signal for impossible or suspicious values.
+    ///
+    /// Errors: non-finite values, a negative frequency, a zero or negative bandwidth
+    /// (the noise power `kTB` would be `-inf` dBm) and a negative noise temperature.
+    /// Warning: a 0 Hz frequency, which is the `Default` placeholder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gainlineup::Input;
+    ///
+    /// let cw = Input::new(1.0e9, 0.0, -30.0, Some(290.0));
+    /// let findings = cw.validate();
+    /// assert_eq!(findings.len(), 1);
+    /// assert_eq!(findings[0].parameter, "bandwidth_hz");
+    /// assert!(findings[0].is_error());
+    ///
+    /// assert!(Input::new(1.0e9, 100.0, -30.0, Some(290.0)).validate().is_empty());
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut findings = vec![];
+
+        if check_finite(&mut findings, None, "frequency_hz", self.frequency_hz) {
+            if self.frequency_hz < 0.0 {
+                findings.push(Diagnostic::new(
+                    Severity::Error,
+                    None,
+                    "frequency_hz",
+                    format!("{} Hz is negative", self.frequency_hz),
+                ));
+            } else if self.frequency_hz == 0.0 {
+                findings.push(Diagnostic::new(
+                    Severity::Warning,
+                    None,
+                    "frequency_hz",
+                    "0 Hz is the default placeholder; set the signal frequency",
+                ));
+            }
+        }
+
+        if check_finite(&mut findings, None, "bandwidth_hz", self.bandwidth_hz)
+            && self.bandwidth_hz <= 0.0
+        {
+            findings.push(Diagnostic::new(
+                Severity::Error,
+                None,
+                "bandwidth_hz",
+                format!(
+                    "{} Hz gives no noise power; for a CW signal use a small bandwidth such as 100 Hz",
+                    self.bandwidth_hz
+                ),
+            ));
+        }
+
+        check_finite(&mut findings, None, "power_dbm", self.power_dbm);
+
+        if check_finite(&mut findings, None, "papr_db", self.papr_db) && self.papr_db < 0.0 {
+            findings.push(Diagnostic::new(
+                Severity::Error,
+                None,
+                "papr_db",
+                format!("{} dB is negative; the peak power is at least the average", self.papr_db),
+            ));
+        }
+
+        if let Some(noise_temperature_k) = self.noise_temperature_k {
+            if check_finite(
+                &mut findings,
+                None,
+                "noise_temperature_k",
+                noise_temperature_k,
+            ) && noise_temperature_k < 0.0
+            {
+                findings.push(Diagnostic::new(
+                    Severity::Error,
+                    None,
+                    "noise_temperature_k",
+                    format!("{} K is negative", noise_temperature_k),
+                ));
+            }
+        }
+
+        findings
+    }
+}
+
+/// Validate the input, every block and the cascade as a whole.
+///
+/// Besides [`Input::validate`] and [`Block::validate`], this cascades the lineup and
+/// flags any block whose output P1dB is below the noise floor at its output (error)
+/// or whose output is driven past its P1dB by the input signal (warning), any ADC that
+/// clips or is sampled too slowly for the signal bandwidth, and any digital stage without
+/// an ADC upstream, decimated below the signal bandwidth or overflowing (warnings). The
+/// cascade checks are skipped when the input or a block already has an error.
+///
+/// # Examples
+///
+/// ```
+/// use gainlineup::{Input, Block, Severity, validate_lineup};
+///
+/// let input = Input::new(1.0e9, 1.0e6, -10.0, Some(290.0));
+/// let amp = Block {
+///     name: "Amp".to_string(),
+///     gain_db: 30.0,
+///     noise_figure_db: 3.0,
+///     output_p1db_dbm: Some(10.0),
+///     ..Default::default()
+/// };
+/// let findings = validate_lineup(&input, &[amp]);
+/// assert_eq!(findings.len(), 1);
+/// assert_eq!(findings[0].severity, Severity::Warning);
+/// assert_eq!(findings[0].parameter, "output_p1db_dbm");
+/// ```
+#[doc(alias = "lint")]
+#[must_use]
+pub fn validate_lineup(input: &Input, blocks: &[Block]) -> Vec<Diagnostic> {
+    let mut findings = validate_parts(input, blocks);
+    if !findings.iter().any(Diagnostic::is_error) && !blocks.is_empty() {
+        let nodes = crate::cascade_vector_return_vector(input.clone(), blocks.to_vec());
+        findings.extend(validate_cascade(input, blocks, &nodes));
+    }
+    findings
+}
+
+/// Validate an already cascaded lineup, reusing its nodes.
+pub(crate) fn validate_cascaded(
+    input: &Input,
+    blocks: &[Block],
+    nodes: &[SignalNode],
+) -> Vec<Diagnostic> {
+    let mut findings = validate_parts(input, blocks);
+    if !findings.iter().any(Diagnostic::is_error) {
+        findings.extend(validate_cascade(input, blocks, nodes));
+    }
+    findings
+}
+
+fn validate_parts(input: &Input, blocks: &[Block]) -> Vec<Diagnostic> {
+    let mut findings = input.validate();
+    if blocks.is_empty() {
+        findings.push(Diagnostic::new(
+            Severity::Warning,
+            None,
+            "blocks",
+            "the lineup has no blocks",
+        ));
+    }
+    for block in blocks {
+        findings.extend(block.validate());
+    }
+    findings
+}
+
+fn validate_cascade(input: &Input, blocks: &[Block], nodes: &[SignalNode]) -> Vec<Diagnostic> {
+    let mut findings = vec![];
+    let mut input_power_dbm = input.power_dbm;
+    for (block, node) in blocks.iter().zip(nodes) {
+        if let Some(output_p1db_dbm) = block.output_p1db_dbm {
+            let name = Some(block.name.as_str());
+            let linear_output_dbm = input_power_dbm + block.gain_db;
+            if output_p1db_dbm < node.noise_power_dbm {
+                findings.push(Diagnostic::new(
+                    Severity::Error,
+                    name,
+                    "output_p1db_dbm",
+                    format!(
+                        "{} dBm is below the {:.1} dBm noise floor at this stage; the block would compress on its own noise",
+                        output_p1db_dbm, node.noise_power_dbm
+                    ),
+                ));
+            } else if linear_output_dbm > output_p1db_dbm {
+                findings.push(Diagnostic::new(
+                    Severity::Warning,
+                    name,
+                    "output_p1db_dbm",
+                    format!(
+                        "the signal drives the output {:.1} dB past its {} dBm P1dB; reduce the drive or use a higher-P1dB part",
+                        linear_output_dbm - output_p1db_dbm,
+                        output_p1db_dbm
+                    ),
+                ));
+            }
+        }
+        if let BlockKind::Adc(adc) = &block.kind {
+            let name = Some(block.name.as_str());
+            if let Some(headroom_db) = node.adc_headroom_db.filter(|headroom| *headroom < 0.0) {
+                findings.push(Diagnostic::new(
+                    Severity::Warning,
+                    name,
+                    "full_scale_dbm",
+                    format!(
+                        "the signal peaks ({:.1} dBm with {} dB PAPR) exceed the {} dBm full scale by {:.1} dB; the converter clips",
+                        input_power_dbm, node.papr_db, adc.full_scale_dbm, -headroom_db
+                    ),
+                ));
+            }
+            if node.signal_bandwidth_hz > adc.nyquist_bandwidth_hz() {
+                findings.push(Diagnostic::new(
+                    Severity::Warning,
+                    name,
+                    "sample_rate_hz",
+                    format!(
+                        "the {} Hz signal bandwidth is wider than the {} Hz Nyquist zone",
+                        node.signal_bandwidth_hz,
+                        adc.nyquist_bandwidth_hz()
+                    ),
+                ));
+            }
+        }
+        if let BlockKind::Digital(stage) = &block.kind {
+            let name = Some(block.name.as_str());
+            match node.sample_rate_hz {
+                None => findings.push(Diagnostic::new(
+                    Severity::Warning,
+                    name,
+                    "sample_rate_hz",
+                    "no ADC upstream sets the sample rate and full scale; the stage adds no rounding noise",
+                )),
+                Some(sample_rate_hz) if node.signal_bandwidth_hz > sample_rate_hz / 2.0 => {
+                    findings.push(Diagnostic::new(
+                        Severity::Warning,
+                        name,
+                        "decimation",
+                        format!(
+                            "the {} Hz signal bandwidth is wider than the {} Hz Nyquist zone after decimating by {}",
+                            node.signal_bandwidth_hz,
+                            sample_rate_hz / 2.0,
+                            stage.decimation
+                        ),
+                    ));
+                }
+                Some(_) => {}
+            }
+            if let Some(peak_dbfs) = node
+                .signal_power_dbfs()
+                .map(|power_dbfs| power_dbfs + node.papr_db)
+                .filter(|peak_dbfs| *peak_dbfs > 0.0)
+            {
+                findings.push(Diagnostic::new(
+                    Severity::Warning,
+                    name,
+                    "gain_db",
+                    format!(
+                        "the signal peaks reach {:.1} dBFS; the digital word overflows",
+                        peak_dbfs
+                    ),
+                ));
+            }
+        }
+        input_power_dbm = node.signal_power_dbm;
+    }
+    findings
+}
+
+/// Validate the input, every block and the structure of a lineup tree.
+pub(crate) fn validate_tree(
+    input: &Input,
+    trunk: &Branch,
+    outputs: &[BranchOutput],
+) -> Vec<Diagnostic> {
+    let mut findings = input.validate();
+    validate_branch(&mut findings, trunk);
+    for (i, output) in outputs.iter().enumerate() {
+        if outputs[..i].iter().any(|earlier| earlier.name == output.name) {
+            findings.push(Diagnostic::new(
+                Severity::Warning,
+                None,
+                "name",
+                format!(
+                    "two outputs are named '{}'; only the first can be looked up by name",
+                    output.name
+                ),
+            ));
+        }
+    }
+    findings
+}
+
+fn validate_branch(findings: &mut Vec<Diagnostic>, branch: &Branch) {
+    for (i, stage) in branch.stages.iter().enumerate() {
+        match stage {
+            Stage::Block(block) => findings.extend(block.validate()),
+            Stage::Split { splitter, branches } => {
+                findings.extend(splitter.validate());
+                check_ports(findings, splitter, branches.len());
+                let after = branch.stages.len() - i - 1;
+                if after > 0 {
+                    findings.push(Diagnostic::new(
+                        Severity::Error,
+                        Some(&splitter.name),
+                        "branches",
+                        format!(
+                            "{} stage(s) after the split in branch '{}' are not cascaded; move them into the branches",
+                            after, branch.name
+                        ),
+                    ));
+                }
+                for branch in branches {
+                    validate_branch(findings, branch);
+                }
+            }
+            Stage::Combine {
+                splitter,
+                branches,
+                combiner,
+            } => {
+                findings.extend(splitter.validate());
+                check_ports(findings, splitter, branches.len());
+                findings.extend(combiner.validate());
+                check_ports(findings, combiner, branches.len());
+                for branch in branches {
+                    validate_branch(findings, branch);
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn validate_array(array: &PhasedArray) -> Vec<Diagnostic> {
+    let mut findings = vec![];
+    if array.elements == 0 {
+        findings.push(Diagnostic::new(
+            Severity::Error,
+            None,
+            "elements",
+            "the array has no elements",
+        ));
+    }
+    for (parameter, value) in [
+        ("amplitude_rms_db", array.errors.amplitude_rms_db),
+        ("phase_rms_deg", array.errors.phase_rms_deg),
+    ] {
+        if !value.is_finite() || value < 0.0 {
+            findings.push(Diagnostic::new(
+                Severity::Error,
+                None,
+                parameter,
+                format!("{} is not an RMS error; use a value of 0 or more", value),
+            ));
+        }
+    }
+    for block in &array.channel {
+        findings.extend(block.validate());
+    }
+    findings.extend(array.combiner.validate());
+    check_ports(&mut findings, &array.combiner, array.elements);
+    for block in &array.back_end {
+        findings.extend(block.validate());
+    }
+    findings
+}
+
+/// Push an error if a splitter or combiner has fewer ports than the branches on it.
+fn check_ports(findings: &mut Vec<Diagnostic>, block: &Block, branches: usize) {
+    let ways = match &block.kind {
+        BlockKind::Splitter(Splitter { ways }) | BlockKind::Combiner(Combiner { ways, .. }) => {
+            *ways
+        }
+        _ => return,
+    };
+    if branches > ways {
+        findings.push(Diagnostic::new(
+            Severity::Error,
+            Some(&block.name),
+            "ways",
+            format!("{} ports cannot serve {} branches", ways, branches),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::CompressionModel;
+    use crate::filter::Filter;
+
+    fn block(name: &str, gain_db: f64, noise_figure_db: f64) -> Block {
+        Block {
+            name: name.to_string(),
+            gain_db,
+            noise_figure_db,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn clean_block_has_no_findings() {
+        let lna = Block {
+            output_p1db_dbm: Some(10.0),
+            output_ip3_dbm: Some(20.0),
+            ..block("LNA", 20.0, 1.0)
+        };
+        assert!(lna.validate().is_empty());
+        // passive block with NF equal to its loss
+        assert!(block("Cable", -3.0, 3.0).validate().is_empty());
+    }
+
+    #[test]
+    fn non_finite_and_negative_values_are_errors() {
+        let findings = block("Bad", f64::NAN, -1.0).validate();
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(Diagnostic::is_error));
+        assert_eq!(findings[0].parameter, "gain_db");
+        assert_eq!(findings[1].parameter, "noise_figure_db");
+
+        let findings = Block {
+            output_ip3_dbm: Some(f64::INFINITY),
+            ..block("Amp", 10.0, 3.0)
+        }
+        .validate();
+        assert_eq!(findings[0].parameter, "output_ip3_dbm");
+    }
+
+    #[test]
+    fn suspicious_values_are_warnings() {
+        let findings = block("Huge", 90.0, 3.0).validate();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+
+        let findings = Block {
+            output_p1db_dbm: Some(20.0),
+            output_ip3_dbm: Some(15.0),
+            ..block("Amp", 20.0, 3.0)
+        }
+        .validate();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].parameter, "output_ip3_dbm");
+    }
+
+    #[test]
+    fn vswr_below_one_is_an_error() {
+        let findings = Block {
+            input_vswr: Some(0.5),
+            output_vswr: Some(1.5),
+            ..block("Amp", 20.0, 3.0)
+        }
+        .validate();
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].is_error());
+        assert_eq!(findings[0].parameter, "input_vswr");
+    }
+
+    #[test]
+    fn invalid_rapp_parameters_are_errors() {
+        let rapp = |smoothness: f64, saturation_power_dbm: Option<f64>| Block {
+            output_p1db_dbm: Some(10.0),
+            compression_model: CompressionModel::Rapp {
+                smoothness,
+                saturation_power_dbm,
+            },
+            ..block("PA", 20.0, 3.0)
+        };
+        for model in [rapp(0.0, None), rapp(f64::NAN, None), rapp(2.0, Some(5.0))] {
+            let findings = model.validate();
+            assert_eq!(findings.len(), 1);
+            assert!(findings[0].is_error());
+            assert_eq!(findings[0].parameter, "compression_model");
+        }
+        assert!(rapp(2.0, Some(13.0)).validate().is_empty());
+    }
+
+    #[test]
+    fn filter_and_mixer_checks() {
+        let filter = Block::filter("BPF", 2.0, Filter::new(1.0e9, 0.0));
+        let findings = filter.validate();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].parameter, "bandwidth_hz");
+
+        let mixer = Block {
+            kind: BlockKind::Mixer(crate::mixer::Mixer::default()),
+            ..block("Mixer", -7.0, 9.0)
+        };
+        // mixers are not held to NF = loss, but need an LO
+        let findings = mixer.validate();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].parameter, "lo_frequency_hz");
+    }
+
+    #[test]
+    fn input_checks() {
+        let findings = Input::new(-1.0, -5.0, f64::NAN, Some(-10.0)).validate();
+        let parameters: Vec<&str> = findings.iter().map(|d| d.parameter.as_str()).collect();
+        assert_eq!(
+            parameters,
+            vec![
+                "frequency_hz",
+                "bandwidth_hz",
+                "power_dbm",
+                "noise_temperature_k"
+            ]
+        );
+        assert!(findings.iter().all(Diagnostic::is_error));
+
+        let findings = Input::default().validate();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn p1db_below_noise_floor_is_error() {
+        let input = Input::new(1.0e9, 100.0e6, -60.0, Some(290.0));
+        let blocks = vec![
+            block("LNA", 60.0, 3.0),
+            Block {
+                output_p1db_dbm: Some(-40.0),
+                ..block("Driver", 10.0, 5.0)
+            },
+        ];
+        let findings = validate_lineup(&input, &blocks);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].is_error());
+        assert_eq!(findings[0].block.as_deref(), Some("Driver"));
+    }
+
+    #[test]
+    fn lineup_skips_cascade_checks_after_errors() {
+        let input = Input::new(1.0e9, 0.0, 0.0, Some(290.0));
+        let blocks = vec![Block {
+            output_p1db_dbm: Some(0.0),
+            ..block("Amp", 30.0, 3.0)
+        }];
+        let findings = validate_lineup(&input, &blocks);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].parameter, "bandwidth_hz");
+
+        let findings = validate_lineup(&Input::new(1.0e9, 1.0e6, 0.0, None), &[]);
+        assert_eq!(findings[0].parameter, "blocks");
+    }
+
+    #[test]
+    fn tree_structure_checks() {
+        use crate::branching::LineupTree;
+
+        let trunk = Branch::new("Trunk", vec![])
+            .split(
+                Block::splitter("Splitter", 2, 0.0),
+                vec![
+                    Branch::new("Rx", vec![]),
+                    Branch::new("Rx", vec![]),
+                    Branch::new("Spare", vec![]),
+                ],
+            )
+            .with_block(block("Late", 10.0, 3.0));
+        let tree = LineupTree::new(Input::new(1.0e9, 1.0e6, -30.0, Some(290.0)), trunk);
+        let findings = tree.validate();
+        let parameters: Vec<&str> = findings.iter().map(|d| d.parameter.as_str()).collect();
+        assert_eq!(parameters, vec!["ways", "branches", "name"]);
+
+        let combiner = Block {
+            kind: BlockKind::Combiner(Combiner::new(0)),
+            ..block("Combiner", -3.0, 3.0)
+        };
+        let findings = combiner.validate();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].parameter, "ways");
+    }
+
+    #[test]
+    fn array_checks() {
+        use crate::array::{ChannelErrors, PhasedArray};
+
+        let array = PhasedArray::new(8, vec![], Block::combiner("Combiner", 4, 0.0))
+            .with_errors(ChannelErrors::new(-0.5, 2.0));
+        let findings = array.validate();
+        let parameters: Vec<&str> = findings.iter().map(|f| f.parameter.as_str()).collect();
+        assert_eq!(parameters, vec!["amplitude_rms_db", "ways"]);
+
+        let empty = PhasedArray::new(0, vec![], Block::combiner("Combiner", 4, 0.0));
+        assert_eq!(empty.validate()[0].parameter, "elements");
+    }
+
+    #[test]
+    fn display_diagnostic() {
+        let findings = Input::new(1.0e9, 0.0, 0.0, None).validate();
+        assert!(findings[0]
+            .to_string()
+            .starts_with("error: bandwidth_hz: 0 Hz gives no noise power"));
+    }
+}
@@ -40,6 +40,7 @@ fn two_stage_lna_mixer_nf_consistency() {
             noise_figure_db: nf_lna,
             output_p1db_dbm: Some(5.0),
             output_ip3_dbm: Some(20.0),
+            ..Default::default()
         },
         Block {
             name: "Mixer".to_string(),
@@ -47,6 +48,7 @@ fn two_stage_lna_mixer_nf_consistency() {
             noise_figure_db: nf_mixer,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: None,
+            ..Default::default()
         },
     ];
 
@@ -77,6 +79,7 @@ fn four_stage_rx_chain_nf() {
             noise_figure_db: nfs[i],
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         })
         .collect();
 
@@ -107,6 +110,7 @@ fn passive_chain_nf_equals_loss() {
             noise_figure_db: loss,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         })
         .collect();
 
@@ -143,6 +147,7 @@ fn vector_cascade_intermediate_nf_consistency() {
             noise_figure_db: nfs[i],
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         })
         .collect();
 
@@ -172,6 +177,7 @@ fn single_block_nf_identity() {
         noise_figure_db: nf,
         output_p1db_dbm: Some(20.0),
         output_ip3_dbm: Some(35.0),
+        ..Default::default()
     }];
 
     let output = cascade_vector_return_output(input, blocks);
@@ -34,6 +34,7 @@ fn ka_band_leo_receive_chain() {
             noise_figure_db: 1.2,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: Some(25.0),
+            ..Default::default()
         },
         Block {
             name: "BPF".to_string(),
@@ -41,6 +42,7 @@ fn ka_band_leo_receive_chain() {
             noise_figure_db: 2.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         },
         Block {
             name: "Mixer".to_string(),
@@ -48,6 +50,7 @@ fn ka_band_leo_receive_chain() {
             noise_figure_db: 8.0,
             output_p1db_dbm: Some(5.0),
             output_ip3_dbm: Some(15.0),
+            ..Default::default()
         },
         Block {
             name: "IF Amp".to_string(),
@@ -55,6 +58,7 @@ fn ka_band_leo_receive_chain() {
             noise_figure_db: 3.0,
             output_p1db_dbm: Some(15.0),
             output_ip3_dbm: Some(28.0),
+            ..Default::default()
         },
         Block {
             name: "ADC Driver".to_string(),
@@ -62,6 +66,7 @@ fn ka_band_leo_receive_chain() {
             noise_figure_db: 5.0,
             output_p1db_dbm: Some(12.0),
             output_ip3_dbm: Some(22.0),
+            ..Default::default()
         },
     ];
 
@@ -101,6 +106,7 @@ fn two_stage_lna_noise_improvement() {
             noise_figure_db: 1.5,
             output_p1db_dbm: Some(5.0),
             output_ip3_dbm: None,
+            ..Default::default()
         },
         Block {
             name: "Downconverter".to_string(),
@@ -108,6 +114,7 @@ fn two_stage_lna_noise_improvement() {
             noise_figure_db: 12.0,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: None,
+            ..Default::default()
         },
     ];
 
@@ -119,6 +126,7 @@ fn two_stage_lna_noise_improvement() {
             noise_figure_db: 1.5,
             output_p1db_dbm: Some(5.0),
             output_ip3_dbm: None,
+            ..Default::default()
         },
         Block {
             name: "LNA2".to_string(),
@@ -126,6 +134,7 @@ fn two_stage_lna_noise_improvement() {
             noise_figure_db: 2.0,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: None,
+            ..Default::default()
         },
         Block {
             name: "Downconverter".to_string(),
@@ -133,6 +142,7 @@ fn two_stage_lna_noise_improvement() {
             noise_figure_db: 12.0,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: None,
+            ..Default::default()
         },
     ];
 
@@ -161,6 +171,7 @@ fn signal_headroom_check() {
             noise_figure_db: 0.8,
             output_p1db_dbm: Some(5.0),
             output_ip3_dbm: Some(20.0),
+            ..Default::default()
         },
         Block {
             name: "IF Amp".to_string(),
@@ -168,6 +179,7 @@ fn signal_headroom_check() {
             noise_figure_db: 3.0,
             output_p1db_dbm: Some(15.0),
             output_ip3_dbm: Some(30.0),
+            ..Default::default()
         },
         Block {
             name: "VGA".to_string(),
@@ -175,6 +187,7 @@ fn signal_headroom_check() {
             noise_figure_db: 5.0,
             output_p1db_dbm: Some(20.0),
             output_ip3_dbm: Some(35.0),
+            ..Default::default()
         },
     ];
 
@@ -217,6 +230,7 @@ fn passive_attenuator_nf_equals_loss() {
         noise_figure_db: 6.0,
         output_p1db_dbm: None,
         output_ip3_dbm: None,
+        ..Default::default()
     }];
 
     let output = cascade_vector_return_output(input, blocks);
@@ -248,6 +262,7 @@ fn ku_band_vsat_link_closes() {
             noise_figure_db: 0.7,
             output_p1db_dbm: Some(0.0),
             output_ip3_dbm: Some(15.0),
+            ..Default::default()
         },
         Block {
             name: "Cable Loss".to_string(),
@@ -255,6 +270,7 @@ fn ku_band_vsat_link_closes() {
             noise_figure_db: 15.0,
             output_p1db_dbm: None,
             output_ip3_dbm: None,
+            ..Default::default()
         },
         Block {
             name: "IRD Input".to_string(),
@@ -262,6 +278,7 @@ fn ku_band_vsat_link_closes() {
             noise_figure_db: 8.0,
             output_p1db_dbm: Some(10.0),
             output_ip3_dbm: None,
+            ..Default::default()
         },
     ];
 
@@ -9,6 +9,7 @@ fn input_signal_construction() {
         frequency_hz: 6.0e9,             // 6 GHz C-band
         bandwidth_hz: 1.0e6,             // 1 MHz channel
         noise_temperature_k: Some(50.0), // cool sky
+        ..Default::default()
     };
 
     assert_eq!(input.power_dbm, -80.0);
@@ -10,6 +10,7 @@ fn block_construction() {
         noise_figure_db: 1.5,
         output_p1db_dbm: Some(5.0),
         output_ip3_dbm: Some(20.0),
+        ..Default::default()
     };
 
     let mixer = Block {
@@ -18,6 +19,7 @@ fn block_construction() {
         noise_figure_db: 8.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: Some(15.0),
+        ..Default::default()
     };
 
     let if_amp = Block {
@@ -26,6 +28,7 @@ fn block_construction() {
         noise_figure_db: 4.0,
         output_p1db_dbm: Some(15.0),
         output_ip3_dbm: Some(25.0),
+        ..Default::default()
     };
 
     assert_eq!(lna.gain_db, 20.0);
@@ -9,6 +9,7 @@ fn cascade_three_stage_receiver() {
         frequency_hz: 6.0e9,
         bandwidth_hz: 1.0e6,
         noise_temperature_k: Some(50.0),
+        ..Default::default()
     };
 
     let lna = Block {
@@ -17,6 +18,7 @@ fn cascade_three_stage_receiver() {
         noise_figure_db: 1.5,
         output_p1db_dbm: Some(5.0),
         output_ip3_dbm: Some(20.0),
+        ..Default::default()
     };
 
     let mixer = Block {
@@ -25,6 +27,7 @@ fn cascade_three_stage_receiver() {
         noise_figure_db: 8.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: Some(15.0),
+        ..Default::default()
     };
 
     let if_amp = Block {
@@ -33,6 +36,7 @@ fn cascade_three_stage_receiver() {
         noise_figure_db: 4.0,
         output_p1db_dbm: Some(15.0),
         output_ip3_dbm: Some(25.0),
+        ..Default::default()
     };
 
     let blocks = vec![lna.clone(), mixer.clone(), if_amp.clone()];
@@ -1,6 +1,6 @@
 //! README example: Compression (P1dB)
 
-use gainlineup::Block;
+use gainlineup::{Block, CompressionModel};
 
 #[test]
 fn compression_linear_region() {
@@ -10,11 +10,12 @@ fn compression_linear_region() {
         noise_figure_db: 5.0,
         output_p1db_dbm: Some(20.0),
         output_ip3_dbm: None,
+        ..Default::default()
     };
 
-    // Linear: -20 + 30 = 10 (below P1dB)
-    assert_eq!(pa.output_power(-20.0), 10.0);
-    assert_eq!(pa.power_gain(-20.0), 30.0);
+    // Linear: -20 + 30 = 10 (10 dB below P1dB)
+    assert!((pa.output_power(-20.0) - 10.0).abs() < 0.01);
+    assert!((pa.power_gain(-20.0) - 30.0).abs() < 0.01);
 }
 
 #[test]
@@ -25,9 +26,31 @@ fn compression_above_p1db() {
         noise_figure_db: 5.0,
         output_p1db_dbm: Some(20.0),
         output_ip3_dbm: None,
+        ..Default::default()
     };
 
-    // Compressed: 0 + 30 = 30, clamps to P1dB + 1 = 21
+    // 1 dB compressed at P1dB: -9 + 30 - 1 = 20
+    assert!((pa.output_power(-9.0) - 20.0).abs() < 1e-9);
+    assert!((pa.power_gain(-9.0) - 29.0).abs() < 1e-9);
+
+    // Saturated: 0 + 30 = 30 linear, about +22.1 dBm out
+    let saturated = pa.output_power(0.0);
+    assert!(saturated > 22.0 && saturated < 22.2, "got {saturated:.2} dBm");
+}
+
+#[test]
+fn compression_hard_clip() {
+    let pa = Block {
+        name: "Power Amplifier".to_string(),
+        gain_db: 30.0,
+        noise_figure_db: 5.0,
+        output_p1db_dbm: Some(20.0),
+        compression_model: CompressionModel::HardClip,
+        ..Default::default()
+    };
+
+    // Linear up to P1dB + 1, then clamped: 0 + 30 = 30 clamps to 21
+    assert_eq!(pa.output_power(-20.0), 10.0);
     assert_eq!(pa.output_power(0.0), 21.0);
     assert_eq!(pa.power_gain(0.0), 21.0);
 }
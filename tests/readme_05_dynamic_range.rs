@@ -10,6 +10,7 @@ fn dynamic_range_output_referred() {
         noise_figure_db: 3.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: None,
+        ..Default::default()
     };
 
     // Output-referred: P1dB_out - noise_floor_out
@@ -25,6 +26,7 @@ fn dynamic_range_input_referred() {
         noise_figure_db: 3.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: None,
+        ..Default::default()
     };
 
     // Input-referred: input_P1dB - input_noise_floor
@@ -40,6 +42,7 @@ fn dynamic_range_none_without_p1db() {
         noise_figure_db: 3.0,
         output_p1db_dbm: None,
         output_ip3_dbm: None,
+        ..Default::default()
     };
     assert!(linear_block.dynamic_range_db(1e6).is_none());
     assert!(linear_block.input_dynamic_range_db(1e6).is_none());
@@ -10,6 +10,7 @@ fn imd3_single_point() {
         noise_figure_db: 5.0,
         output_p1db_dbm: None,
         output_ip3_dbm: Some(30.0), // OIP3 = +30 dBm
+        ..Default::default()
     };
 
     // Pin = -30 -> Pout = -10
@@ -30,6 +31,7 @@ fn imd3_3_to_1_slope() {
         noise_figure_db: 5.0,
         output_p1db_dbm: None,
         output_ip3_dbm: Some(30.0),
+        ..Default::default()
     };
 
     let im3_a = amp.imd3_output_power_dbm(-30.0).unwrap();
@@ -47,6 +49,7 @@ fn imd3_sweep_structure() {
         noise_figure_db: 5.0,
         output_p1db_dbm: None,
         output_ip3_dbm: Some(30.0),
+        ..Default::default()
     };
 
     let sweep = amp.imd3_sweep(-50.0, -10.0, 5.0);
@@ -11,6 +11,7 @@ fn node_dynamic_range_summary() {
         noise_figure_db: 1.5,
         output_p1db_dbm: Some(5.0),
         output_ip3_dbm: Some(20.0),
+        ..Default::default()
     }];
     let node = cascade_vector_return_output(input, blocks);
 
@@ -28,3 +29,31 @@ fn node_dynamic_range_summary() {
     assert!(summary.mds_dbm < 0.0);
     assert!(summary.max_input_dbm > summary.mds_dbm);
 }
+
+#[test]
+fn cascaded_p1db_names_limiting_stage() {
+    let input = Input::new(1.0e9, 1.0e6, -60.0, None);
+    let lna = Block {
+        name: "LNA".to_string(),
+        gain_db: 20.0,
+        output_p1db_dbm: Some(10.0),
+        ..Default::default()
+    };
+    let pa = Block {
+        name: "PA".to_string(),
+        gain_db: 15.0,
+        output_p1db_dbm: Some(30.0),
+        ..Default::default()
+    };
+    let node = cascade_vector_return_output(input, vec![lna, pa]);
+
+    // LNA referred to the output: +10 + 15 = +25 dBm, below the PA's +30 dBm
+    assert_eq!(node.p1db_limiting_stage.as_deref(), Some("LNA"));
+    let p1db = node.output_p1db_dbm.unwrap();
+    assert!(p1db < 25.0 && p1db > 23.0, "got {p1db:.2}");
+
+    let summary = node.dynamic_range_summary().unwrap();
+    assert_eq!(summary.p1db_limiting_stage.as_deref(), Some("LNA"));
+    assert!((summary.max_input_dbm - (p1db - 35.0)).abs() < 1e-9);
+    assert!((node.input_p1db_dbm().unwrap() - (p1db - 35.0 + 1.0)).abs() < 1e-9);
+}
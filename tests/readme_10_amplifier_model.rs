@@ -10,6 +10,7 @@ fn amplifier_model_basic() {
         noise_figure_db: 5.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: Some(25.0),
+        ..Default::default()
     };
 
     // Simple: no AM-PM
@@ -25,6 +26,7 @@ fn amplifier_model_with_am_pm() {
         noise_figure_db: 5.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: Some(25.0),
+        ..Default::default()
     };
 
     // With AM-PM coefficient (10 deg/dB near P1dB)
@@ -40,6 +42,7 @@ fn amplifier_model_builder() {
         noise_figure_db: 5.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: Some(25.0),
+        ..Default::default()
     };
 
     // Builder pattern for full configuration
@@ -61,6 +64,7 @@ fn amplifier_model_sweep() {
         noise_figure_db: 5.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: Some(25.0),
+        ..Default::default()
     };
 
     let model = AmplifierModel::with_am_pm(&pa, 10.0);
@@ -83,6 +87,7 @@ fn amplifier_model_backoff_and_evm() {
         noise_figure_db: 5.0,
         output_p1db_dbm: Some(10.0),
         output_ip3_dbm: Some(25.0),
+        ..Default::default()
     };
 
     let model = AmplifierModel::with_am_pm(&pa, 10.0);
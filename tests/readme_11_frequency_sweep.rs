@@ -0,0 +1,54 @@
+//! README example: Wideband Lineups (Frequency Sweep)
+
+use gainlineup::{
+    cascade_frequency_sweep, frequency_grid, Block, FrequencyPoint, FrequencyResponse, Input,
+};
+
+#[test]
+fn ku_band_lnb_worst_case_noise_figure() {
+    let point = |frequency_hz: f64, gain_db: f64, noise_figure_db: f64| FrequencyPoint {
+        frequency_hz,
+        gain_db,
+        noise_figure_db,
+        output_p1db_dbm: Some(10.0),
+        output_ip3_dbm: Some(20.0),
+    };
+
+    let lna = Block {
+        name: "Ku LNA".to_string(),
+        frequency_response: Some(FrequencyResponse::new(vec![
+            point(10.7e9, 32.0, 0.7),
+            point(11.7e9, 31.0, 0.8),
+            point(12.75e9, 29.0, 1.1),
+        ])),
+        ..Default::default()
+    };
+
+    let bpf = Block {
+        name: "Image Reject Filter".to_string(),
+        gain_db: -1.5,
+        noise_figure_db: 1.5,
+        output_p1db_dbm: None,
+        output_ip3_dbm: None,
+        ..Default::default()
+    };
+
+    let input = Input::new(11.7e9, 36.0e6, -90.0, Some(290.0));
+    let grid = frequency_grid(10.7e9, 12.75e9, 42);
+    let sweep = cascade_frequency_sweep(&input, &[lna, bpf], &grid);
+
+    assert_eq!(sweep.nodes.len(), 42);
+
+    // Worst-case NF is at the top of the band, where the LNA is noisiest
+    let (frequency_hz, nf_db) = sweep.worst_case_noise_figure().unwrap();
+    assert_eq!(frequency_hz, 12.75e9);
+    assert!(nf_db > 1.1 && nf_db < 1.2, "got {nf_db:.3} dB");
+
+    // Gain slopes 3 dB across the band
+    let flatness = sweep.gain_flatness_db().unwrap();
+    assert!((flatness - 3.0).abs() < 1e-9);
+
+    for (frequency_hz, node) in sweep.outputs() {
+        assert_eq!(node.signal_frequency_hz, frequency_hz);
+    }
+}
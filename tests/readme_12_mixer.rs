@@ -0,0 +1,44 @@
+//! README example: Mixers (Frequency Conversion)
+
+use gainlineup::{cascade_vector_return_vector, Block, Injection, Input, Mixer, MixerNoiseFigure};
+
+#[test]
+fn superhet_downconversion_to_if() {
+    let lna = Block {
+        name: "LNA".to_string(),
+        gain_db: 18.0,
+        noise_figure_db: 1.2,
+        output_p1db_dbm: Some(15.0),
+        output_ip3_dbm: Some(28.0),
+        ..Default::default()
+    };
+
+    // 2.45 GHz down to a 300 MHz IF with the LO below the signal
+    let mixer = Block::mixer(
+        "Mixer",
+        -7.0,
+        MixerNoiseFigure::Dsb(4.0),
+        Mixer::from_if(2.45e9, 300.0e6, Injection::LowSide).with_lo_power(13.0),
+    );
+
+    let if_amp = Block {
+        name: "IF Amplifier".to_string(),
+        gain_db: 20.0,
+        noise_figure_db: 3.0,
+        output_p1db_dbm: None,
+        output_ip3_dbm: None,
+        ..Default::default()
+    };
+
+    let input = Input::new(2.45e9, 200.0e3, -90.0, Some(290.0));
+    let nodes = cascade_vector_return_vector(input, vec![lna, mixer, if_amp]);
+
+    assert_eq!(nodes[0].signal_frequency_hz, 2.45e9);
+    assert_eq!(nodes[1].signal_frequency_hz, 300.0e6);
+    assert_eq!(nodes[1].image_frequency_hz, Some(1.85e9));
+    assert_eq!(nodes[2].signal_frequency_hz, 300.0e6);
+
+    // DSB 4 dB is used as SSB ~7 dB in the cascade
+    let nf_db = nodes[2].cumulative_noise_figure_db;
+    assert!(nf_db > 1.6 && nf_db < 1.7, "got {nf_db:.3} dB");
+}
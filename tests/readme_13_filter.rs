@@ -0,0 +1,47 @@
+//! README example: Filters (Noise Bandwidth and Rejection)
+
+use gainlineup::{cascade_vector_return_vector, Block, Filter, Input};
+
+#[test]
+fn if_filter_sets_noise_bandwidth() {
+    let lna = Block {
+        name: "LNA".to_string(),
+        gain_db: 30.0,
+        noise_figure_db: 1.0,
+        output_p1db_dbm: None,
+        output_ip3_dbm: None,
+        ..Default::default()
+    };
+
+    // 70 MHz IF filter: 1 MHz passband, 40 dB down at ±2 MHz
+    let if_filter = Block::filter(
+        "IF Filter",
+        3.0,
+        Filter::new(70.0e6, 1.0e6).with_rejection(vec![(2.0e6, 40.0)]),
+    );
+
+    let input = Input::new(70.0e6, 20.0e6, -100.0, Some(290.0));
+    let nodes = cascade_vector_return_vector(input.clone(), vec![lna.clone(), if_filter.clone()]);
+
+    assert_eq!(nodes[0].signal_bandwidth_hz, 20.0e6);
+    assert_eq!(nodes[1].signal_bandwidth_hz, 1.0e6);
+
+    // Noise drops by the bandwidth ratio (13 dB) plus the 3 dB insertion loss
+    let noise_drop_db = nodes[0].noise_power_dbm - nodes[1].noise_power_dbm;
+    assert!(
+        (noise_drop_db - 16.0).abs() < 0.05,
+        "got {noise_drop_db:.3} dB"
+    );
+
+    // SNR improves by the bandwidth ratio
+    let snr_gain_db = nodes[1].signal_to_noise_ratio_db() - nodes[0].signal_to_noise_ratio_db();
+    assert!((snr_gain_db - 13.0).abs() < 0.05, "got {snr_gain_db:.3} dB");
+
+    // A signal 3 MHz away is rejected by 40 dB on top of the insertion loss
+    let adjacent = Input {
+        frequency_hz: 73.0e6,
+        ..input
+    };
+    let nodes = cascade_vector_return_vector(adjacent, vec![lna, if_filter]);
+    assert!((nodes[1].signal_power_dbm - (-113.0)).abs() < 1e-9);
+}
@@ -0,0 +1,44 @@
+//! README example: Smooth Compression Models
+
+use gainlineup::{Block, CompressionModel};
+
+#[test]
+fn rapp_gain_is_one_db_down_at_p1db() {
+    let pa = Block {
+        name: "Power Amplifier".to_string(),
+        gain_db: 30.0,
+        noise_figure_db: 5.0,
+        output_p1db_dbm: Some(20.0),
+        output_ip3_dbm: None,
+        compression_model: CompressionModel::Rapp {
+            smoothness: 2.0,
+            saturation_power_dbm: None,
+        },
+        ..Default::default()
+    };
+
+    // Input P1dB is -9 dBm: 30 dB gain, 1 dB compressed, 20 dBm out
+    assert!((pa.power_gain(-9.0) - 29.0).abs() < 1e-9);
+    assert!((pa.output_power(-9.0) - 20.0).abs() < 1e-9);
+
+    // Gain is already slightly compressed a few dB below P1dB
+    let gain_db = pa.power_gain(-15.0);
+    assert!(gain_db < 30.0 && gain_db > 29.5, "got {gain_db:.3} dB");
+
+    // Deep in compression the output approaches saturation
+    let psat_dbm = pa.compression_model.saturation_power_dbm(20.0);
+    assert!(psat_dbm - pa.output_power(20.0) < 0.01);
+
+    // Every smooth model is calibrated to the same P1dB
+    for model in [
+        CompressionModel::Saleh,
+        CompressionModel::Tanh,
+        CompressionModel::Cubic,
+    ] {
+        let block = Block {
+            compression_model: model,
+            ..pa.clone()
+        };
+        assert!((block.power_gain(-9.0) - 29.0).abs() < 1e-6, "{model}");
+    }
+}
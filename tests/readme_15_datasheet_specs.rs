@@ -0,0 +1,48 @@
+//! README example: Datasheet Specs (Input-Referred, Noise Temperature, Linear Gain)
+
+use gainlineup::{Block, GainlineupError};
+
+#[test]
+fn builder_converts_datasheet_specs() {
+    let lna = Block::builder("Low Noise Amplifier")
+        .gain_linear(100.0) // 20 dB
+        .noise_temperature_k(35.0) // ~0.49 dB NF
+        .input_ip3_dbm(-10.0)
+        .input_p1db_dbm(-20.0)
+        .build()
+        .unwrap();
+
+    assert!((lna.gain_db - 20.0).abs() < 1e-9);
+    assert!((lna.noise_figure_db - 0.49).abs() < 0.01);
+    assert!((lna.output_ip3_dbm.unwrap() - 10.0).abs() < 1e-9); // OIP3 = IIP3 + G
+    assert!((lna.output_p1db_dbm.unwrap() - (-1.0)).abs() < 1e-9); // OP1dB = IP1dB + G - 1
+
+    // and back again
+    assert!((lna.input_ip3_dbm().unwrap() - (-10.0)).abs() < 1e-9);
+    assert!((lna.input_p1db_dbm().unwrap() - (-20.0)).abs() < 1e-9);
+    assert!((lna.noise_temperature() - 35.0).abs() < 1e-6);
+}
+
+#[test]
+fn builder_rejects_conflicting_specs() {
+    // Both forms are fine when they agree
+    let ok = Block::builder("Amp")
+        .gain_db(20.0)
+        .output_ip3_dbm(30.0)
+        .input_ip3_dbm(10.0)
+        .build();
+    assert!(ok.is_ok());
+
+    // ...and an error when they don't
+    let err = Block::builder("Amp")
+        .gain_db(20.0)
+        .output_ip3_dbm(30.0)
+        .input_ip3_dbm(15.0)
+        .build()
+        .unwrap_err();
+    assert!(
+        matches!(&err, GainlineupError::InvalidParameter { parameter, .. } if parameter == "IP3"),
+        "{err}"
+    );
+    assert!(err.to_string().contains("invalid IP3 for block 'Amp'"), "{err}");
+}
@@ -0,0 +1,73 @@
+//! README example: IMD2 (Second-Order Intercept)
+
+use gainlineup::{cascade_vector_return_output, Block, Input};
+
+#[test]
+fn imd2_single_point() {
+    let mixer = Block::builder("Mixer")
+        .gain_db(-7.0)
+        .noise_figure_db(7.0)
+        .input_ip2_dbm(55.0) // OIP2 = +48 dBm
+        .build()
+        .unwrap();
+    assert_eq!(mixer.output_ip2_dbm, Some(48.0));
+
+    // Pin = -30 -> Pout = -37
+    // IM2 = 2*(-37) - 48 = -122 dBm
+    let im2 = mixer.imd2_output_power_dbm(-30.0).unwrap();
+    assert!((im2 - (-122.0)).abs() < 0.01);
+
+    // Rejection = 48 - (-37) = 85 dB
+    let rejection = mixer.imd2_rejection_db(-30.0).unwrap();
+    assert!((rejection - 85.0).abs() < 0.01);
+}
+
+#[test]
+fn imd2_2_to_1_slope() {
+    let mixer = Block {
+        name: "Mixer".to_string(),
+        gain_db: -7.0,
+        noise_figure_db: 7.0,
+        output_ip2_dbm: Some(48.0),
+        ..Default::default()
+    };
+
+    let sweep = mixer.imd2_sweep(-50.0, -10.0, 5.0);
+    assert_eq!(sweep.len(), 9);
+
+    // 2:1 slope: 5 dB input increase -> 10 dB IM2 increase
+    let delta = sweep[1].im2_output_dbm - sweep[0].im2_output_dbm;
+    assert!((delta - 10.0).abs() < 0.01);
+}
+
+#[test]
+fn imd2_cascade_and_sfdr2() {
+    let lna = Block {
+        name: "LNA".to_string(),
+        gain_db: 20.0,
+        noise_figure_db: 2.0,
+        output_p1db_dbm: Some(10.0),
+        output_ip2_dbm: Some(50.0),
+        ..Default::default()
+    };
+    let mixer = Block {
+        name: "Mixer".to_string(),
+        gain_db: -7.0,
+        noise_figure_db: 7.0,
+        output_p1db_dbm: Some(5.0),
+        output_ip2_dbm: Some(48.0),
+        ..Default::default()
+    };
+
+    let input = Input::new(1.0e9, 1.0e6, -60.0, None);
+    let node = cascade_vector_return_output(input, vec![lna, mixer]);
+
+    // Cascaded OIP2 is below both the mixer's and the LNA's (referred to the output)
+    let oip2 = node.cumulative_oip2_dbm.unwrap();
+    assert!(oip2 < 43.0, "got {oip2:.2}");
+
+    let summary = node.dynamic_range_summary().unwrap();
+    assert_eq!(summary.sfdr2_db, node.sfdr2_db);
+    assert!(summary.sfdr2_db.unwrap() > 50.0);
+    assert!(summary.sfdr_db.is_none()); // no OIP3 given
+}
@@ -0,0 +1,49 @@
+//! README example: Noise Budget (Per-Stage Contributions)
+
+use gainlineup::{cascade_vector_return_output, Block, Input};
+
+#[test]
+fn noise_budget_finds_the_culprit() {
+    let input = Input::new(2.4e9, 1.0e6, -70.0, Some(290.0));
+    let blocks = vec![
+        Block {
+            name: "LNA".to_string(),
+            gain_db: 15.0,
+            noise_figure_db: 1.0,
+            ..Default::default()
+        },
+        Block {
+            name: "Mixer".to_string(),
+            gain_db: -7.0,
+            noise_figure_db: 10.0,
+            ..Default::default()
+        },
+        Block {
+            name: "IF Amp".to_string(),
+            gain_db: 20.0,
+            noise_figure_db: 4.0,
+            ..Default::default()
+        },
+    ];
+    let node = cascade_vector_return_output(input, blocks);
+
+    let expected = [
+        ("Input", 290.0, 56.1),
+        ("LNA", 75.09, 14.5),
+        ("Mixer", 82.54, 16.0),
+        ("IF Amp", 69.49, 13.4),
+    ];
+    assert_eq!(node.noise_contributions.len(), expected.len());
+    for (c, (name, kelvin, percent)) in node.noise_contributions.iter().zip(expected) {
+        assert_eq!(c.name, name);
+        assert!((c.noise_temperature_k - kelvin).abs() < 0.01, "{c}");
+        assert!((c.percent - percent).abs() < 0.05, "{c}");
+    }
+
+    // The mixer adds more noise than the LNA itself
+    let worst_stage = node.noise_contributions[1..]
+        .iter()
+        .max_by(|a, b| a.noise_temperature_k.total_cmp(&b.noise_temperature_k))
+        .unwrap();
+    assert_eq!(worst_stage.name, "Mixer");
+}
@@ -0,0 +1,56 @@
+//! README example: Linearity Budget (Who Limits OIP3/P1dB)
+
+use gainlineup::{cascade_vector_return_vector, Block, Input};
+
+#[test]
+fn linearity_budget_names_limiting_stage() {
+    let input = Input::new(1.0e9, 1.0e6, -60.0, None);
+    let blocks = vec![
+        Block {
+            name: "LNA".to_string(),
+            gain_db: 20.0,
+            output_ip3_dbm: Some(30.0),
+            output_p1db_dbm: Some(20.0),
+            ..Default::default()
+        },
+        Block {
+            name: "Mixer".to_string(),
+            gain_db: -8.0,
+            output_ip3_dbm: Some(15.0),
+            output_p1db_dbm: Some(5.0),
+            ..Default::default()
+        },
+    ];
+    let nodes = cascade_vector_return_vector(input, blocks);
+
+    // After the LNA it is the only contributor
+    assert_eq!(nodes[0].oip3_limiting_stage(), Some("LNA"));
+    assert_eq!(nodes[0].linearity_contributions[0].oip3_percent, Some(100.0));
+
+    // After the mixer, the mixer dominates both
+    let node = &nodes[1];
+    assert_eq!(node.oip3_limiting_stage(), Some("Mixer"));
+    assert_eq!(node.p1db_limiting_stage.as_deref(), Some("Mixer"));
+
+    // the LNA's +30 dBm OIP3 is +22 dBm at the mixer output, 7 dB above the mixer's
+    let lna = &node.linearity_contributions[0];
+    assert_eq!(lna.oip3_dbm, Some(22.0));
+    assert!((lna.oip3_percent.unwrap() - 16.6).abs() < 0.1);
+    let mixer = &node.linearity_contributions[1];
+    assert!(mixer.oip3_percent.unwrap() > 80.0);
+    assert!(mixer.p1db_percent.unwrap() > 80.0);
+
+    // shares add up to 100 %
+    let oip3_total: f64 = node
+        .linearity_contributions
+        .iter()
+        .filter_map(|c| c.oip3_percent)
+        .sum();
+    let p1db_total: f64 = node
+        .linearity_contributions
+        .iter()
+        .filter_map(|c| c.p1db_percent)
+        .sum();
+    assert!((oip3_total - 100.0).abs() < 1e-9);
+    assert!((p1db_total - 100.0).abs() < 1e-9);
+}
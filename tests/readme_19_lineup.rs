@@ -0,0 +1,49 @@
+//! README example: Editing a Lineup
+
+use gainlineup::{cascade_vector_return_vector, Block, Input, Lineup};
+
+fn block(name: &str, gain_db: f64, noise_figure_db: f64) -> Block {
+    Block {
+        name: name.to_string(),
+        gain_db,
+        noise_figure_db,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn lineup_edits_match_full_cascade() {
+    let input = Input::new(2.4e9, 20.0e6, -60.0, Some(290.0));
+    let mut lineup = Lineup::new(
+        input.clone(),
+        vec![block("LNA", 20.0, 1.0), block("Amp", 15.0, 4.0)],
+    );
+    assert_eq!(lineup.output().unwrap().signal_power_dbm, -25.0);
+
+    // add a 3 dB pad between the two amplifiers
+    assert_eq!(lineup.insert_after("LNA", block("Pad", -3.0, 3.0)), Some(1));
+    assert_eq!(lineup.output().unwrap().signal_power_dbm, -28.0);
+
+    // tweak a stage in place
+    assert!(lineup.update_by_name("Amp", |amp| amp.gain_db = 18.0));
+    assert_eq!(lineup.output().unwrap().signal_power_dbm, -25.0);
+    let nf_pad_after = lineup.output().unwrap().cumulative_noise_figure_db;
+
+    // move the pad in front of the LNA: same gain, worse noise figure
+    assert!(lineup.move_by_name("Pad", 0));
+    let output = lineup.output().unwrap();
+    assert_eq!(output.signal_power_dbm, -25.0);
+    assert!(output.cumulative_noise_figure_db > nf_pad_after + 2.5);
+    assert_eq!(lineup.node_by_name("LNA").unwrap().signal_power_dbm, -43.0);
+
+    // the cached nodes agree with cascading from scratch
+    let expected = cascade_vector_return_vector(input, lineup.blocks().to_vec());
+    for (node, expected) in lineup.nodes().iter().zip(expected.iter()) {
+        assert_eq!(node.name, expected.name);
+        assert_eq!(node.signal_power_dbm, expected.signal_power_dbm);
+        assert_eq!(
+            node.cumulative_noise_figure_db,
+            expected.cumulative_noise_figure_db
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! README example: Errors
+
+use std::path::PathBuf;
+
+use gainlineup::{cli, GainlineupError};
+
+#[test]
+fn missing_touchstone_frequency_is_typed() {
+    let err = cli::load_config("files/touchstone_invalid_frequency/config.toml").unwrap_err();
+    match err {
+        GainlineupError::TouchstoneFrequencyNotFound { path, frequency_hz } => {
+            // the path is reported as written in the config file
+            assert_eq!(path, PathBuf::from("ntwk3.s2p"));
+            assert_eq!(frequency_hz, 11.0e9);
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+}
+
+#[test]
+fn missing_config_is_io_error() {
+    let err = cli::load_config("files/no_such_config.toml").unwrap_err();
+    assert!(matches!(err, GainlineupError::Io { .. }), "{}", err);
+    assert!(std::error::Error::source(&err).is_some());
+}
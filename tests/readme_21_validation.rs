@@ -0,0 +1,54 @@
+//! README example: Validating a Lineup
+
+use gainlineup::{validate_lineup, Block, Input, Lineup, Severity};
+
+#[test]
+fn validation_reports_errors_and_warnings() {
+    let input = Input::new(1.0e9, 0.0, -30.0, Some(290.0));
+    let pad = Block {
+        name: "Pad".to_string(),
+        gain_db: -6.0,
+        noise_figure_db: 2.0,
+        ..Default::default()
+    };
+
+    let findings = validate_lineup(&input, std::slice::from_ref(&pad));
+    assert_eq!(findings.len(), 2);
+
+    assert_eq!(findings[0].severity, Severity::Error);
+    assert_eq!(findings[0].block, None);
+    assert_eq!(findings[0].parameter, "bandwidth_hz");
+    assert!(findings[0]
+        .to_string()
+        .starts_with("error: bandwidth_hz: 0 Hz gives no noise power"));
+
+    assert_eq!(findings[1].severity, Severity::Warning);
+    assert_eq!(findings[1].block.as_deref(), Some("Pad"));
+    assert!(findings[1].to_string().starts_with(
+        "warning: block 'Pad' noise_figure_db: 2.00 dB differs from the 6.00 dB loss"
+    ));
+
+    // fixing both leaves a clean lineup
+    let input = Input::new(1.0e9, 100.0, -30.0, Some(290.0));
+    let pad = Block {
+        noise_figure_db: 6.0,
+        ..pad
+    };
+    assert!(Lineup::new(input, vec![pad]).validate().is_empty());
+}
+
+#[test]
+fn overdriven_stage_is_a_warning() {
+    let input = Input::new(1.0e9, 1.0e6, -10.0, Some(290.0));
+    let amp = Block {
+        name: "Amp".to_string(),
+        gain_db: 30.0,
+        noise_figure_db: 3.0,
+        output_p1db_dbm: Some(10.0),
+        ..Default::default()
+    };
+    let findings = validate_lineup(&input, &[amp]);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Warning);
+    assert!(findings[0].message.contains("10.0 dB past its 10 dBm P1dB"));
+}
@@ -0,0 +1,72 @@
+//! README example: Mismatch (VSWR and S-Parameter Cascade)
+
+use gainlineup::{
+    cascade_s_parameters, cascade_vector_return_output, cascade_vector_with_mismatch,
+    mismatch_interfaces, Block, Input,
+};
+
+fn lineup() -> Vec<Block> {
+    let lna = Block::builder("LNA")
+        .gain_db(20.0)
+        .noise_figure_db(1.0)
+        .output_vswr(1.8)
+        .build()
+        .unwrap();
+    let filter = Block::builder("Filter")
+        .gain_db(-2.0)
+        .noise_figure_db(2.0)
+        .input_vswr(2.0)
+        .output_vswr(2.0)
+        .build()
+        .unwrap();
+    let amp = Block::builder("Amp")
+        .gain_db(15.0)
+        .noise_figure_db(4.0)
+        .input_vswr(1.5)
+        .build()
+        .unwrap();
+    vec![lna, filter, amp]
+}
+
+#[test]
+fn mismatch_uncertainty_per_interface() {
+    let blocks = lineup();
+
+    let interfaces = mismatch_interfaces(&blocks);
+    assert_eq!(interfaces.len(), 2);
+    for interface in &interfaces {
+        println!(
+            "{} -> {}: {:+.2} dB ({:+.2} to {:+.2} dB)",
+            interface.from,
+            interface.to,
+            interface.mismatch_db,
+            interface.uncertainty_min_db,
+            interface.uncertainty_max_db
+        );
+        assert!(interface.uncertainty_min_db < 0.0);
+        assert!(interface.uncertainty_max_db > 0.0);
+    }
+    // |Γ| = 0.286 and 0.333: −20·log10(1 ∓ 0.095)
+    assert_eq!(interfaces[0].from, "LNA");
+    assert!((interfaces[0].uncertainty_max_db - 0.869).abs() < 1e-3);
+    assert!((interfaces[0].uncertainty_min_db - (-0.791)).abs() < 1e-3);
+
+    // |S21|² of the whole lineup includes every interface
+    let s = cascade_s_parameters(&blocks);
+    let total_mismatch_db: f64 = interfaces.iter().map(|i| i.mismatch_db).sum();
+    assert!((s.gain_db() - (33.0 + total_mismatch_db)).abs() < 1e-9);
+}
+
+#[test]
+fn cascade_with_mismatch_gain() {
+    let input = Input::new(6.0e9, 1.0e6, -60.0, Some(290.0));
+    let blocks = lineup();
+
+    let matched = cascade_vector_return_output(input.clone(), blocks.clone());
+    let nodes = cascade_vector_with_mismatch(input, blocks.clone());
+    let output = nodes.last().unwrap();
+
+    assert_eq!(matched.cumulative_gain_db, 33.0);
+    assert!((output.cumulative_gain_db - cascade_s_parameters(&blocks).gain_db()).abs() < 1e-9);
+    assert!((output.signal_power_dbm - (-60.0 + output.cumulative_gain_db)).abs() < 1e-9);
+}
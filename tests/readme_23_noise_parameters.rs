@@ -0,0 +1,35 @@
+//! README example: Noise Parameters (Active Devices)
+
+use gainlineup::{cli, Complex, NoiseParameters};
+
+#[test]
+fn noise_figure_depends_on_source_reflection() {
+    let lna = NoiseParameters {
+        frequency_hz: 3.0e9,
+        minimum_noise_figure_db: 0.5,
+        optimum_reflection: Complex::from_polar_deg(0.47, 125.0),
+        normalized_noise_resistance: 0.17, // Rn / 50 Ω
+    };
+
+    let from_50_ohm = lna.noise_figure_db(Complex::ZERO);
+    let matched = lna.noise_figure_db(lna.optimum_reflection);
+    println!("NF from 50 Ω: {:.2} dB", from_50_ohm);
+    println!("NF noise-matched: {:.2} dB", matched);
+
+    assert!((from_50_ohm - 1.28).abs() < 0.01);
+    assert!((matched - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn touchstone_lna_noise_figure_from_file() {
+    let config = cli::load_config("files/touchstone_noise/config.toml").unwrap();
+
+    // the first LNA sees 50 Ω, the second sees the filter's 1.8:1 output
+    let first = &config.blocks[0];
+    let second = &config.blocks[2];
+    println!("{}: NF {:.2} dB", first.name, first.noise_figure_db);
+    println!("{}: NF {:.2} dB", second.name, second.noise_figure_db);
+
+    assert!((first.noise_figure_db - 1.28).abs() < 0.01);
+    assert!(second.noise_figure_db > first.noise_figure_db);
+}
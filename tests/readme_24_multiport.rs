@@ -0,0 +1,35 @@
+//! README example: Multi-Port Files
+
+use gainlineup::{cli, Complex, SParameters};
+
+#[test]
+fn splitter_path_with_unused_port_terminated() {
+    // an ideal 3 dB splitter with a little leakage between its outputs
+    let k = Complex::new(1.0 / 2.0_f64.sqrt(), 0.0);
+    let leak = Complex::new(0.05, 0.0);
+    let z = Complex::ZERO;
+    let splitter = vec![vec![z, k, k], vec![k, z, leak], vec![k, leak, z]];
+
+    let matched = SParameters::from_multiport(&splitter, 1, 3, Complex::ZERO).unwrap();
+    let open = SParameters::from_multiport(&splitter, 1, 3, Complex::ONE).unwrap();
+    println!("Port 1 -> 3, port 2 matched: {:.2} dB", matched.gain_db());
+    println!("Port 1 -> 3, port 2 open: {:.2} dB", open.gain_db());
+
+    assert!((matched.gain_db() - (-3.01)).abs() < 0.01);
+    // the open port reflects its share back, some of it leaking to port 3
+    assert!(open.gain_db() > matched.gain_db());
+
+    // reverse direction
+    let reverse = SParameters::from_multiport(&splitter, 3, 1, Complex::ZERO).unwrap();
+    assert!((reverse.gain_db() - matched.gain_db()).abs() < 1e-12);
+}
+
+#[test]
+fn multiport_touchstone_blocks() {
+    let config = cli::load_config("files/touchstone_multiport/config.toml").unwrap();
+    for block in &config.blocks {
+        println!("{}: {:.2} dB", block.name, block.gain_db);
+    }
+    assert_eq!(config.blocks.len(), 3);
+    assert!((config.blocks[0].gain_db - 20.0 * 0.69_f64.log10()).abs() < 1e-9);
+}
@@ -0,0 +1,35 @@
+//! README example: Touchstone Linearity
+
+use gainlineup::{cascade_vector_return_output, cli, AmplifierModel, Input};
+
+#[test]
+fn touchstone_blocks_take_linearity_specs() {
+    let config = cli::load_config("files/touchstone_options/config.toml").unwrap();
+    for block in &config.blocks {
+        println!("{}", block);
+    }
+
+    let first = &config.blocks[0];
+    assert_eq!(first.output_p1db_dbm, Some(20.0));
+    assert_eq!(first.output_ip3_dbm, Some(30.0));
+    // no P1dB means linear, as for explicit blocks
+    assert!(config.blocks[2].output_p1db_dbm.is_none());
+
+    let input = Input::new(
+        config.frequency_hz,
+        1.0e6,
+        config.input_power_dbm,
+        Some(290.0),
+    );
+    let output = cascade_vector_return_output(input, vec![first.clone()]);
+    println!("Cascaded OIP3: {:?} dBm", output.cumulative_oip3_dbm);
+    println!("SFDR: {:?} dB", output.sfdr_db);
+    assert!(output.cumulative_oip3_dbm.is_some());
+    assert!(output.sfdr_db.is_some());
+
+    // the AM-PM coefficient carries over to the amplifier model
+    let mut pa = first.clone();
+    pa.am_pm_coefficient_deg_per_db = Some(3.0);
+    let model = AmplifierModel::new(&pa);
+    assert_eq!(model.am_pm_coefficient_deg_per_db, Some(3.0));
+}
@@ -0,0 +1,65 @@
+//! README example: Phase Noise (LO Chains)
+
+use gainlineup::{
+    cascade_vector_return_output, cascade_vector_return_vector, cli, Block, Injection, Input,
+    Mixer, MixerNoiseFigure, PhaseNoise,
+};
+
+#[test]
+fn lo_chain_phase_noise_on_nodes() {
+    // 1.425 GHz synthesizer, ×4 to a 5.7 GHz LO (+12 dB)
+    let synthesizer = PhaseNoise::new(&[(1.0e3, -100.0), (1.0e5, -110.0), (1.0e7, -150.0)]);
+    let lo = synthesizer.multiplied(4.0);
+    assert!((lo.level_at(1.0e5).unwrap() - (-97.96)).abs() < 0.01);
+
+    let mixer = Block::mixer(
+        "Mixer",
+        -7.0,
+        MixerNoiseFigure::Ssb(7.0),
+        Mixer::new(5.7e9, Injection::LowSide).with_lo_phase_noise(lo.clone()),
+    );
+    let input = Input::new(5.8e9, 20.0e6, -70.0, Some(290.0));
+    let output = cascade_vector_return_output(input.clone(), vec![mixer.clone()]);
+
+    let phase_error_deg = output.rms_phase_error_deg(1.0e3, 10.0e6).unwrap();
+    let jitter_s = output.rms_jitter_s(1.0e3, 10.0e6).unwrap();
+    println!("RMS phase error: {:.3} deg", phase_error_deg);
+    println!("RMS jitter at 100 MHz IF: {:.3} ps", jitter_s * 1.0e12);
+    let expected_deg = lo.rms_phase_error_deg(1.0e3, 10.0e6).unwrap();
+    assert!((phase_error_deg - expected_deg).abs() < 1e-12);
+    assert!(
+        (jitter_s - expected_deg.to_radians() / (2.0 * std::f64::consts::PI * 100.0e6)).abs()
+            < 1e-18
+    );
+
+    // -30 dBm blocker 1 MHz away: -30 - 7 + L(1 MHz) + 10·log10(20 MHz)
+    let blocker_noise_dbm = output.reciprocal_mixing_noise_dbm(-30.0, 1.0e6).unwrap();
+    let expected_dbm = -37.0 + lo.level_at(1.0e6).unwrap() + 10.0 * 20.0e6_f64.log10();
+    assert!((blocker_noise_dbm - expected_dbm).abs() < 1e-9);
+
+    // the source's phase noise adds to the LO's, but not to reciprocal mixing
+    let source = PhaseNoise::new(&[(1.0e3, -100.0), (1.0e7, -150.0)]);
+    let nodes = cascade_vector_return_vector(input.with_phase_noise(source), vec![mixer]);
+    assert!(nodes[0].rms_phase_error_deg(1.0e3, 10.0e6).unwrap() > phase_error_deg);
+    assert_eq!(nodes[0].lo_phase_noise, Some(lo));
+}
+
+#[test]
+fn phase_noise_from_toml() {
+    let config = cli::load_config("files/phase_noise/config.toml").unwrap();
+    let input = Input::new(
+        config.frequency_hz,
+        config.bandwidth_hz.unwrap(),
+        config.input_power_dbm,
+        Some(290.0),
+    )
+    .with_phase_noise(config.phase_noise.clone().unwrap());
+    let nodes = cascade_vector_return_vector(input, config.blocks);
+
+    // the LNA passes the source phase noise through; the mixer adds the LO's
+    assert!(nodes[0].lo_phase_noise.is_none());
+    let before = nodes[0].rms_phase_error_deg(1.0e3, 10.0e6).unwrap();
+    let after = nodes[2].rms_phase_error_deg(1.0e3, 10.0e6).unwrap();
+    println!("RMS phase error: {:.3} deg -> {:.3} deg", before, after);
+    assert!(after > before);
+}
@@ -0,0 +1,83 @@
+//! README example: Phase and Group Delay
+
+use gainlineup::{cascade_vector_return_output, cli, Block, Input};
+
+fn pa_chain(cable_phase_deg: f64) -> Vec<Block> {
+    vec![
+        Block::builder("Cable")
+            .gain_db(-0.5)
+            .noise_figure_db(0.5)
+            .phase_deg(cable_phase_deg)
+            .group_delay_s(2.0e-9)
+            .build()
+            .unwrap(),
+        Block::builder("Driver")
+            .gain_db(15.0)
+            .noise_figure_db(4.0)
+            .output_p1db_dbm(25.0)
+            .am_pm_coefficient_deg_per_db(2.0)
+            .phase_deg(20.0)
+            .group_delay_s(0.8e-9)
+            .build()
+            .unwrap(),
+        Block::builder("PA")
+            .gain_db(12.0)
+            .noise_figure_db(6.0)
+            .output_p1db_dbm(34.0)
+            .am_pm_coefficient_deg_per_db(5.0)
+            .phase_deg(-60.0)
+            .group_delay_s(1.2e-9)
+            .build()
+            .unwrap(),
+    ]
+}
+
+#[test]
+fn pa_chain_phase_depends_on_drive() {
+    let mut previous_am_pm = 0.0;
+    for input_power_dbm in [-10.0, 0.0, 5.0, 10.0, 15.0] {
+        let input = Input::new(2.4e9, 20.0e6, input_power_dbm, None);
+        let output = cascade_vector_return_output(input, pa_chain(-90.0));
+        println!(
+            "Pin {:>5.1} dBm: phase {:>7.2} deg (AM-PM {:>5.2} deg), delay {:.1} ns",
+            input_power_dbm,
+            output.cumulative_phase_deg,
+            output.cumulative_am_pm_deg,
+            output.cumulative_group_delay_s * 1.0e9
+        );
+        assert!(output.cumulative_am_pm_deg >= previous_am_pm);
+        assert!((output.cumulative_group_delay_s - 4.0e-9).abs() < 1e-18);
+        previous_am_pm = output.cumulative_am_pm_deg;
+    }
+
+    let backed_off =
+        cascade_vector_return_output(Input::new(2.4e9, 20.0e6, -10.0, None), pa_chain(-90.0));
+    assert_eq!(backed_off.cumulative_am_pm_deg, 0.0);
+    assert!((backed_off.cumulative_phase_deg - (-130.0)).abs() < 1e-9);
+    assert!(previous_am_pm > 10.0);
+}
+
+#[test]
+fn channel_to_channel_phase() {
+    let input = Input::new(2.4e9, 20.0e6, -10.0, None);
+    let channel_a = cascade_vector_return_output(input.clone(), pa_chain(-90.0));
+    let channel_b = cascade_vector_return_output(input, pa_chain(-97.5));
+    let difference_deg = channel_a.cumulative_phase_deg - channel_b.cumulative_phase_deg;
+    println!("Channel A - B: {:.1} deg", difference_deg);
+    assert!((difference_deg - 7.5).abs() < 1e-9);
+}
+
+#[test]
+fn touchstone_phase_and_group_delay() {
+    let config = cli::load_config("files/touchstone_options/config.toml").unwrap();
+    let block = &config.blocks[0];
+    let phase_deg = block.phase_deg.unwrap();
+    let group_delay_s = block.group_delay_s.unwrap();
+    println!(
+        "S21 phase {:.1} deg, group delay {:.3} ns",
+        phase_deg,
+        group_delay_s * 1.0e9
+    );
+    assert!((phase_deg - block.two_port().s21.arg_deg()).abs() < 1e-9);
+    assert!(group_delay_s > 0.0);
+}
@@ -0,0 +1,59 @@
+//! README example: Harmonics (Filter Planning)
+
+use gainlineup::{cascade_vector_return_vector, cli, Block, Filter, Input};
+
+#[test]
+fn harmonics_through_a_transmitter() {
+    let driver = Block::builder("Driver")
+        .gain_db(20.0)
+        .output_p1db_dbm(24.0)
+        .output_h2_intercept_dbm(45.0)
+        .output_h3_intercept_dbm(35.0)
+        .build()
+        .unwrap();
+    // datasheet: -25 dBc H2 and -35 dBc H3 at +28 dBm out
+    let pa = Block::builder("Power Amplifier")
+        .gain_db(15.0)
+        .output_p1db_dbm(33.0)
+        .second_harmonic_dbc(-25.0, 28.0)
+        .third_harmonic_dbc(-35.0, 28.0)
+        .build()
+        .unwrap();
+    let harmonic_filter = Block::filter(
+        "Harmonic Filter",
+        0.5,
+        Filter::new(2.45e9, 100.0e6).with_rejection(vec![(1.0e9, 40.0), (3.0e9, 55.0)]),
+    );
+
+    let input = Input::new(2.4e9, 20.0e6, -10.0, None);
+    let nodes = cascade_vector_return_vector(input, vec![driver, pa, harmonic_filter]);
+    for node in &nodes {
+        for harmonic in &node.harmonics {
+            println!(
+                "{}: H{} at {:.1} GHz = {:.1} dBm ({:.1} dBc)",
+                node.name,
+                harmonic.order,
+                harmonic.frequency_hz / 1e9,
+                harmonic.power_dbm,
+                harmonic.power_dbm - node.signal_power_dbm
+            );
+        }
+    }
+
+    let pa_output = &nodes[1];
+    // the driver's H2 (amplified by the PA) and the PA's own add in phase
+    assert!((pa_output.harmonics[0].power_dbm - 0.18).abs() < 0.01);
+    let output = &nodes[2];
+    assert_eq!(output.harmonics[1].frequency_hz, 7.2e9);
+    assert!((output.harmonic_dbc(2).unwrap() - (-74.92)).abs() < 0.01);
+    assert!((output.harmonic_dbc(3).unwrap() - (-93.39)).abs() < 0.01);
+}
+
+#[test]
+fn harmonics_from_toml() {
+    let config = cli::load_config("files/harmonics/config.toml").unwrap();
+    let pa = &config.blocks[1];
+    assert_eq!(pa.output_h2_intercept_dbm, Some(53.0));
+    // +28 dBm out, less 0.08 dB of compression
+    assert!((pa.harmonic_dbc(2, 13.0).unwrap() - (-25.08)).abs() < 0.01);
+}
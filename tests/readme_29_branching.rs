@@ -0,0 +1,95 @@
+//! README example: Splitters, Combiners and Branching Lineups
+
+use gainlineup::{cascade_vector_return_vector, Block, Branch, Input, LineupTree, Severity};
+
+fn amplifier(name: &str, gain_db: f64, noise_figure_db: f64) -> Block {
+    Block::builder(name)
+        .gain_db(gain_db)
+        .noise_figure_db(noise_figure_db)
+        .output_ip3_dbm(30.0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn distribution_network_has_named_outputs() {
+    let trunk = Branch::new("Antenna", vec![amplifier("LNA", 25.0, 1.0)]).split(
+        Block::splitter("4-Way Splitter", 4, 0.5),
+        vec![
+            Branch::new("Receiver A", vec![amplifier("Line Amp A", 10.0, 4.0)]),
+            Branch::new("Receiver B", vec![amplifier("Line Amp B", 10.0, 4.0)]),
+            Branch::new("Monitor", vec![]),
+            Branch::new("Spare", vec![]),
+        ],
+    );
+    let input = Input::new(1.5e9, 1.0e6, -100.0, None);
+    let tree = LineupTree::new(input.clone(), trunk);
+
+    assert_eq!(tree.outputs().len(), 4);
+    assert!(tree.validate().is_empty());
+
+    // each output is the straight cascade along its path
+    let straight = cascade_vector_return_vector(
+        input,
+        vec![
+            amplifier("LNA", 25.0, 1.0),
+            Block::splitter("4-Way Splitter", 4, 0.5),
+            amplifier("Line Amp A", 10.0, 4.0),
+        ],
+    );
+    let receiver_a = tree.output("Receiver A").unwrap();
+    let expected = straight.last().unwrap();
+    assert!((receiver_a.signal_power_dbm - expected.signal_power_dbm).abs() < 1e-9);
+    assert!(
+        (receiver_a.cumulative_noise_figure_db - expected.cumulative_noise_figure_db).abs() < 1e-9
+    );
+
+    // an output without blocks of its own ends at the splitter
+    let monitor = tree.output("Monitor").unwrap();
+    assert_eq!(monitor.name, "4-Way Splitter Output");
+    assert!((monitor.signal_power_dbm - (-100.0 + 25.0 - 6.5206)).abs() < 1e-3);
+    assert_eq!(tree.branch_output("Spare").unwrap().nodes.len(), 2);
+}
+
+#[test]
+fn balanced_amplifier_combines_coherently() {
+    let trunk = Branch::new("Balanced Amplifier", vec![]).combine(
+        Block::splitter("Input Hybrid", 2, 0.2),
+        vec![
+            Branch::new("A", vec![amplifier("Amplifier A", 15.0, 2.0)]),
+            Branch::new("B", vec![amplifier("Amplifier B", 15.0, 2.0)]),
+        ],
+        Block::combiner("Output Hybrid", 2, 0.2),
+    );
+    let tree = LineupTree::new(Input::new(2.0e9, 10.0e6, -40.0, None), trunk);
+    let output = tree.output("Balanced Amplifier").unwrap();
+
+    // the signal recombines in phase: 15 dB less the two excess losses
+    assert!((output.cumulative_gain_db - 14.6).abs() < 1e-9);
+    // the amplifiers' noise is uncorrelated and adds in power, so the noise figure is that
+    // of one amplifier behind the input hybrid's excess loss, plus a little for the output
+    assert!(output.cumulative_noise_figure_db > 2.2 && output.cumulative_noise_figure_db < 2.3);
+
+    // each amplifier's share of the noise is listed under its branch
+    assert!(output
+        .noise_contributions
+        .iter()
+        .any(|contribution| contribution.name == "A: Amplifier A"));
+}
+
+#[test]
+fn too_few_ports_is_an_error() {
+    let trunk = Branch::new("Trunk", vec![]).split(
+        Block::splitter("Splitter", 2, 0.0),
+        vec![
+            Branch::new("A", vec![]),
+            Branch::new("B", vec![]),
+            Branch::new("C", vec![]),
+        ],
+    );
+    let tree = LineupTree::new(Input::new(1.0e9, 1.0e6, -50.0, None), trunk);
+    let findings = tree.validate();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Error);
+    assert_eq!(findings[0].parameter, "ways");
+}
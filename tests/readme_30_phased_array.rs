@@ -0,0 +1,111 @@
+//! README example: Phased Arrays (Element Combining)
+
+use gainlineup::{Block, ChannelErrors, Input, PhasedArray, SignalNode};
+
+fn element_channel() -> Vec<Block> {
+    vec![
+        Block::builder("LNA")
+            .gain_db(28.0)
+            .noise_figure_db(1.2)
+            .build()
+            .unwrap(),
+        Block::builder("Phase Shifter")
+            .gain_db(-6.0)
+            .noise_figure_db(6.0)
+            .build()
+            .unwrap(),
+    ]
+}
+
+fn receiver() -> Block {
+    Block::builder("Receiver")
+        .gain_db(30.0)
+        .noise_figure_db(8.0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn array_gain_and_g_over_t() {
+    // 256 elements at 5 dBi looking at a 40 K sky
+    let array = PhasedArray::new(
+        256,
+        element_channel(),
+        Block::combiner("Beamformer", 256, 2.0),
+    )
+    .with_element_gain_dbi(5.0)
+    .with_back_end(vec![receiver()]);
+    let input = Input::new(12.0e9, 10.0e6, -125.0, Some(40.0));
+
+    let element = array.element_nodes(&input);
+    let nodes = array.cascade(&input);
+    let beamformer = &nodes[0];
+    assert_eq!(beamformer.name, "Beamformer Output");
+
+    // identical channels: the system noise temperature is that of one channel (plus about
+    // 1 K from the beamformer's excess loss), and the signal comes out 24 dB further above
+    // the noise
+    let system_temperature = |node: &SignalNode| -> f64 {
+        node.noise_contributions
+            .iter()
+            .map(|c| c.noise_temperature_k)
+            .sum()
+    };
+    let added = system_temperature(beamformer) - system_temperature(&element[1]);
+    assert!(added > 0.0 && added < 1.5, "got {added:.2} K");
+    let improvement_db = beamformer.array_snr_improvement_db().unwrap();
+    assert!(improvement_db > 24.0 && improvement_db < 24.0824, "got {improvement_db:.3} dB");
+
+    // G/T = 5 dBi + 24.1 dB - 10·log10(Tsys)
+    let expected = 5.0 + 24.0824 - 10.0 * system_temperature(beamformer).log10();
+    assert!((beamformer.g_over_t_db().unwrap() - expected).abs() < 1e-3);
+
+    // the receiver's noise after the beamformer costs about half a dB of G/T
+    let output = &nodes[1];
+    let g_over_t_loss = beamformer.g_over_t_db().unwrap() - output.g_over_t_db().unwrap();
+    assert!(g_over_t_loss > 0.4 && g_over_t_loss < 0.6, "got {g_over_t_loss:.2} dB");
+    assert!(output.array.is_some());
+}
+
+#[test]
+fn channel_errors_cost_snr() {
+    let ideal = PhasedArray::new(
+        64,
+        element_channel(),
+        Block::combiner("Beamformer", 64, 0.0),
+    );
+    let errors = ChannelErrors::new(1.0, 10.0);
+    let real = ideal.clone().with_errors(errors);
+    let input = Input::new(12.0e9, 10.0e6, -125.0, Some(40.0));
+
+    let ideal_output = &ideal.cascade(&input)[0];
+    let real_output = &real.cascade(&input)[0];
+    let loss_db = ideal_output.array_snr_improvement_db().unwrap()
+        - real_output.array_snr_improvement_db().unwrap();
+    assert!((loss_db - errors.snr_loss_db(64)).abs() < 1e-9);
+    // about 0.15 dB for 1 dB and 10° RMS
+    assert!(loss_db > 0.1 && loss_db < 0.2, "got {loss_db:.3} dB");
+    assert_eq!(
+        real_output.array.unwrap().error_loss_db,
+        errors.snr_loss_db(64)
+    );
+
+    // the lost coherent gain shows up in the noise budget
+    assert!(real_output.cumulative_noise_figure_db > ideal_output.cumulative_noise_figure_db);
+    assert!(real_output
+        .noise_contributions
+        .iter()
+        .any(|contribution| contribution.name == "Beamformer (combining)"));
+}
+
+#[test]
+fn validation_checks_the_combiner_ports() {
+    let array = PhasedArray::new(
+        64,
+        element_channel(),
+        Block::combiner("Beamformer", 32, 0.0),
+    );
+    let findings = array.validate();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].parameter, "ways");
+}
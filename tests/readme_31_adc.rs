@@ -0,0 +1,73 @@
+//! README example: ADC (Quantization, Jitter and Clipping)
+
+use gainlineup::{cascade_vector_return_vector, validate_lineup, Adc, Block, Input};
+
+fn receiver() -> Vec<Block> {
+    vec![
+        Block::builder("LNA")
+            .gain_db(20.0)
+            .noise_figure_db(1.5)
+            .build()
+            .unwrap(),
+        Block::builder("IF Amplifier")
+            .gain_db(20.0)
+            .noise_figure_db(6.0)
+            .build()
+            .unwrap(),
+    ]
+}
+
+fn converter() -> Adc {
+    // +4 dBm full scale, 11.5 ENOB at 500 MSPS, 100 fs of jitter
+    Adc::from_enob(4.0, 500.0e6, 11.5)
+        .with_sfdr_db(80.0)
+        .with_aperture_jitter_s(100.0e-15)
+}
+
+#[test]
+fn converter_noise_and_processing_gain() {
+    let adc = converter();
+    assert!((adc.snr_db - 70.99).abs() < 0.01);
+    // a 10 MHz channel sees 14 dB less of the Nyquist-zone noise
+    assert!((adc.processing_gain_db(10.0e6) - 13.98).abs() < 0.01);
+    assert!((adc.noise_figure_db() - 23.0).abs() < 0.1);
+
+    let mut blocks = receiver();
+    blocks.push(Block::adc("ADC", adc.clone()));
+    let input = Input::new(100.0e6, 10.0e6, -70.0, Some(290.0)).with_papr_db(10.0);
+    let nodes = cascade_vector_return_vector(input, blocks);
+    let before = &nodes[1];
+    let after = &nodes[2];
+
+    // 40 dB of gain ahead of a 23 dB NF converter: it costs well under a dB of SNR
+    let snr_loss_db = before.signal_to_noise_ratio_db() - after.signal_to_noise_ratio_db();
+    assert!(snr_loss_db > 0.0 && snr_loss_db < 0.5, "got {snr_loss_db:.2} dB");
+    assert_eq!(after.signal_power_dbm, before.signal_power_dbm);
+    assert_eq!(after.sfdr_db, Some(80.0));
+    // -30 dBm with 10 dB PAPR against +4 dBm full scale
+    assert!((after.adc_headroom_db.unwrap() - 24.0).abs() < 1e-9);
+    assert_eq!(after.noise_contributions.last().unwrap().name, "ADC");
+}
+
+#[test]
+fn clipping_is_a_warning() {
+    let mut blocks = receiver();
+    blocks.push(Block::adc("ADC", converter()));
+    let input = Input::new(100.0e6, 10.0e6, -40.0, Some(290.0)).with_papr_db(10.0);
+
+    let findings = validate_lineup(&input, &blocks);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].parameter, "full_scale_dbm");
+    // 0 dBm plus 10 dB PAPR is 6 dB over full scale
+    let nodes = cascade_vector_return_vector(input, blocks);
+    assert!((nodes[2].adc_headroom_db.unwrap() + 6.0).abs() < 1e-9);
+}
+
+#[test]
+fn jitter_matters_near_full_scale() {
+    let adc = converter();
+    // 100 fs at 100 MHz limits the SNR to 84 dB
+    assert!((adc.jitter_snr_db(100.0e6).unwrap() - 84.04).abs() < 0.01);
+    assert!(adc.noise_figure_db_at(0.0, 100.0e6) > adc.noise_figure_db());
+    assert!((adc.noise_figure_db_at(-60.0, 100.0e6) - adc.noise_figure_db()).abs() < 1e-3);
+}
@@ -0,0 +1,76 @@
+//! README example: Noise Folding (Anti-Alias Filtering)
+
+use gainlineup::{cascade_vector_return_output, Adc, Block, Filter, Input};
+
+fn lna() -> Block {
+    Block::builder("LNA")
+        .gain_db(30.0)
+        .noise_figure_db(2.0)
+        .build()
+        .unwrap()
+}
+
+fn anti_alias_filter() -> Block {
+    Block::filter(
+        "Anti-Alias Filter",
+        1.0,
+        Filter::new(20.0e6, 20.0e6).with_rejection(vec![(40.0e6, 40.0), (60.0e6, 70.0)]),
+    )
+}
+
+fn converter(noise_bandwidth_hz: Option<f64>) -> Block {
+    let adc = Adc::new(10.0, 100.0e6, 74.0);
+    match noise_bandwidth_hz {
+        Some(noise_bandwidth_hz) => {
+            Block::adc("ADC", adc.with_noise_bandwidth_hz(noise_bandwidth_hz))
+        }
+        None => Block::adc("ADC", adc),
+    }
+}
+
+fn input() -> Input {
+    Input::new(20.0e6, 1.0e6, -90.0, Some(290.0))
+}
+
+#[test]
+fn wideband_noise_folds_without_a_filter() {
+    // 500 MHz of noise at 100 MSPS: nine other bands fold onto the 20 MHz signal
+    assert_eq!(
+        Adc::new(10.0, 100.0e6, 74.0)
+            .with_noise_bandwidth_hz(500.0e6)
+            .alias_frequencies_hz(20.0e6)
+            .len(),
+        9
+    );
+    let ideal = cascade_vector_return_output(input(), vec![lna(), converter(None)]);
+    let folded = cascade_vector_return_output(input(), vec![lna(), converter(Some(500.0e6))]);
+
+    let degradation_db = folded.alias_snr_degradation_db.unwrap();
+    assert!(degradation_db > 5.0, "got {degradation_db:.2} dB");
+    let snr_loss_db = ideal.signal_to_noise_ratio_db() - folded.signal_to_noise_ratio_db();
+    assert!((snr_loss_db - degradation_db).abs() < 1e-9);
+    assert!(ideal.alias_snr_degradation_db.is_none());
+    assert!(folded.cumulative_noise_figure_db > ideal.cumulative_noise_figure_db);
+}
+
+#[test]
+fn anti_alias_filter_stops_the_folding() {
+    let filtered = cascade_vector_return_output(
+        input(),
+        vec![lna(), anti_alias_filter(), converter(Some(500.0e6))],
+    );
+    let degradation_db = filtered.alias_snr_degradation_db.unwrap();
+    assert!(degradation_db < 0.1, "got {degradation_db:.3} dB");
+
+    // an amplifier between the filter and the converter brings the noise back
+    let driver = Block::builder("ADC Driver")
+        .gain_db(10.0)
+        .noise_figure_db(10.0)
+        .build()
+        .unwrap();
+    let unfiltered_driver = cascade_vector_return_output(
+        input(),
+        vec![lna(), anti_alias_filter(), driver, converter(Some(500.0e6))],
+    );
+    assert!(unfiltered_driver.alias_snr_degradation_db.unwrap() > degradation_db);
+}
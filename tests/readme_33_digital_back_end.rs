@@ -0,0 +1,77 @@
+//! README example: Digital Back End (NCO, Decimation and Rounding)
+
+use gainlineup::{
+    cascade_vector_return_vector, validate_lineup, Adc, Block, DigitalStage, Filter, Input,
+};
+
+fn receiver(demodulator_bits: u32) -> Vec<Block> {
+    vec![
+        Block::builder("LNA")
+            .gain_db(40.0)
+            .noise_figure_db(2.0)
+            .build()
+            .unwrap(),
+        Block::adc("ADC", Adc::from_enob(4.0, 100.0e6, 11.0)),
+        Block::digital(
+            "DDC",
+            0.0,
+            DigitalStage::nco(30.0e6)
+                .with_filter(Filter::new(0.0, 1.0e6).with_rejection(vec![(3.0e6, 90.0)]))
+                .with_decimation(25)
+                .with_output_bits(18),
+        ),
+        Block::digital("AGC", 12.0, DigitalStage::truncation(demodulator_bits)),
+    ]
+}
+
+fn input() -> Input {
+    // a 500 kHz channel at 30 MHz, 10 dB PAPR
+    Input::new(30.0e6, 500.0e3, -80.0, Some(290.0)).with_papr_db(10.0)
+}
+
+#[test]
+fn lineup_continues_in_dbfs() {
+    let nodes = cascade_vector_return_vector(input(), receiver(16));
+    assert!(validate_lineup(&input(), &receiver(16)).is_empty());
+    let adc = &nodes[1];
+    let ddc = &nodes[2];
+    let demodulator = &nodes[3];
+
+    assert!(nodes[0].signal_power_dbfs().is_none());
+    // -40 dBm against +4 dBm full scale
+    assert!((adc.signal_power_dbfs().unwrap() + 44.0).abs() < 1e-9);
+    assert!((demodulator.signal_power_dbfs().unwrap() + 32.0).abs() < 1e-9);
+
+    // the NCO moves the channel to DC and the decimator to 4 MSPS
+    assert_eq!(ddc.signal_frequency_hz, 0.0);
+    assert_eq!(ddc.sample_rate_hz, Some(4.0e6));
+    // 500 kHz in 50 MHz, then in 2 MHz
+    assert!((adc.processing_gain_db().unwrap() - 20.0).abs() < 1e-9);
+    assert!((ddc.processing_gain_db().unwrap() - 6.02).abs() < 0.01);
+
+    // wide words keep the SNR of the converter
+    let loss_db = adc.signal_to_noise_ratio_db() - demodulator.signal_to_noise_ratio_db();
+    assert!(loss_db > 0.0 && loss_db < 0.05, "got {loss_db:.3} dB");
+}
+
+#[test]
+fn narrow_words_cost_snr() {
+    let wide = cascade_vector_return_vector(input(), receiver(16));
+    let narrow = cascade_vector_return_vector(input(), receiver(6));
+    let loss_db = wide[3].signal_to_noise_ratio_db() - narrow[3].signal_to_noise_ratio_db();
+    assert!(loss_db > 1.0, "got {loss_db:.2} dB");
+    assert_eq!(narrow[3].noise_contributions.last().unwrap().name, "AGC");
+}
+
+#[test]
+fn digital_gain_can_overflow() {
+    let mut blocks = receiver(16);
+    blocks.push(Block::digital(
+        "Too Much Gain",
+        30.0,
+        DigitalStage::default(),
+    ));
+    let findings = validate_lineup(&input(), &blocks);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].parameter, "gain_db");
+}
@@ -9,7 +9,13 @@ echo "Updating plots for all top-level TOML configurations..."
 cargo run -- files/defaults_to_cw.toml
 cargo run -- files/wideband.toml
 cargo run -- files/compression/compression_test.toml
+cargo run -- files/compression/compression_models.toml
 cargo run -- files/include_directive/include.toml
 cargo run -- files/touchstone_options/config.toml
+cargo run -- files/touchstone_noise/config.toml
+cargo run -- files/touchstone_multiport/config.toml
+cargo run -- files/superhet/superhet.toml
+cargo run -- files/phase_noise/config.toml
+cargo run -- files/harmonics/config.toml
 
 echo "All plots updated successfully."